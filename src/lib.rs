@@ -1 +1 @@
-pub mod uno_game;
\ No newline at end of file
+pub mod uno_game;
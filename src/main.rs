@@ -1,8 +1,10 @@
 use clap::Parser;
 use std::io::Write;
 use std::path::PathBuf;
+use uno::uno_game::controller::{play_session, play_turn};
+use uno::uno_game::script::run_script;
 use uno::uno_game::ui::ConsoleUI;
-use uno::uno_game::{start_api_server, GameEvent, GameSession, SessionManager, UnoGame};
+use uno::uno_game::{start_api_server, CleanupConfig, ServerConfig, SessionManager, UnoGame};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -10,11 +12,75 @@ struct Args {
     /// Run in server mode (API only)
     #[arg(short, long)]
     server: bool,
+
+    /// Delete sessions not updated for this many days (server mode)
+    #[arg(long)]
+    session_max_age_days: Option<i64>,
+
+    /// Delete completed sessions not updated for this many days (server mode)
+    #[arg(long, requires = "session_max_age_days")]
+    completed_max_age_days: Option<i64>,
+
+    /// How often to look for expired sessions, in hours
+    #[arg(long, default_value_t = 24)]
+    cleanup_interval_hours: u64,
+
+    /// Draw a card for players who take longer than this many seconds over a turn (server mode)
+    #[arg(long)]
+    turn_timeout_secs: Option<i64>,
+
+    /// Allow players to be dealt a fresh hand through the API (server mode)
+    #[arg(long)]
+    allow_redeal: bool,
+
+    /// Save changed games every this many milliseconds instead of after every move (server mode)
+    #[arg(long)]
+    save_interval_ms: Option<u64>,
+
+    /// Let browsers call the API from this origin; repeat or comma-separate for several (server mode)
+    #[arg(long = "cors-origin", value_delimiter = ',')]
+    cors_origins: Vec<String>,
+
+    /// Accept browser requests from any origin, for local development (server mode)
+    #[arg(long)]
+    dev: bool,
+
+    /// Also serve the games in this directory, without ever writing to it (server mode)
+    #[arg(long)]
+    snapshot_dir: Option<PathBuf>,
+
+    /// Refuse new games while this many are in the lobby or in progress (server mode)
+    #[arg(long)]
+    max_active_games: Option<usize>,
+
+    /// Enable the admin routes, such as reloading games edited on disk, behind this bearer token (server mode)
+    #[arg(long)]
+    admin_token: Option<String>,
+
+    /// Number of computer players to add to new games (CLI mode)
+    #[arg(long, default_value_t = 0)]
+    bots: usize,
+
+    /// Run the game commands in this file instead of the interactive menu
+    #[arg(long, conflicts_with = "server")]
+    script: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    if let Some(path) = &args.script {
+        let script = std::fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            std::process::exit(1);
+        });
+        if let Err(e) = run_script(&script, &mut ConsoleUI::new()) {
+            eprintln!("Script failed at {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let sessions_dir = PathBuf::from("sessions");
     let session_manager =
         SessionManager::new(sessions_dir.clone()).expect("Failed to create session manager");
@@ -22,25 +88,41 @@ async fn main() {
     if args.server {
         // Run in server mode
         println!("Starting Uno API server...");
-        if let Err(e) = start_api_server(sessions_dir).await {
+        let config = ServerConfig {
+            session_cleanup: args.session_max_age_days.map(|days| CleanupConfig {
+                interval: std::time::Duration::from_secs(args.cleanup_interval_hours * 60 * 60),
+                max_age: chrono::Duration::days(days),
+                completed_max_age: args.completed_max_age_days.map(chrono::Duration::days),
+            }),
+            turn_timeout: args.turn_timeout_secs.map(chrono::Duration::seconds),
+            allow_redeal: args.allow_redeal,
+            write_behind: args.save_interval_ms.map(std::time::Duration::from_millis),
+            cors_origins: args.cors_origins,
+            dev: args.dev,
+            snapshot_dir: args.snapshot_dir,
+            max_active_games: args.max_active_games,
+            admin_token: args.admin_token,
+        };
+        if let Err(e) = start_api_server(sessions_dir, config).await {
             eprintln!("Failed to start API server: {}", e);
             std::process::exit(1);
         }
     } else {
         // Run in CLI mode
         let mut ui = ConsoleUI::new();
-        run_cli(&session_manager, &mut ui);
+        run_cli(&session_manager, &mut ui, args.bots);
     }
 }
 
-fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI) {
+fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI, bots: usize) {
     loop {
         println!("\nUno Game Session Manager");
         println!("1. Create new game");
         println!("2. List games");
-        println!("3. Load game");
-        println!("4. Delete game");
-        println!("5. Exit");
+        println!("3. Load game (play one turn)");
+        println!("4. Play game (until you quit)");
+        println!("5. Delete game");
+        println!("6. Exit");
         print!("Enter your choice: ");
         std::io::stdout().flush().unwrap();
 
@@ -50,12 +132,19 @@ fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI) {
 
         match choice {
             "1" => {
-                let player_names = ui.get_player_names();
+                let mut player_names = ui.get_player_names_min(2_usize.saturating_sub(bots).max(1));
+                let humans = player_names.len();
+                player_names.extend((1..=bots).map(|i| format!("Bot {}", i)));
                 match UnoGame::new(player_names) {
-                    Ok(game) => match session_manager.create_session(game) {
-                        Ok(session) => println!("Created new game session: {}", session.id),
-                        Err(e) => println!("Failed to create session: {}", e),
-                    },
+                    Ok(mut game) => {
+                        for player in game.players.iter_mut().skip(humans) {
+                            player.is_bot = true;
+                        }
+                        match session_manager.create_session(game) {
+                            Ok(session) => println!("Created new game session: {}", session.id),
+                            Err(e) => println!("Failed to create session: {}", e),
+                        }
+                    }
                     Err(e) => println!("Failed to create game: {:?}", e),
                 }
             }
@@ -72,7 +161,7 @@ fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI) {
                 }
                 Err(e) => println!("Failed to list sessions: {}", e),
             },
-            "3" => {
+            "3" | "4" => {
                 print!("Enter game ID: ");
                 std::io::stdout().flush().unwrap();
                 let mut id = String::new();
@@ -82,12 +171,16 @@ fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI) {
                 match session_manager.load_session(id) {
                     Ok(mut session) => {
                         println!("Loaded game session: {}", session.id);
-                        play_turn(&mut session, session_manager, ui);
+                        if choice == "3" {
+                            play_turn(&mut session, session_manager, ui);
+                        } else {
+                            play_session(&mut session, session_manager, ui);
+                        }
                     }
                     Err(e) => println!("Failed to load session: {}", e),
                 }
             }
-            "4" => {
+            "5" => {
                 print!("Enter game ID to delete: ");
                 std::io::stdout().flush().unwrap();
                 let mut id = String::new();
@@ -99,68 +192,8 @@ fn run_cli(session_manager: &SessionManager, ui: &mut ConsoleUI) {
                     Err(e) => println!("Failed to delete session: {}", e),
                 }
             }
-            "5" => break,
-            _ => println!("Invalid choice. Please enter 1-5."),
-        }
-    }
-}
-
-fn play_turn(session: &mut GameSession, manager: &SessionManager, ui: &mut ConsoleUI) {
-    println!(
-        "\n=== Current Turn: Player {} ===",
-        session.game.current_turn
-    );
-    ui.display_game_state(&session.game);
-
-    let player_name = session.game.players[session.game.current_turn].name.clone();
-    let player = &session.game.players[session.game.current_turn];
-    ui.display_player_hand(&player.name, &player.hand);
-
-    let choice = ui.get_player_action();
-    let event = match choice.as_str() {
-        "1" => {
-            let (index, color) = match ui.get_card_play(&player.hand[0]) {
-                Ok(result) => result,
-                Err(e) => {
-                    println!("{}", e);
-                    return;
-                }
-            };
-
-            if let Some(color) = color {
-                session.game.players[session.game.current_turn].hand[index].color = color;
-            }
-
-            match session.game.play_card(session.game.current_turn, index) {
-                Ok(event) => event,
-                Err(e) => {
-                    println!("Error: {:?}", e);
-                    return;
-                }
-            }
-        }
-        "2" => match session.game.draw_card(session.game.current_turn) {
-            Ok(event) => event,
-            Err(e) => {
-                println!("Error: {:?}", e);
-                return;
-            }
-        },
-        _ => {
-            println!("Invalid choice. Please enter 1 or 2.");
-            return;
+            "6" => break,
+            _ => println!("Invalid choice. Please enter 1-6."),
         }
-    };
-
-    ui.handle_game_event(&event, &session.game);
-
-    if let GameEvent::PlayerWins { player_id: _ } = event {
-        println!("Game Over! Player {} wins!", player_name);
-        return;
-    }
-
-    session.game.next_turn();
-    if let Err(e) = session.save(&manager.sessions_dir) {
-        println!("Failed to save game state: {}", e);
     }
 }
@@ -1,29 +1,293 @@
-use super::card::{Card, CardType, Color};
-use super::game::GameStatus;
-use crate::uno_game::{GameSession, SessionManager, UnoGame};
+use super::auth;
+use super::bot;
+use super::card::{Card, Color};
+use super::game::{Action, GameError, GameEvent, GameStatus, ReshuffleLog, Standing};
+use super::metrics::Metrics;
+use super::openapi;
+use super::replay;
+use crate::uno_game::{
+    GameConfig, GameSession, PlayerStats, SessionManager, SessionQuery, SessionSummary,
+    StatusFilter, UnoGame,
+};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{delete, get, post},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse,
+    },
+    routing::{delete, get, patch, post},
     Json, Router,
 };
-use log::{error, info};
+use futures_util::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 use tower_http::{
     cors::CorsLayer,
-    trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer},
+    limit::RequestBodyLimitLayer,
+    sensitive_headers::SetSensitiveRequestHeadersLayer,
+    trace::{DefaultOnResponse, TraceLayer},
 };
+use tracing::{error, info, instrument, warn};
 
 #[derive(Clone)]
 pub struct AppState {
     session_manager: SessionManager,
+    updates: GameUpdates,
+    turn_timeout: Option<chrono::Duration>,
+    metrics: Metrics,
+    allow_redeal: bool,
+    max_active_games: Option<usize>,
+    /// The hash of the token admin routes require; they are off when `None`.
+    admin_token_hash: Option<String>,
+}
+
+/// Largest request body accepted; bigger ones get `413 Payload Too Large` before being read.
+/// Generous enough for importing an exported game or replaying a long one.
+const MAX_BODY_BYTES: usize = 256 * 1024;
+
+/// Number of discards listed in the game state's `recent_discards`.
+const RECENT_DISCARDS: usize = 5;
+
+/// Seconds a client is asked to wait before creating a game again when the server is full.
+const FULL_RETRY_AFTER_SECS: u64 = 30;
+
+/// Number of unread updates a subscriber may fall behind before it skips ahead.
+const UPDATE_CHANNEL_CAPACITY: usize = 16;
+
+/// One broadcast channel per game id, carrying the full state after each change.
+#[derive(Clone, Default)]
+pub struct GameUpdates {
+    channels: Arc<Mutex<HashMap<String, broadcast::Sender<GameStateResponse>>>>,
+}
+
+impl GameUpdates {
+    fn subscribe(&self, id: &str) -> broadcast::Receiver<GameStateResponse> {
+        let mut channels = self.channels.lock().unwrap();
+        channels
+            .entry(id.to_string())
+            .or_insert_with(|| broadcast::channel(UPDATE_CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Pushes the session's current state to anyone watching it.
+    fn publish(&self, session: &GameSession) {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(sender) = channels.get(&session.id) {
+            if sender
+                .send(GameStateResponse::from_session(session))
+                .is_err()
+            {
+                // Every subscriber has gone away
+                channels.remove(&session.id);
+            }
+        }
+    }
+
+    fn remove(&self, id: &str) {
+        self.channels.lock().unwrap().remove(id);
+    }
+}
+
+/// The JSON body of every error response: a human-readable message plus a stable code
+/// clients can branch on.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    error: String,
+    code: String,
+    /// Seconds for the client to wait before retrying, sent as `Retry-After`.
+    #[serde(skip)]
+    retry_after: Option<u64>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: message.into(),
+            code: code.to_string(),
+            retry_after: None,
+        }
+    }
+
+    fn bad_request(code: &str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, message)
+    }
+
+    fn forbidden(code: &str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, code, message)
+    }
+
+    fn not_found(e: impl std::fmt::Display) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NOT_FOUND", e.to_string())
+    }
+
+    /// A session that couldn't be loaded: 404 if it doesn't exist, otherwise 500,
+    /// e.g. for a file that is corrupt.
+    fn from_load_error(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => Self::not_found(e),
+            _ => Self::internal(format!("Game could not be loaded: {}", e)),
+        }
+    }
+
+    fn internal(e: impl std::fmt::Display) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            e.to_string(),
+        )
+    }
+}
+
+impl From<GameError> for ApiError {
+    fn from(e: GameError) -> Self {
+        let code = match e {
+            GameError::InvalidMove | GameError::UnplayableCard(_) => "INVALID_MOVE",
+            GameError::NotYourTurn => "NOT_YOUR_TURN",
+            GameError::CardNotInHand => "CARD_NOT_IN_HAND",
+            GameError::GameAlreadyOver => "GAME_ALREADY_OVER",
+            GameError::NotStarted => "GAME_NOT_STARTED",
+            GameError::EmptyDeck => "EMPTY_DECK",
+            GameError::EmptyDiscardPile => return Self::internal(e),
+            GameError::Other(_) => "GAME_ERROR",
+        };
+        Self::bad_request(code, e.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        let mut response = (self.status, Json(&self)).into_response();
+        if let Some(seconds) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
+    }
 }
 
 #[derive(Deserialize)]
 pub struct CreateGameRequest {
     player_names: Vec<String>,
+    /// Deals reproducibly, so the game can later be checked with `/games/import-replay`.
+    #[serde(default)]
+    seed: Option<u64>,
+    /// Opens the game in the lobby, so more players can join before it is started.
+    #[serde(default)]
+    lobby: bool,
+    /// Ids of the players the server plays for with `/games/{id}/advance-bots`.
+    #[serde(default)]
+    bots: Vec<usize>,
+    /// House rules for the game; options left out take their defaults.
+    #[serde(default)]
+    config: GameConfig,
+}
+
+#[derive(Deserialize)]
+pub struct JoinGameRequest {
+    name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReplayRequest {
+    player_names: Vec<String>,
+    seed: u64,
+    events: Vec<GameEvent>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayResponse {
+    steps_applied: usize,
+    state: GameStateResponse,
+    /// Each player's token, by player id.
+    tokens: HashMap<usize, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JoinGameResponse {
+    player_id: usize,
+    /// The new player's token. It is not shown again.
+    token: String,
+    state: GameStateResponse,
+}
+
+#[derive(Deserialize)]
+pub struct ListGamesQuery {
+    #[serde(default)]
+    detailed: bool,
+    status: Option<StatusFilter>,
+    /// Only games with a player whose name contains this, ignoring case.
+    player: Option<String>,
+    /// Games per page, at most `MAX_PAGE_SIZE`.
+    limit: Option<usize>,
+    #[serde(default)]
+    offset: usize,
+}
+
+#[derive(Deserialize)]
+pub struct PurgeGamesQuery {
+    /// Required, so that a bare `DELETE /games` can't remove games in progress.
+    status: Option<StatusFilter>,
+    player: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PurgeGamesResponse {
+    removed: Vec<String>,
+    count: usize,
+}
+
+/// A game the player in `GET /players/{name}/games` is seated in.
+#[derive(Serialize, Deserialize)]
+pub struct PlayerGameResponse {
+    id: String,
+    player_id: usize,
+    /// Whether the game is waiting on this player.
+    your_turn: bool,
+    status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerGamesResponse {
+    games: Vec<PlayerGameResponse>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReloadAllResponse {
+    /// Cached games dropped.
+    dropped: usize,
+}
+
+/// Games listed per page when the request doesn't give a `limit`.
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest `limit` accepted; bigger ones are cut down to this.
+const MAX_PAGE_SIZE: usize = 200;
+
+/// One page of `GET /games`: game ids, or summaries when `detailed` is set.
+#[derive(Serialize, Deserialize)]
+pub struct GamePage<T> {
+    games: Vec<T>,
+    /// Games matching the request across all pages.
+    total: usize,
+    /// The `offset` of the next page, or `None` on the last page.
+    next_offset: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GameSummaryResponse {
+    id: String,
+    player_names: Vec<String>,
+    current_turn: usize,
+    status: String,
+    last_updated: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,11 +295,18 @@ pub struct GameResponse {
     id: String,
     current_turn: usize,
     players: Vec<PlayerResponse>,
-    discard_pile_top: CardResponse,
+    /// `None` in the lobby, before the first card is turned over.
+    discard_pile_top: Option<TopCardResponse>,
     deck_cards_remaining: usize,
     pending_draws: usize,
+    /// Skips stacked on the current player under `skip_stacking`.
+    pending_skips: usize,
     status: String,
     winner: Option<WinnerResponse>,
+    /// The finishing order, once the game is over.
+    standings: Option<Vec<Standing>>,
+    /// Each player's token, by player id. Only sent when the game is created.
+    tokens: Option<HashMap<usize, String>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -43,9 +314,11 @@ pub struct PlayerResponse {
     id: usize,
     name: String,
     hand_size: usize,
+    active: bool,
+    is_bot: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CardResponse {
     color: String,
     card_type: String,
@@ -53,11 +326,83 @@ pub struct CardResponse {
     player_id: Option<usize>,
 }
 
+/// The card on top of the discard pile, plus the color it asks for.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TopCardResponse {
+    color: String,
+    card_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    player_id: Option<usize>,
+    /// The color the next card must match, or `None` while a wild still awaits its color.
+    effective_color: Option<String>,
+    /// True when the top card is a wild that has been given a color.
+    is_wild_active: bool,
+}
+
 #[derive(Deserialize)]
 pub struct PlayCardRequest {
     card_index: usize,
     #[serde(default)]
     color: Option<String>,
+    #[serde(default)]
+    target_player_id: Option<usize>,
+}
+
+/// A move to try with `POST /games/{id}/simulate`, always for the current player.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum SimulateRequest {
+    Play {
+        card_index: usize,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default)]
+        target_player_id: Option<usize>,
+    },
+    Draw,
+}
+
+/// One move in a `POST /games/{id}/moves` batch, made by whoever's turn it is by then.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum MoveRequest {
+    Play {
+        card_index: usize,
+        #[serde(default)]
+        color: Option<String>,
+        #[serde(default)]
+        target_player_id: Option<usize>,
+    },
+    Draw,
+    TakePenalty,
+    Pass,
+    Challenge,
+}
+
+#[derive(Deserialize)]
+pub struct MovesRequest {
+    moves: Vec<MoveRequest>,
+}
+
+/// The events from a batch of moves, in order, and the state after them.
+#[derive(Serialize, Deserialize)]
+pub struct MovesResponse {
+    events: Vec<GameEvent>,
+    state: GameStateResponse,
+}
+
+/// The bot moves made by `/games/{id}/advance-bots`, in order, and the state after them.
+#[derive(Serialize, Deserialize)]
+pub struct AdvanceBotsResponse {
+    events: Vec<GameEvent>,
+    state: GameStateResponse,
+}
+
+/// What a simulated move would do. Nothing is saved.
+#[derive(Serialize, Deserialize)]
+pub struct SimulateResponse {
+    event: GameEvent,
+    state: GameStateResponse,
 }
 
 #[derive(Deserialize)]
@@ -65,80 +410,337 @@ pub struct ChooseColorRequest {
     color: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Deserialize)]
+pub struct RenamePlayerRequest {
+    name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ForfeitRequest {
+    player_id: usize,
+}
+
+#[derive(Deserialize)]
+pub struct RestartQuery {
+    /// Clears every player's running stats instead of carrying them into the new round.
+    #[serde(default)]
+    reset_stats: bool,
+}
+
+#[derive(Deserialize)]
+pub struct KickRequest {
+    player_id: usize,
+}
+
+#[derive(Deserialize)]
+pub struct GameUpdatesQuery {
+    player_id: Option<usize>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameStateResponse {
     id: String,
+    version: u64,
     current_turn: usize,
+    /// Who plays after the current player, allowing for direction and players who have left.
+    next_turn: usize,
     direction: String,
     players: Vec<PlayerStateResponse>,
-    discard_pile_top: CardResponse,
+    /// `None` in the lobby, before the first card is turned over.
+    discard_pile_top: Option<TopCardResponse>,
+    /// The last `RECENT_DISCARDS` cards on the discard pile, most recent first.
+    recent_discards: Vec<CardResponse>,
     deck_cards_remaining: usize,
     pending_draws: usize,
+    /// Skips stacked on the current player under `skip_stacking`.
+    pending_skips: usize,
     status: String,
     winner: Option<WinnerResponse>,
+    /// Seconds left before a card is drawn for the current player, when a turn timer is running.
+    turn_seconds_remaining: Option<i64>,
 }
 
+/// Everything about a game that any onlooker may see: hand sizes, but no cards.
 #[derive(Serialize, Deserialize)]
+pub struct PublicStateResponse {
+    id: String,
+    version: u64,
+    current_turn: usize,
+    direction: String,
+    players: Vec<PlayerResponse>,
+    /// `None` in the lobby, before the first card is turned over.
+    discard_pile_top: Option<TopCardResponse>,
+    deck_cards_remaining: usize,
+    pending_draws: usize,
+    /// Skips stacked on the current player under `skip_stacking`.
+    pending_skips: usize,
+    status: String,
+    winner: Option<WinnerResponse>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PlayerStateResponse {
     id: usize,
     name: String,
     hand: Vec<(usize, CardResponse)>,
+    hand_size: usize,
+    active: bool,
+    is_bot: bool,
+    stats: PlayerStats,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerStatsResponse {
+    id: usize,
+    name: String,
+    stats: PlayerStats,
 }
 
+/// The deck, bottom first: the last card is the next one drawn.
 #[derive(Serialize, Deserialize)]
 pub struct DeckResponse {
     cards: Vec<CardResponse>,
 }
 
+#[derive(Deserialize)]
+pub struct DeckQuery {
+    /// List the deck in the order it will be drawn, next card first.
+    #[serde(default)]
+    reversed: bool,
+}
+
+/// How many of each card are left in the deck, from `GET /games/{id}/deck/composition`.
 #[derive(Serialize, Deserialize)]
+pub struct DeckCompositionResponse {
+    /// Cards left in the deck.
+    total: usize,
+    /// One entry per color and card type still in the deck, by color and then type.
+    cards: Vec<CardCountResponse>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CardCountResponse {
+    color: String,
+    card_type: String,
+    count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct WinnerResponse {
     id: usize,
     name: String,
 }
 
+/// The result of `POST /games/{id}/take-penalty`.
+#[derive(Serialize, Deserialize)]
+pub struct PenaltyResponse {
+    event: GameEvent,
+    /// Fewer than the penalty only if the hand size limit cut it short.
+    cards_drawn: usize,
+    discard_pile_top: TopCardResponse,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DiscardPileResponse {
     cards: Vec<(CardResponse, Option<usize>)>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct HintsResponse {
+    player_id: usize,
+    playable_cards: Vec<(usize, CardResponse)>,
+    must_draw: bool,
+    pending_draws: usize,
+}
+
+/// A card in the current player's hand, flagged if it can be played now.
+#[derive(Serialize, Deserialize)]
+pub struct HandCardResponse {
+    index: usize,
+    card: CardResponse,
+    playable: bool,
+}
+
+/// One card in a player's hand, from `GET /games/{id}/players/{player_id}/hand/{index}`.
+#[derive(Serialize, Deserialize)]
+pub struct CardDetailResponse {
+    index: usize,
+    color: String,
+    card_type: String,
+    /// How the card reads, e.g. "Red 7" or "Wild Draw Four".
+    display: String,
+    points: u32,
+    /// True if it is this player's turn and the card can be played now.
+    playable: bool,
+}
+
+/// The game from the point of view of whoever must act now.
+#[derive(Serialize, Deserialize)]
+pub struct CurrentPlayerResponse {
+    player_id: usize,
+    name: String,
+    hand: Vec<HandCardResponse>,
+    /// The color to match: the top card's, or the one chosen for a wild.
+    effective_color: Option<String>,
+    pending_draws: usize,
+    /// The names of the moves open to the player: any of `play`, `draw`, `pass`,
+    /// `challenge`, `take_penalty` and `choose_color`. Empty unless the game is in progress.
+    actions: Vec<String>,
+    /// The moves open to the player in full, with one `play` per playable card.
+    legal_actions: Vec<Action>,
+}
+
+/// Refuses a new game while `max_active_games` games are unfinished, asking the client to
+/// retry later.
+fn check_game_limit(state: &AppState) -> Result<(), ApiError> {
+    let Some(limit) = state.max_active_games else {
+        return Ok(());
+    };
+    let summaries = state
+        .session_manager
+        .list_sessions_with_meta()
+        .map_err(|e| {
+            error!("Failed to count active games: {}", e);
+            ApiError::internal(e)
+        })?;
+    let active = summaries.iter().filter(|s| !s.status.is_over()).count();
+    if active < limit {
+        return Ok(());
+    }
+    warn!(active, limit, "Refusing new game: server is full");
+    Err(ApiError {
+        retry_after: Some(FULL_RETRY_AFTER_SECS),
+        ..ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SERVER_FULL",
+            format!(
+                "The server already has {} active games; try again later",
+                active
+            ),
+        )
+    })
+}
+
 pub async fn create_game(
     State(state): State<AppState>,
     Json(req): Json<CreateGameRequest>,
 ) -> impl IntoResponse {
     info!("Creating new game with players: {:?}", req.player_names);
-    match UnoGame::new(req.player_names) {
-        Ok(game) => match state.session_manager.create_session(game) {
-            Ok(session) => {
+    if let Err(e) = check_game_limit(&state) {
+        return e.into_response();
+    }
+    let game = match (req.lobby, req.seed) {
+        (true, seed) => UnoGame::new_lobby(req.player_names, req.config, seed),
+        (false, Some(seed)) => {
+            UnoGame::new_with_config_and_seed(req.player_names, req.config, seed)
+        }
+        (false, None) => UnoGame::new_with_config(req.player_names, req.config),
+    };
+    let game = game.and_then(|mut game| {
+        for &player_id in &req.bots {
+            let player = game
+                .players
+                .get_mut(player_id)
+                .ok_or_else(|| GameError::Other(format!("Invalid bot player id: {}", player_id)))?;
+            player.is_bot = true;
+        }
+        Ok(game)
+    });
+    match game {
+        Ok(game) => match state.session_manager.create_session_with_tokens(game) {
+            Ok((session, tokens)) => {
                 info!("Created new game session: {}", session.id);
-                let response = GameResponse::from_session(&session);
+                state.metrics.game_created();
+                let response = GameResponse {
+                    tokens: Some(tokens),
+                    ..GameResponse::from_session(&session)
+                };
                 (StatusCode::CREATED, Json(response)).into_response()
             }
             Err(e) => {
                 error!("Failed to create session: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+                ApiError::internal(e).into_response()
             }
         },
         Err(e) => {
             error!("Failed to create game: {:?}", e);
-            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            ApiError::from(e).into_response()
         }
     }
 }
 
-pub async fn list_games(State(state): State<AppState>) -> impl IntoResponse {
+pub async fn list_games(
+    State(state): State<AppState>,
+    Query(query): Query<ListGamesQuery>,
+) -> impl IntoResponse {
     info!("Listing all games");
-    match state.session_manager.list_sessions() {
-        Ok(sessions) => {
-            info!("Found {} games", sessions.len());
-            Json(sessions).into_response()
-        }
+    let filter = SessionQuery {
+        status: query.status,
+        player: query.player,
+    };
+    let summaries = match state.session_manager.query(filter) {
+        Ok(summaries) => summaries,
         Err(e) => {
             error!("Failed to list sessions: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            return ApiError::internal(e).into_response();
         }
+    };
+    info!("Found {} games", summaries.len());
+
+    let total = summaries.len();
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let end = query.offset.saturating_add(limit);
+    let next_offset = (end < total).then_some(end);
+    let page = summaries.into_iter().skip(query.offset).take(limit);
+    if query.detailed {
+        Json(GamePage {
+            games: page.map(GameSummaryResponse::from_summary).collect(),
+            total,
+            next_offset,
+        })
+        .into_response()
+    } else {
+        Json(GamePage {
+            games: page.map(|summary| summary.id).collect(),
+            total,
+            next_offset,
+        })
+        .into_response()
     }
 }
 
+/// The unfinished games a player is in, those waiting on them first.
+#[instrument(skip_all, fields(player = %name))]
+pub async fn list_player_games(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    info!("Listing games for player: {}", name);
+    let summaries = match state.session_manager.player_games(&name) {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            error!("Failed to list sessions: {}", e);
+            return ApiError::internal(e).into_response();
+        }
+    };
+    let games = summaries
+        .iter()
+        .filter_map(|summary| {
+            Some(PlayerGameResponse {
+                id: summary.id.clone(),
+                player_id: summary.seat_of(&name)?,
+                your_turn: summary.is_turn_of(&name),
+                status: status_label(&summary.status).to_string(),
+            })
+        })
+        .collect();
+    Json(PlayerGamesResponse { games }).into_response()
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
 pub async fn get_game(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
     info!("Getting game with ID: {}", id);
     match state.session_manager.load_session(&id) {
@@ -149,11 +751,12 @@ pub async fn get_game(State(state): State<AppState>, Path(id): Path<String>) ->
         }
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
+#[instrument(skip_all, fields(game_id = %id))]
 pub async fn get_game_state(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -163,458 +766,4378 @@ pub async fn get_game_state(
         Ok(session) => {
             info!("Found game state: {}", id);
             let response = GameStateResponse::from_session(&session);
-            Json(response).into_response()
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
         }
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-pub async fn get_deck(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
-    info!("Getting deck for game ID: {}", id);
-    match state.session_manager.load_session(&id) {
-        Ok(session) => {
-            info!("Found game deck: {}", id);
-            let response = DeckResponse {
-                cards: session
-                    .game
-                    .deck
-                    .iter()
-                    .map(|card| CardResponse::from_card(card, None))
-                    .collect(),
-            };
-            Json(response).into_response()
-        }
+/// The game as it was after the first `move_number` recorded moves, rebuilt by
+/// replaying them from the deal. Move 0 is the deal itself.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_state_at(
+    State(state): State<AppState>,
+    Path((id, move_number)): Path<(String, usize)>,
+) -> impl IntoResponse {
+    info!(move_number, "Getting past state for game ID: {}", id);
+    let session = match state.session_manager.load_session(&id) {
+        Ok(session) => session,
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            return ApiError::from_load_error(e).into_response();
+        }
+    };
+    match session.state_at(move_number) {
+        Some(Ok(game)) => {
+            let past = GameSession {
+                game,
+                turn_deadline: None,
+                ..session
+            };
+            Json(GameStateResponse::from_session(&past)).into_response()
+        }
+        Some(Err(e)) => {
+            warn!("History of game {} can't be replayed: {}", id, e);
+            ApiError::new(
+                StatusCode::CONFLICT,
+                "HISTORY_DIVERGED",
+                format!("The recorded moves can't be replayed: {}", e),
+            )
+            .into_response()
+        }
+        None => {
+            info!(move_number, "No such move in game: {}", id);
+            ApiError::not_found(format!(
+                "Game {} has {} recorded moves",
+                id,
+                session.history.len()
+            ))
+            .into_response()
         }
     }
 }
 
-pub async fn get_discard_pile(
+/// The game as a spectator sees it, with every hand hidden.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_public_state(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    info!("Getting discard pile for game ID: {}", id);
+    info!("Getting public state for game ID: {}", id);
     match state.session_manager.load_session(&id) {
         Ok(session) => {
-            info!("Found game discard pile: {}", id);
-            let response = DiscardPileResponse {
-                cards: session
-                    .game
-                    .discard_pile
-                    .iter()
-                    .map(|(card, player_id)| {
-                        (
-                            CardResponse::from_card(card, None),
-                            if *player_id == usize::MAX {
-                                None
-                            } else {
-                                Some(*player_id)
-                            },
-                        )
-                    })
-                    .collect(),
-            };
-            Json(response).into_response()
+            let response = PublicStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
         }
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-pub async fn delete_game(
+/// Streams the game state as server-sent events: once on connect, then after every change.
+/// With `?player_id=N` the other players' hands are hidden.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn game_updates(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(query): Query<GameUpdatesQuery>,
 ) -> impl IntoResponse {
-    info!("Deleting game with ID: {}", id);
-    match state.session_manager.delete_session(&id) {
-        Ok(_) => {
-            info!("Successfully deleted game: {}", id);
-            StatusCode::NO_CONTENT.into_response()
-        }
+    info!("Subscribing to updates for game ID: {}", id);
+    // Subscribe before loading so no change can slip in between
+    let receiver = state.updates.subscribe(&id);
+    let session = match state.session_manager.load_session(&id) {
+        Ok(session) => session,
         Err(e) => {
-            info!("Failed to delete game: {} - {}", id, e);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            info!("Game not found: {}", id);
+            state.updates.remove(&id);
+            return ApiError::from_load_error(e).into_response();
+        }
+    };
+    if let Some(player_id) = query.player_id {
+        if player_id >= session.game.players.len() {
+            info!("Invalid player {} for game: {}", player_id, id);
+            return ApiError::bad_request(
+                "INVALID_PLAYER",
+                format!("Invalid player id: {}", player_id),
+            )
+            .into_response();
         }
     }
+
+    let initial = GameStateResponse::from_session(&session);
+    Sse::new(state_stream(initial, receiver, query.player_id))
+        .keep_alive(KeepAlive::default())
+        .into_response()
 }
 
-pub async fn play_card(
+fn state_stream(
+    initial: GameStateResponse,
+    receiver: broadcast::Receiver<GameStateResponse>,
+    player_id: Option<usize>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(
+        (Some(initial), receiver),
+        move |(pending, mut receiver)| async move {
+            let state = match pending {
+                Some(state) => state,
+                None => loop {
+                    match receiver.recv().await {
+                        Ok(state) => break state,
+                        // A slow client only needs the newest state
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                },
+            };
+            let state = match player_id {
+                Some(player_id) => state.for_player(player_id),
+                None => state,
+            };
+            let event = Event::default()
+                .event("state")
+                .json_data(&state)
+                .unwrap_or_else(|_| Event::default().event("error"));
+            Some((Ok(event), (None, receiver)))
+        },
+    )
+}
+
+/// Returns the complete saved session, including every hand and the deck.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn export_game(
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(req): Json<PlayCardRequest>,
 ) -> impl IntoResponse {
-    info!("Playing card at index {} in game: {}", req.card_index, id);
+    info!("Exporting game ID: {}", id);
     match state.session_manager.load_session(&id) {
-        Ok(mut session) => {
-            // Check if the card being played is a Wild or Wild Draw Four
-            let card = session.game.players[session.game.current_turn]
-                .hand
-                .get(req.card_index);
-            let requires_color = card.map_or(false, |c| {
-                matches!(c.card_type, CardType::Wild | CardType::WildDrawFour)
-            });
-
-            // If it's a Wild card, require a color
-            if requires_color {
-                if let Some(color_str) = &req.color {
-                    let color = match color_str.to_lowercase().as_str() {
-                        "red" => Color::Red,
-                        "green" => Color::Green,
-                        "blue" => Color::Blue,
-                        "yellow" => Color::Yellow,
-                        _ => {
-                            info!("Invalid color {} in game: {}", color_str, id);
-                            return (StatusCode::BAD_REQUEST, "Invalid color".to_string())
-                                .into_response();
-                        }
-                    };
-                    // Set the color of the Wild card before playing it
-                    session.game.players[session.game.current_turn].hand[req.card_index].color =
-                        color;
-                } else {
-                    info!("Color required for Wild card in game: {}", id);
-                    return (
-                        StatusCode::BAD_REQUEST,
-                        "Color required for Wild card".to_string(),
-                    )
-                        .into_response();
-                }
-            }
-
-            match session
-                .game
-                .play_card(session.game.current_turn, req.card_index)
-            {
-                Ok(event) => {
-                    info!("Successfully played card in game: {}", id);
-                    if let Err(e) = session.save(&state.session_manager.sessions_dir) {
-                        error!("Failed to save game state: {}", e);
-                        return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
-                    }
-                    Json(event).into_response()
-                }
-                Err(e) => {
-                    info!("Failed to play card in game: {} - {}", id, e);
-                    (StatusCode::BAD_REQUEST, e.to_string()).into_response()
-                }
-            }
-        }
+        Ok(session) => Json(session).into_response(),
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-pub async fn draw_card(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
-    info!("Drawing card in game: {}", id);
-    match state.session_manager.load_session(&id) {
-        Ok(mut session) => match session.game.draw_card(session.game.current_turn) {
-            Ok(event) => {
-                info!("Successfully drew card in game: {}", id);
-                if let Err(e) = session.save(&state.session_manager.sessions_dir) {
-                    error!("Failed to save game state: {}", e);
-                    return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
-                }
-                Json(event).into_response()
-            }
-            Err(e) => {
-                info!("Failed to draw card in game: {} - {}", id, e);
-                (StatusCode::BAD_REQUEST, e.to_string()).into_response()
-            }
-        },
+/// Recreates an exported session under a fresh id.
+pub async fn import_game(
+    State(state): State<AppState>,
+    Json(session): Json<GameSession>,
+) -> impl IntoResponse {
+    info!("Importing game exported as: {}", session.id);
+    if let Err(e) = session.game.validate() {
+        info!("Rejected imported game {}: {}", session.id, e);
+        return ApiError::bad_request("GAME_ERROR", e).into_response();
+    }
+    if let Err(e) = check_game_limit(&state) {
+        return e.into_response();
+    }
+    match state
+        .session_manager
+        .create_session_with_tokens(session.game)
+    {
+        Ok((session, tokens)) => {
+            info!("Imported game as: {}", session.id);
+            let response = GameResponse {
+                tokens: Some(tokens),
+                ..GameResponse::from_session(&session)
+            };
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
         Err(e) => {
-            info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            error!("Failed to create session: {}", e);
+            ApiError::internal(e).into_response()
         }
     }
 }
 
-pub async fn choose_color(
+/// Rebuilds a seeded game from its recorded events and saves it as a new game.
+pub async fn import_replay(
     State(state): State<AppState>,
-    Path(id): Path<String>,
-    Json(req): Json<ChooseColorRequest>,
+    Json(req): Json<ReplayRequest>,
 ) -> impl IntoResponse {
-    info!("Choosing color {} in game: {}", req.color, id);
-    // Validate color first
-    let color = match req.color.to_lowercase().as_str() {
-        "red" => Color::Red,
-        "green" => Color::Green,
-        "blue" => Color::Blue,
-        "yellow" => Color::Yellow,
-        _ => {
-            info!("Invalid color {} in game: {}", req.color, id);
-            return (StatusCode::BAD_REQUEST, "Invalid color".to_string()).into_response();
+    info!("Replaying {} events", req.events.len());
+    let game = match replay::replay(req.player_names, req.seed, &req.events) {
+        Ok(game) => game,
+        Err(e) => {
+            info!("Rejected replay: {}", e);
+            return ApiError::bad_request("INVALID_REPLAY", e.to_string()).into_response();
         }
     };
+    if let Err(e) = check_game_limit(&state) {
+        return e.into_response();
+    }
+    match state.session_manager.create_session_with_tokens(game) {
+        Ok((session, tokens)) => {
+            info!("Imported replay as: {}", session.id);
+            let response = ReplayResponse {
+                steps_applied: req.events.len(),
+                state: GameStateResponse::from_session(&session),
+                tokens,
+            };
+            (StatusCode::CREATED, Json(response)).into_response()
+        }
+        Err(e) => {
+            error!("Failed to create session: {}", e);
+            ApiError::internal(e).into_response()
+        }
+    }
+}
 
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_board(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    info!("Rendering board for game ID: {}", id);
     match state.session_manager.load_session(&id) {
-        Ok(mut session) => {
-            if let Some((top_card, _)) = session.game.discard_pile.last_mut() {
-                top_card.color = color;
-                if let Err(e) = session.save(&state.session_manager.sessions_dir) {
-                    error!("Failed to save game state: {}", e);
-                    return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
-                }
-                info!("Successfully chose color in game: {}", id);
-                StatusCode::OK.into_response()
-            } else {
-                info!("No card in discard pile for game: {}", id);
-                (
-                    StatusCode::BAD_REQUEST,
-                    "No card in discard pile".to_string(),
-                )
-                    .into_response()
-            }
-        }
+        Ok(session) => (
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            session.game.render_board(),
+        )
+            .into_response(),
         Err(e) => {
             info!("Game not found: {}", id);
-            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-impl GameResponse {
-    fn from_session(session: &GameSession) -> Self {
-        let (status, winner) = match session.game.status {
-            GameStatus::InProgress => ("In Progress".to_string(), None),
-            GameStatus::Complete { winner_id } => (
-                "Complete".to_string(),
-                Some(WinnerResponse {
-                    id: winner_id,
-                    name: session.game.players[winner_id].name.clone(),
-                }),
-            ),
-        };
-
-        Self {
-            id: session.id.clone(),
-            current_turn: session.game.current_turn,
-            players: session
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_stats(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    info!("Getting player stats for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            let stats: Vec<PlayerStatsResponse> = session
                 .game
                 .players
                 .iter()
-                .map(|p| PlayerResponse {
+                .map(|p| PlayerStatsResponse {
                     id: p.id,
                     name: p.name.clone(),
-                    hand_size: p.hand.len(),
+                    stats: p.stats.clone(),
                 })
-                .collect(),
-            discard_pile_top: {
-                let (card, player_id) = session.game.discard_pile.last().unwrap();
-                CardResponse::from_card(
-                    card,
-                    if *player_id == usize::MAX {
-                        None
-                    } else {
-                        Some(*player_id)
-                    },
-                )
-            },
-            deck_cards_remaining: session.game.deck.len(),
-            pending_draws: session.game.pending_draws,
-            status,
-            winner,
+                .collect();
+            Json(stats).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-impl CardResponse {
-    fn from_card(card: &Card, player_id: Option<usize>) -> Self {
-        Self {
-            color: format!("{:?}", card.color),
-            card_type: format!("{:?}", card.card_type),
-            player_id,
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_deck(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<DeckQuery>,
+) -> impl IntoResponse {
+    info!("Getting deck for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            info!("Found game deck: {}", id);
+            let mut cards: Vec<CardResponse> = session
+                .game
+                .deck
+                .iter()
+                .map(|card| CardResponse::from_card(card, None))
+                .collect();
+            if query.reversed {
+                cards.reverse();
+            }
+            let response = DeckResponse { cards };
+            Json(response).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-impl GameStateResponse {
-    fn from_session(session: &GameSession) -> Self {
-        let (status, winner) = match session.game.status {
-            GameStatus::InProgress => ("In Progress".to_string(), None),
-            GameStatus::Complete { winner_id } => (
-                "Complete".to_string(),
-                Some(WinnerResponse {
-                    id: winner_id,
-                    name: session.game.players[winner_id].name.clone(),
-                }),
-            ),
-        };
+/// The house rules the game is played with.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_game_config(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    info!("Getting config for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => Json(session.game.config).into_response(),
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
 
-        Self {
-            id: session.id.clone(),
-            current_turn: session.game.current_turn,
-            direction: format!("{:?}", session.game.direction),
-            players: session
-                .game
-                .players
-                .iter()
-                .map(|p| PlayerStateResponse {
-                    id: p.id,
-                    name: p.name.clone(),
-                    hand: p
-                        .hand
-                        .iter()
-                        .enumerate()
-                        .map(|(i, card)| (i, CardResponse::from_card(card, None)))
-                        .collect(),
-                })
-                .collect(),
-            discard_pile_top: {
-                let (card, player_id) = session.game.discard_pile.last().unwrap();
-                CardResponse::from_card(
-                    card,
-                    if *player_id == usize::MAX {
-                        None
-                    } else {
-                        Some(*player_id)
-                    },
+/// The next card to be drawn, for games that set `allow_deck_peek`.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_deck_next(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    info!("Peeking at the deck for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            if !session.game.config.allow_deck_peek {
+                info!("Deck peek refused in game: {}", id);
+                return ApiError::forbidden(
+                    "PEEK_DISABLED",
+                    "This game does not allow looking at the deck",
                 )
-            },
-            deck_cards_remaining: session.game.deck.len(),
-            pending_draws: session.game.pending_draws,
-            status,
-            winner,
+                .into_response();
+            }
+            match session.game.peek_top_deck() {
+                Some(card) => Json(CardResponse::from_card(card, None)).into_response(),
+                None => ApiError::not_found("The deck is empty").into_response(),
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
         }
     }
 }
 
-pub async fn start_api_server(sessions_dir: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging
-    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
-    info!("Starting Uno API server...");
-
-    let session_manager = SessionManager::new(sessions_dir)?;
-    let state = AppState { session_manager };
-
-    let cors = CorsLayer::permissive();
-
-    // Create a trace layer for logging
-    let trace_layer = TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new().include_headers(true))
-        .on_response(DefaultOnResponse::new().include_headers(true));
-
-    let app = Router::new()
-        .route("/games", post(create_game))
-        .route("/games", get(list_games))
-        .route("/games/{id}", get(get_game))
-        .route("/games/{id}/state", get(get_game_state))
-        .route("/games/{id}/deck", get(get_deck))
-        .route("/games/{id}/discard", get(get_discard_pile))
-        .route("/games/{id}", delete(delete_game))
-        .route("/games/{id}/play", post(play_card))
-        .route("/games/{id}/draw", post(draw_card))
-        .route("/games/{id}/color", post(choose_color))
-        .layer(cors)
-        .layer(trace_layer)
-        .with_state(state);
+/// Counts the cards left in the deck by color and card type.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_deck_composition(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    info!("Getting deck composition for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            let mut counts: Vec<_> = session.game.deck_composition().into_iter().collect();
+            counts.sort();
+            let response = DeckCompositionResponse {
+                total: session.game.deck.len(),
+                cards: counts
+                    .into_iter()
+                    .map(|((color, card_type), count)| CardCountResponse {
+                        color: format!("{:?}", color),
+                        card_type: format!("{:?}", card_type),
+                        count,
+                    })
+                    .collect(),
+            };
+            Json(response).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
-    info!("API server running on http://127.0.0.1:3000");
-    info!("Request/response logging enabled");
-    axum::serve(listener, app).await?;
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_discard_pile(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    info!("Getting discard pile for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            info!("Found game discard pile: {}", id);
+            let response = DiscardPileResponse {
+                cards: session
+                    .game
+                    .discard_pile
+                    .iter()
+                    .map(|(card, player_id)| {
+                        (
+                            CardResponse::from_card(card, None),
+                            if *player_id == usize::MAX {
+                                None
+                            } else {
+                                Some(*player_id)
+                            },
+                        )
+                    })
+                    .collect(),
+            };
+            Json(response).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
 
-    Ok(())
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_hints(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    info!("Getting hints for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            info!("Found game hints: {}", id);
+            let response = HintsResponse::from_session(&session);
+            Json(response).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use axum::{
-        body::{to_bytes, Body},
-        http::{Request, StatusCode},
-    };
-    use serde_json::json;
+/// Everything the player whose turn it is needs in one response.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_current_player(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    info!("Getting current player view for game ID: {}", id);
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            let response = CurrentPlayerResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Drops the cached copy of a game and serves it as stored, for operators who edited
+/// its file by hand.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn reload_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = check_admin(&headers, &state) {
+        info!("Unauthorized reload of game: {}", id);
+        return e.into_response();
+    }
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.reload_session(&id) {
+        Ok(session) => {
+            warn!("Reloaded game from the store: {}", id);
+            state.updates.publish(&session);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Failed to reload game: {} - {}", id, e);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Empties the session cache, so every game is read from the store when next used.
+pub async fn reload_all(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(e) = check_admin(&headers, &state) {
+        info!("Unauthorized reload of all games");
+        return e.into_response();
+    }
+    let dropped = state.session_manager.clear_cache();
+    warn!(dropped, "Cleared the session cache");
+    Json(ReloadAllResponse { dropped }).into_response()
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn delete_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Deleting game with ID: {}", id);
+    if let Err(e) = check_admin(&headers, &state) {
+        info!("Unauthorized delete of game: {}", id);
+        return e.into_response();
+    }
+    // Wait for any request already working on the game, so it can't save it back
+    let lock = state.session_manager.session_lock(&id);
+    let guard = lock.lock().await;
+    let deleted = state.session_manager.delete_session(&id);
+    drop(guard);
+    drop(lock);
+    state.session_manager.release_lock(&id);
+    match deleted {
+        Ok(_) => {
+            // Dropping the channel ends any open update streams
+            state.updates.remove(&id);
+            info!("Successfully deleted game: {}", id);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => {
+            info!("Failed to delete game: {} - {}", id, e);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Deletes every game with the given status (and player, if given), working from the
+/// session summaries rather than loading each game.
+#[instrument(skip_all)]
+pub async fn purge_games(
+    State(state): State<AppState>,
+    Query(query): Query<PurgeGamesQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = check_admin(&headers, &state) {
+        info!("Unauthorized purge of games");
+        return e.into_response();
+    }
+    let Some(status) = query.status else {
+        info!("Refusing to purge games without a status");
+        return ApiError::bad_request(
+            "STATUS_REQUIRED",
+            "Pass the status of the games to delete, e.g. ?status=complete",
+        )
+        .into_response();
+    };
+    info!("Purging games with status {:?}", status);
+    let filter = SessionQuery {
+        status: Some(status),
+        player: query.player,
+    };
+    let summaries = match state.session_manager.query(filter) {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            error!("Failed to list sessions: {}", e);
+            return ApiError::internal(e).into_response();
+        }
+    };
+
+    let mut removed = Vec::new();
+    for summary in summaries {
+        // Wait for any request already working on the game
+        let lock = state.session_manager.session_lock(&summary.id);
+        let guard = lock.lock().await;
+        let deleted = state.session_manager.delete_session(&summary.id);
+        drop(guard);
+        drop(lock);
+        state.session_manager.release_lock(&summary.id);
+        match deleted {
+            Ok(()) => {
+                state.updates.remove(&summary.id);
+                removed.push(summary.id);
+            }
+            // Deleted by someone else in the meantime
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => error!("Failed to delete game: {} - {}", summary.id, e),
+        }
+    }
+    info!("Purged {} games", removed.len());
+    Json(PurgeGamesResponse {
+        count: removed.len(),
+        removed,
+    })
+    .into_response()
+}
+
+fn etag(session: &GameSession) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", session.game.version)).unwrap()
+}
+
+/// Checks the request's `If-Match` header against the game's current version.
+/// `*` matches any version.
+fn check_if_match(headers: &HeaderMap, session: &GameSession) -> Result<(), ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Err(ApiError::new(
+            StatusCode::PRECONDITION_REQUIRED,
+            "VERSION_REQUIRED",
+            "If-Match header with the game version is required",
+        ));
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(());
+    }
+    let matches = value.split(',').any(|tag| {
+        let tag = tag.trim();
+        let tag = tag.strip_prefix("W/").unwrap_or(tag);
+        tag.trim_matches('"').parse::<u64>().ok() == Some(session.game.version)
+    });
+    if matches {
+        Ok(())
+    } else {
+        Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "VERSION_CONFLICT",
+            format!(
+                "Game has changed: current version is {}",
+                session.game.version
+            ),
+        ))
+    }
+}
+
+/// Checks the request's `Authorization: Bearer` token against the player it moves for.
+/// Players without a token, as in games saved before tokens were issued, need none.
+fn check_player_token(
+    headers: &HeaderMap,
+    session: &GameSession,
+    player_id: usize,
+) -> Result<(), ApiError> {
+    let token = bearer_token(headers);
+    match session.check_token(player_id, token) {
+        None | Some(true) => Ok(()),
+        Some(false) if token.is_none() => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "TOKEN_REQUIRED",
+            format!("A bearer token for player {} is required", player_id),
+        )),
+        Some(false) => Err(ApiError::forbidden(
+            "INVALID_TOKEN",
+            format!("The token is not player {}'s", player_id),
+        )),
+    }
+}
+
+/// Checks the request carries the token of any player in the game, for changes to the
+/// whole table such as starting it. Games without tokens are open to anyone.
+fn check_seated_player(headers: &HeaderMap, session: &GameSession) -> Result<(), ApiError> {
+    if session.token_hashes.is_empty() {
+        return Ok(());
+    }
+    match bearer_token(headers) {
+        None => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "TOKEN_REQUIRED",
+            "A bearer token for one of the game's players is required",
+        )),
+        Some(token) if session.token_owner(token).is_some() => Ok(()),
+        Some(_) => Err(ApiError::forbidden(
+            "INVALID_TOKEN",
+            "The token is not one of this game's players'",
+        )),
+    }
+}
+
+/// Checks the request carries the admin token. Admin routes are refused outright when
+/// the server has none.
+fn check_admin(headers: &HeaderMap, state: &AppState) -> Result<(), ApiError> {
+    let Some(hash) = &state.admin_token_hash else {
+        return Err(ApiError::forbidden(
+            "ADMIN_DISABLED",
+            "Admin routes are not enabled on this server",
+        ));
+    };
+    match bearer_token(headers) {
+        None => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "TOKEN_REQUIRED",
+            "The admin bearer token is required",
+        )),
+        Some(token) if auth::verify_token(token, hash) => Ok(()),
+        Some(_) => Err(ApiError::forbidden(
+            "INVALID_TOKEN",
+            "The token is not the admin token",
+        )),
+    }
+}
+
+/// The token in an `Authorization: Bearer` header, if there is one.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+/// Plays the current player's card at `card_index`, first giving a wild the requested color.
+fn play_for_current_player(
+    game: &mut UnoGame,
+    card_index: usize,
+    color: Option<&str>,
+    target_player_id: Option<usize>,
+) -> Result<GameEvent, ApiError> {
+    let player_id = game.current_turn;
+    // Check if the card being played is a Wild or Wild Draw Four
+    let requires_color = game.players[player_id]
+        .hand
+        .get(card_index)
+        .is_some_and(|c| c.is_wild());
+
+    // If it's a Wild card, require a color
+    if requires_color {
+        let color_str = color.ok_or_else(|| {
+            ApiError::bad_request("COLOR_REQUIRED", "Color required for Wild card")
+        })?;
+        let color: Color = color_str
+            .parse()
+            .map_err(|e| ApiError::bad_request("INVALID_COLOR", e))?;
+        // Set the color of the Wild card before playing it
+        game.players[player_id].hand[card_index].color = color;
+    }
+
+    game.play_card_with_target(player_id, card_index, target_player_id)
+        .map_err(ApiError::from)
+}
+
+/// Draws for the current player, explaining a refusal under `must_play_if_able`.
+fn draw_for_current_player(game: &mut UnoGame) -> Result<GameEvent, ApiError> {
+    let player_id = game.current_turn;
+    if game.must_play(player_id) {
+        let indices: Vec<String> = game
+            .playable_indices()
+            .iter()
+            .map(|index| index.to_string())
+            .collect();
+        return Err(ApiError::bad_request(
+            "INVALID_MOVE",
+            format!(
+                "You must play instead of drawing; playable hand indices: {}",
+                indices.join(", ")
+            ),
+        ));
+    }
+    game.draw_card(player_id).map_err(ApiError::from)
+}
+
+/// Makes one move of a batch for the current player, with the same checks as its own
+/// endpoint.
+fn apply_move(game: &mut UnoGame, request: &MoveRequest) -> Result<GameEvent, ApiError> {
+    let player_id = game.current_turn;
+    match request {
+        MoveRequest::Play {
+            card_index,
+            color,
+            target_player_id,
+        } => play_for_current_player(game, *card_index, color.as_deref(), *target_player_id),
+        MoveRequest::Draw => draw_for_current_player(game),
+        MoveRequest::TakePenalty if !game.has_penalty() => Err(ApiError::bad_request(
+            "NO_PENALTY",
+            "There is no penalty to take",
+        )),
+        MoveRequest::TakePenalty => game.take_penalty(player_id).map_err(ApiError::from),
+        MoveRequest::Pass => game.pass_turn(player_id).map_err(ApiError::from),
+        MoveRequest::Challenge => game
+            .challenge_wild_draw_four(player_id)
+            .map_err(ApiError::from),
+    }
+}
+
+/// Makes several moves in order as one change: they are tried on a copy of the game,
+/// which is saved only if every move succeeds. If one fails, its error is returned,
+/// naming its position in the batch, and the game is left as it was.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn apply_moves(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<MovesRequest>,
+) -> impl IntoResponse {
+    info!(moves = req.moves.len(), "Applying moves in game: {}", id);
+    if req.moves.is_empty() {
+        return ApiError::bad_request("INVALID_MOVE", "The batch has no moves").into_response();
+    }
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    let mut session = match state.session_manager.load_session(&id) {
+        Ok(session) => session,
+        Err(e) => {
+            info!("Game not found: {}", id);
+            return ApiError::from_load_error(e).into_response();
+        }
+    };
+    if let Err(response) = check_if_match(&headers, &session) {
+        info!("Stale or missing version for game: {}", id);
+        return response.into_response();
+    }
+
+    // Reshuffles are listed before the move that caused them
+    let reshuffles = ReshuffleLog::attach(&mut session.game);
+    let mut events = Vec::with_capacity(req.moves.len());
+    for (index, request) in req.moves.iter().enumerate() {
+        let moved = check_player_token(&headers, &session, session.game.current_turn)
+            .and_then(|()| apply_move(&mut session.game, request));
+        match moved {
+            Ok(event) => {
+                session.record(&event);
+                events.extend(reshuffles.take());
+                events.push(event);
+            }
+            Err(mut e) => {
+                info!("Move {} rejected in game: {} - {}", index, id, e.error);
+                e.error = format!("Move {}: {}", index, e.error);
+                return e.into_response();
+            }
+        }
+    }
+
+    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+    if let Err(e) = state.session_manager.save_session(&session) {
+        error!("Failed to save game state: {}", e);
+        return ApiError::internal(e).into_response();
+    }
+    let played = req
+        .moves
+        .iter()
+        .filter(|request| matches!(request, MoveRequest::Play { .. }))
+        .count();
+    state.metrics.cards_played(played as u64);
+    state.metrics.move_applied(&session.game);
+    state.updates.publish(&session);
+    info!(moves = req.moves.len(), "Applied moves in game: {}", id);
+    let response = MovesResponse {
+        events,
+        state: GameStateResponse::from_session(&session),
+    };
+    ([(header::ETAG, etag(&session))], Json(response)).into_response()
+}
+
+/// Tries a move on a copy of the game and reports what would happen, saving nothing.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn simulate_move(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<SimulateRequest>,
+) -> impl IntoResponse {
+    info!("Simulating move in game: {}", id);
+    let session = match state.session_manager.load_session(&id) {
+        Ok(session) => session,
+        Err(e) => {
+            info!("Game not found: {}", id);
+            return ApiError::from_load_error(e).into_response();
+        }
+    };
+    let mut simulated = session.clone();
+    let result = match req {
+        SimulateRequest::Play {
+            card_index,
+            color,
+            target_player_id,
+        } => play_for_current_player(
+            &mut simulated.game,
+            card_index,
+            color.as_deref(),
+            target_player_id,
+        ),
+        SimulateRequest::Draw => draw_for_current_player(&mut simulated.game),
+    };
+    match result {
+        Ok(event) => {
+            info!(event = event.name(), "Simulated move in game: {}", id);
+            let response = SimulateResponse {
+                event,
+                state: GameStateResponse::from_session(&simulated),
+            };
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Simulated move rejected in game: {} - {}", id, e.error);
+            e.into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn play_card(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<PlayCardRequest>,
+) -> impl IntoResponse {
+    info!(card_index = req.card_index, "Playing card in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, session.game.current_turn)
+            {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            match play_for_current_player(
+                &mut session.game,
+                req.card_index,
+                req.color.as_deref(),
+                req.target_player_id,
+            ) {
+                Ok(event) => {
+                    info!(
+                        event = event.name(),
+                        "Successfully played card in game: {}", id
+                    );
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.card_played();
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    ([(header::ETAG, etag(&session))], Json(event)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to play card in game: {} - {}", id, e.error);
+                    e.into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Takes the pending Draw Two or Wild Draw Four penalty, or the stacked Skips, for the
+/// current player.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn take_penalty(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Taking penalty in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, session.game.current_turn)
+            {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            if !session.game.has_penalty() {
+                info!("No penalty to take in game: {}", id);
+                return ApiError::bad_request("NO_PENALTY", "There is no penalty to take")
+                    .into_response();
+            }
+            match session.game.take_penalty(session.game.current_turn) {
+                Ok(event) => {
+                    info!(event = event.name(), "Took penalty in game: {}", id);
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    let cards_drawn = match &event {
+                        GameEvent::DrawTwo { cards, .. } => cards.len(),
+                        _ => 0,
+                    };
+                    let Some(top) = session.game.discard_pile.last() else {
+                        return ApiError::from(GameError::EmptyDiscardPile).into_response();
+                    };
+                    let response = PenaltyResponse {
+                        event,
+                        cards_drawn,
+                        discard_pile_top: TopCardResponse::from_discard(top),
+                    };
+                    ([(header::ETAG, etag(&session))], Json(response)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to take penalty in game: {} - {}", id, e);
+                    ApiError::from(e).into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn draw_card(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Drawing card in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, session.game.current_turn)
+            {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            match draw_for_current_player(&mut session.game) {
+                Ok(event) => {
+                    info!(
+                        event = event.name(),
+                        "Successfully drew card in game: {}", id
+                    );
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    ([(header::ETAG, etag(&session))], Json(event)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to draw card in game: {} - {}", id, e.error);
+                    e.into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn pass_turn(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Passing turn in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, session.game.current_turn)
+            {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            match session.game.pass_turn(session.game.current_turn) {
+                Ok(event) => {
+                    info!(
+                        event = event.name(),
+                        "Successfully passed turn in game: {}", id
+                    );
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    ([(header::ETAG, etag(&session))], Json(event)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to pass turn in game: {} - {}", id, e);
+                    ApiError::from(e).into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn challenge(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Challenging Wild Draw Four in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, session.game.current_turn)
+            {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            match session
+                .game
+                .challenge_wild_draw_four(session.game.current_turn)
+            {
+                Ok(event) => {
+                    info!(event = event.name(), "Resolved challenge in game: {}", id);
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    ([(header::ETAG, etag(&session))], Json(event)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to challenge in game: {} - {}", id, e);
+                    ApiError::from(e).into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn rename_player(
+    State(state): State<AppState>,
+    Path((id, player_id)): Path<(String, usize)>,
+    headers: HeaderMap,
+    Json(req): Json<RenamePlayerRequest>,
+) -> impl IntoResponse {
+    info!("Renaming player {} in game: {}", player_id, id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if player_id >= session.game.players.len() {
+                info!("Player {} not found in game: {}", player_id, id);
+                return ApiError::not_found(format!("Player {} not found", player_id))
+                    .into_response();
+            }
+            if let Err(response) = check_player_token(&headers, &session, player_id) {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(e) = session.game.rename_player(player_id, &req.name) {
+                info!("Failed to rename player in game: {} - {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Renamed player {} in game: {}", player_id, id);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Sorts the hand of the player named in the path, who must present their token.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn sort_hand(
+    State(state): State<AppState>,
+    Path((id, player_id)): Path<(String, usize)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Sorting hand of player {} in game: {}", player_id, id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if player_id >= session.game.players.len() {
+                info!("Player {} not found in game: {}", player_id, id);
+                return ApiError::not_found(format!("Player {} not found", player_id))
+                    .into_response();
+            }
+            if let Err(response) = check_player_token(&headers, &session, player_id) {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(e) = session.game.sort_hand(player_id) {
+                info!("Failed to sort hand in game: {} - {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Sorted hand of player {} in game: {}", player_id, id);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Describes one card in a player's hand, for clients that show a card at a time. Only
+/// that player, by their token, or the admin may look, unless the game issued no tokens.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn get_hand_card(
+    State(state): State<AppState>,
+    Path((id, player_id, index)): Path<(String, usize, usize)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!(
+        "Getting card {} of player {} in game: {}",
+        index, player_id, id
+    );
+    match state.session_manager.load_session(&id) {
+        Ok(session) => {
+            if let Err(response) = check_player_token(&headers, &session, player_id) {
+                if check_admin(&headers, &state).is_err() {
+                    info!("Unauthorized look at a hand in game: {}", id);
+                    return response.into_response();
+                }
+            }
+            let game = &session.game;
+            let Some(player) = game.players.get(player_id) else {
+                info!("Player {} not found in game: {}", player_id, id);
+                return ApiError::not_found(format!("Player {} not found", player_id))
+                    .into_response();
+            };
+            let Some(card) = player.hand.get(index) else {
+                info!(
+                    "Card {} not found for player {} in game: {}",
+                    index, player_id, id
+                );
+                return ApiError::not_found(format!(
+                    "Player {} has no card at index {}",
+                    player_id, index
+                ))
+                .into_response();
+            };
+            let playable = game.status == GameStatus::InProgress
+                && game.current_turn == player_id
+                && game.playable_indices().contains(&index);
+            Json(CardDetailResponse {
+                index,
+                color: format!("{:?}", card.color),
+                card_type: format!("{:?}", card.card_type),
+                display: card.to_string(),
+                points: card.points(),
+                playable,
+            })
+            .into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Plays every bot turn until a human must act or the game ends.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn advance_bots(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Advancing bots in game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            let cards_played_before = total_cards_played(&session.game);
+            let events = match bot::advance_bots(&mut session.game) {
+                Ok(events) => events,
+                Err(e) => {
+                    error!("Bot move failed in game: {} - {}", id, e);
+                    return ApiError::internal(e).into_response();
+                }
+            };
+            if !events.is_empty() {
+                for event in &events {
+                    session.record(event);
+                }
+                state
+                    .metrics
+                    .cards_played(total_cards_played(&session.game) - cards_played_before);
+                session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                if let Err(e) = state.session_manager.save_session(&session) {
+                    error!("Failed to save game state: {}", e);
+                    return ApiError::internal(e).into_response();
+                }
+                state.metrics.move_applied(&session.game);
+                state.updates.publish(&session);
+            }
+            info!(moves = events.len(), "Advanced bots in game: {}", id);
+            let response = AdvanceBotsResponse {
+                events,
+                state: GameStateResponse::from_session(&session),
+            };
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+fn total_cards_played(game: &UnoGame) -> u64 {
+    game.players
+        .iter()
+        .map(|p| u64::from(p.stats.cards_played))
+        .sum()
+}
+
+/// Deals a player a fresh hand, if the server allows redeals.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn redeal_player(
+    State(state): State<AppState>,
+    Path((id, player_id)): Path<(String, usize)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Redealing player {} in game: {}", player_id, id);
+    if !state.allow_redeal {
+        info!("Redeal refused in game: {}", id);
+        return ApiError::forbidden("REDEAL_DISABLED", "Redeals are not allowed on this server")
+            .into_response();
+    }
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if player_id >= session.game.players.len() {
+                info!("Player {} not found in game: {}", player_id, id);
+                return ApiError::not_found(format!("Player {} not found", player_id))
+                    .into_response();
+            }
+            if let Err(response) = check_player_token(&headers, &session, player_id) {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(e) = session.game.redeal_player(player_id) {
+                info!("Failed to redeal in game: {} - {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Redealt player {} in game: {}", player_id, id);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Adds a player to a game waiting in the lobby.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn join_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<JoinGameRequest>,
+) -> impl IntoResponse {
+    info!("Player {} joining game: {}", req.name, id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            let player_id = match session.game.join(req.name) {
+                Ok(player_id) => player_id,
+                Err(e) => {
+                    info!("Failed to join game: {} - {}", id, e);
+                    return ApiError::from(e).into_response();
+                }
+            };
+            let token = session.issue_token(player_id);
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Player {} joined game: {}", player_id, id);
+            let response = JoinGameResponse {
+                player_id,
+                token,
+                state: GameStateResponse::from_session(&session),
+            };
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Deals the cards for a game waiting in the lobby.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn start_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    info!("Starting game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_seated_player(&headers, &session) {
+                info!("Unauthorized start of game: {}", id);
+                return response.into_response();
+            }
+            if let Err(e) = session.game.start() {
+                info!("Failed to start game: {} - {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+            session.restart_history();
+            session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Started game: {}", id);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Deals a finished game again to the same players, keeping its id.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn restart_game(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<RestartQuery>,
+) -> impl IntoResponse {
+    info!("Restarting game: {}", id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_seated_player(&headers, &session) {
+                info!("Unauthorized restart of game: {}", id);
+                return response.into_response();
+            }
+            if let Err(e) = session.game.restart(!query.reset_stats) {
+                info!("Failed to restart game: {} - {}", id, e);
+                return ApiError::from(e).into_response();
+            }
+            session.restart_history();
+            session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+            if let Err(e) = state.session_manager.save_session(&session) {
+                error!("Failed to save game state: {}", e);
+                return ApiError::internal(e).into_response();
+            }
+            state.updates.publish(&session);
+            info!("Restarted game: {}", id);
+            let response = GameStateResponse::from_session(&session);
+            ([(header::ETAG, etag(&session))], Json(response)).into_response()
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn forfeit(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<ForfeitRequest>,
+) -> impl IntoResponse {
+    info!("Player {} forfeiting game: {}", req.player_id, id);
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_player_token(&headers, &session, req.player_id) {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            match session.game.forfeit(req.player_id) {
+                Ok(event) => {
+                    info!(
+                        event = event.name(),
+                        "Player {} forfeited game: {}", req.player_id, id
+                    );
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    Json(event).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to forfeit in game: {} - {}", id, e);
+                    ApiError::from(e).into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+/// Removes a stalling player on a moderator's behalf. Needs the admin token.
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn kick_player(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<KickRequest>,
+) -> impl IntoResponse {
+    info!("Kicking player {} from game: {}", req.player_id, id);
+    if let Err(e) = check_admin(&headers, &state) {
+        info!("Unauthorized kick in game: {}", id);
+        return e.into_response();
+    }
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            match session.game.kick(req.player_id) {
+                Ok(event) => {
+                    warn!(
+                        event = event.name(),
+                        "Moderator kicked player {} ({}) from game: {}",
+                        req.player_id,
+                        session.game.players[req.player_id].name,
+                        id
+                    );
+                    session.record(&event);
+                    session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+                    if let Err(e) = state.session_manager.save_session(&session) {
+                        error!("Failed to save game state: {}", e);
+                        return ApiError::internal(e).into_response();
+                    }
+                    state.metrics.move_applied(&session.game);
+                    state.updates.publish(&session);
+                    ([(header::ETAG, etag(&session))], Json(event)).into_response()
+                }
+                Err(e) => {
+                    info!("Failed to kick in game: {} - {}", id, e);
+                    ApiError::from(e).into_response()
+                }
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[instrument(skip_all, fields(game_id = %id))]
+pub async fn choose_color(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<ChooseColorRequest>,
+) -> impl IntoResponse {
+    info!("Choosing color {} in game: {}", req.color, id);
+    // Validate color first
+    let color: Color = match req.color.parse() {
+        Ok(color) => color,
+        Err(e) => {
+            info!("Invalid color {} in game: {}", req.color, id);
+            return ApiError::bad_request("INVALID_COLOR", e).into_response();
+        }
+    };
+
+    let lock = state.session_manager.session_lock(&id);
+    let _guard = lock.lock().await;
+    match state.session_manager.load_session(&id) {
+        Ok(mut session) => {
+            // The color is the choice of whoever played the top card; the first card
+            // turned over belongs to nobody, so then the current player chooses.
+            let chooser = match session.game.discard_pile.last() {
+                Some(&(_, player_id)) if player_id < session.game.players.len() => player_id,
+                _ => session.game.current_turn,
+            };
+            if let Err(response) = check_player_token(&headers, &session, chooser) {
+                info!("Unauthorized move in game: {}", id);
+                return response.into_response();
+            }
+            if let Err(response) = check_if_match(&headers, &session) {
+                info!("Stale or missing version for game: {}", id);
+                return response.into_response();
+            }
+            if let Some((top_card, _)) = session.game.discard_pile.last_mut() {
+                top_card.color = color;
+                session.game.version += 1;
+                session.record(&GameEvent::WildColorChosen {
+                    player_id: chooser,
+                    color,
+                });
+                if let Err(e) = state.session_manager.save_session(&session) {
+                    error!("Failed to save game state: {}", e);
+                    return ApiError::internal(e).into_response();
+                }
+                state.updates.publish(&session);
+                info!("Successfully chose color in game: {}", id);
+                (StatusCode::OK, [(header::ETAG, etag(&session))]).into_response()
+            } else {
+                info!("No card in discard pile for game: {}", id);
+                ApiError::bad_request("NO_DISCARD", "No card in discard pile").into_response()
+            }
+        }
+        Err(e) => {
+            info!("Game not found: {}", id);
+            ApiError::from_load_error(e).into_response()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HealthResponse {
+    status: String,
+    version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReadyResponse {
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Liveness: the server is up and answering requests.
+pub async fn health() -> impl IntoResponse {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Readiness: the server can also save games.
+pub async fn ready(State(state): State<AppState>) -> impl IntoResponse {
+    match state.session_manager.check_writable() {
+        Ok(()) => Json(ReadyResponse {
+            status: "ready".to_string(),
+            error: None,
+        })
+        .into_response(),
+        Err(e) => {
+            error!("Session store is not writable: {}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ReadyResponse {
+                    status: "unavailable".to_string(),
+                    error: Some(e.to_string()),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Server counters in the Prometheus text format.
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let active_sessions = match state.session_manager.list_sessions() {
+        Ok(ids) => ids.len(),
+        Err(e) => {
+            error!("Failed to list sessions for metrics: {}", e);
+            return ApiError::internal(e).into_response();
+        }
+    };
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(active_sessions),
+    )
+        .into_response()
+}
+
+pub async fn get_openapi() -> impl IntoResponse {
+    Json(openapi::spec())
+}
+
+pub async fn get_docs() -> impl IntoResponse {
+    Html(openapi::DOCS_HTML)
+}
+
+impl GameResponse {
+    fn from_session(session: &GameSession) -> Self {
+        let (status, winner) = describe_status(&session.game);
+
+        Self {
+            id: session.id.clone(),
+            current_turn: session.game.current_turn,
+            players: session
+                .game
+                .players
+                .iter()
+                .map(|p| PlayerResponse {
+                    id: p.id,
+                    name: p.name.clone(),
+                    hand_size: p.hand.len(),
+                    active: p.active,
+                    is_bot: p.is_bot,
+                })
+                .collect(),
+            discard_pile_top: session
+                .game
+                .discard_pile
+                .last()
+                .map(TopCardResponse::from_discard),
+            deck_cards_remaining: session.game.deck.len(),
+            pending_draws: session.game.pending_draws,
+            pending_skips: session.game.pending_skips,
+            status,
+            winner,
+            standings: session.game.standings(),
+            tokens: None,
+        }
+    }
+}
+
+impl CardResponse {
+    fn from_card(card: &Card, player_id: Option<usize>) -> Self {
+        Self {
+            color: format!("{:?}", card.color),
+            card_type: format!("{:?}", card.card_type),
+            player_id,
+        }
+    }
+}
+
+impl TopCardResponse {
+    fn from_discard((card, player_id): &(Card, usize)) -> Self {
+        let colored = card.color != Color::Wild;
+        Self {
+            color: format!("{:?}", card.color),
+            card_type: format!("{:?}", card.card_type),
+            player_id: if *player_id == usize::MAX {
+                None
+            } else {
+                Some(*player_id)
+            },
+            effective_color: colored.then(|| format!("{:?}", card.color)),
+            is_wild_active: card.chosen_color().is_some(),
+        }
+    }
+}
+
+impl GameStateResponse {
+    fn from_session(session: &GameSession) -> Self {
+        let (status, winner) = describe_status(&session.game);
+
+        Self {
+            id: session.id.clone(),
+            version: session.game.version,
+            current_turn: session.game.current_turn,
+            next_turn: session.game.peek_next_player(),
+            direction: format!("{:?}", session.game.direction),
+            players: session
+                .game
+                .players
+                .iter()
+                .map(|p| PlayerStateResponse {
+                    id: p.id,
+                    name: p.name.clone(),
+                    hand: p
+                        .hand
+                        .iter()
+                        .enumerate()
+                        .map(|(i, card)| (i, CardResponse::from_card(card, None)))
+                        .collect(),
+                    hand_size: p.hand.len(),
+                    active: p.active,
+                    is_bot: p.is_bot,
+                    stats: p.stats.clone(),
+                })
+                .collect(),
+            discard_pile_top: session
+                .game
+                .discard_pile
+                .last()
+                .map(TopCardResponse::from_discard),
+            recent_discards: session
+                .game
+                .discard_pile
+                .iter()
+                .rev()
+                .take(RECENT_DISCARDS)
+                .map(|(card, player_id)| {
+                    CardResponse::from_card(card, (*player_id != usize::MAX).then_some(*player_id))
+                })
+                .collect(),
+            deck_cards_remaining: session.game.deck.len(),
+            pending_draws: session.game.pending_draws,
+            pending_skips: session.game.pending_skips,
+            status,
+            winner,
+            // Counted down by the wall clock; the deadline was set by the manager's clock,
+            // which is the same one outside tests
+            turn_seconds_remaining: session
+                .turn_deadline
+                .map(|deadline| (deadline - chrono::Utc::now()).num_seconds().max(0)),
+        }
+    }
+}
+
+impl GameStateResponse {
+    /// Hides every hand except the given player's; hand sizes stay visible.
+    fn for_player(mut self, player_id: usize) -> Self {
+        for player in &mut self.players {
+            if player.id != player_id {
+                player.hand.clear();
+            }
+        }
+        self
+    }
+}
+
+/// The status label and, once the game has ended, its winner.
+fn describe_status(game: &UnoGame) -> (String, Option<WinnerResponse>) {
+    let winner = |winner_id: usize| {
+        Some(WinnerResponse {
+            id: winner_id,
+            name: game.players[winner_id].name.clone(),
+        })
+    };
+    match game.status {
+        GameStatus::Lobby => ("Lobby".to_string(), None),
+        GameStatus::InProgress => ("In Progress".to_string(), None),
+        GameStatus::Complete { winner_id } => ("Complete".to_string(), winner(winner_id)),
+        GameStatus::Stalemate { winner_id } => ("Stalemate".to_string(), winner(winner_id)),
+    }
+}
+
+impl PublicStateResponse {
+    fn from_session(session: &GameSession) -> Self {
+        let (status, winner) = describe_status(&session.game);
+        Self {
+            id: session.id.clone(),
+            version: session.game.version,
+            current_turn: session.game.current_turn,
+            direction: format!("{:?}", session.game.direction),
+            players: session
+                .game
+                .players
+                .iter()
+                .map(|p| PlayerResponse {
+                    id: p.id,
+                    name: p.name.clone(),
+                    hand_size: p.hand.len(),
+                    active: p.active,
+                    is_bot: p.is_bot,
+                })
+                .collect(),
+            discard_pile_top: session
+                .game
+                .discard_pile
+                .last()
+                .map(TopCardResponse::from_discard),
+            deck_cards_remaining: session.game.deck.len(),
+            pending_draws: session.game.pending_draws,
+            pending_skips: session.game.pending_skips,
+            status,
+            winner,
+        }
+    }
+}
+
+impl GameSummaryResponse {
+    fn from_summary(summary: SessionSummary) -> Self {
+        Self {
+            status: status_label(&summary.status).to_string(),
+            id: summary.id,
+            player_names: summary.player_names,
+            current_turn: summary.current_turn,
+            last_updated: summary.last_updated,
+        }
+    }
+}
+
+/// The status as summaries show it, without the winner.
+fn status_label(status: &GameStatus) -> &'static str {
+    match status {
+        GameStatus::Lobby => "Lobby",
+        GameStatus::InProgress => "In Progress",
+        GameStatus::Complete { .. } => "Complete",
+        GameStatus::Stalemate { .. } => "Stalemate",
+    }
+}
+
+impl HintsResponse {
+    fn from_session(session: &GameSession) -> Self {
+        let player = &session.game.players[session.game.current_turn];
+        let playable_cards: Vec<(usize, CardResponse)> = session
+            .game
+            .playable_indices()
+            .into_iter()
+            .map(|i| (i, CardResponse::from_card(&player.hand[i], None)))
+            .collect();
+
+        Self {
+            player_id: player.id,
+            must_draw: playable_cards.is_empty(),
+            playable_cards,
+            pending_draws: session.game.pending_draws,
+        }
+    }
+}
+
+impl CurrentPlayerResponse {
+    fn from_session(session: &GameSession) -> Self {
+        let game = &session.game;
+        let player = &game.players[game.current_turn];
+        let playable = game.playable_indices();
+        let hand = player
+            .hand
+            .iter()
+            .enumerate()
+            .map(|(index, card)| HandCardResponse {
+                index,
+                card: CardResponse::from_card(card, None),
+                playable: playable.contains(&index),
+            })
+            .collect();
+
+        let legal_actions = game.legal_actions();
+        let mut actions: Vec<String> = Vec::new();
+        for action in &legal_actions {
+            if !actions.iter().any(|name| name == action.name()) {
+                actions.push(action.name().to_string());
+            }
+        }
+
+        Self {
+            player_id: player.id,
+            name: player.name.clone(),
+            hand,
+            effective_color: game
+                .discard_pile
+                .last()
+                .and_then(|top| TopCardResponse::from_discard(top).effective_color),
+            pending_draws: game.pending_draws,
+            actions,
+            legal_actions,
+        }
+    }
+}
+
+/// Settings for the background task that removes expired sessions.
+#[derive(Debug, Clone)]
+pub struct CleanupConfig {
+    pub interval: std::time::Duration,
+    pub max_age: chrono::Duration,
+    pub completed_max_age: Option<chrono::Duration>,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(24 * 60 * 60),
+            max_age: chrono::Duration::days(30),
+            completed_max_age: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServerConfig {
+    pub session_cleanup: Option<CleanupConfig>,
+    /// How long a player has to move before a card is drawn for them. Off when `None`.
+    pub turn_timeout: Option<chrono::Duration>,
+    /// Whether `POST /games/{id}/players/{player_id}/redeal` is allowed.
+    pub allow_redeal: bool,
+    /// Save changed games in the background this often instead of after every move.
+    /// Off (every move saved before it is answered) when `None`.
+    pub write_behind: Option<std::time::Duration>,
+    /// Origins browsers may call the API from, e.g. `https://uno.example.com`.
+    pub cors_origins: Vec<String>,
+    /// Allow every origin, for local development. Overrides `cors_origins`.
+    pub dev: bool,
+    /// A read-only directory of games to serve alongside the sessions directory, e.g.
+    /// example games. Changes to them are saved to the sessions directory.
+    pub snapshot_dir: Option<PathBuf>,
+    /// Most games that may be in the lobby or in progress at once. New games are refused
+    /// with `503` while the server is full. Unlimited when `None`.
+    pub max_active_games: Option<usize>,
+    /// The bearer token admin routes such as `POST /admin/reload-all` require. They are
+    /// refused when `None`.
+    pub admin_token: Option<String>,
+}
+
+/// Builds the CORS layer. Browsers may only call the API from `origins`, or from
+/// anywhere in dev mode. Fails if an origin isn't a valid header value.
+fn cors_layer(origins: &[String], dev: bool) -> Result<CorsLayer, String> {
+    if dev {
+        return Ok(CorsLayer::permissive());
+    }
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            HeaderValue::from_str(origin).map_err(|_| format!("Invalid CORS origin: {:?}", origin))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::IF_MATCH,
+            header::AUTHORIZATION,
+        ])
+        .expose_headers([header::ETAG]))
+}
+
+fn spawn_session_cleanup(session_manager: SessionManager, config: CleanupConfig) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+        loop {
+            interval.tick().await;
+            match session_manager.cleanup_expired_with(config.max_age, config.completed_max_age) {
+                Ok(removed) => info!("Removed {} expired sessions", removed.len()),
+                Err(e) => error!("Failed to clean up expired sessions: {}", e),
+            }
+        }
+    });
+}
+
+fn spawn_session_flush(session_manager: SessionManager, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = session_manager.flush() {
+                error!("Failed to save sessions: {}", e);
+            }
+        }
+    });
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+    }
+}
+
+/// The span for one HTTP request. Each gets a fresh `request_id`, and handler spans
+/// (carrying `game_id`) nest inside it, so every log line shows both.
+fn request_span(request: &axum::extract::Request) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %uuid::Uuid::new_v4(),
+        headers = ?request.headers(),
+    )
+}
+
+/// How often the turn timer looks for players who have run out of time.
+const TURN_TIMER_TICK: std::time::Duration = std::time::Duration::from_secs(1);
+
+fn spawn_turn_timer(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TURN_TIMER_TICK);
+        loop {
+            interval.tick().await;
+            let timed_out = expire_turns(&state).await;
+            if !timed_out.is_empty() {
+                info!(
+                    "Auto-drew for timed out players in {} games",
+                    timed_out.len()
+                );
+            }
+        }
+    });
+}
+
+/// Draws for and moves past every player whose turn deadline has passed.
+/// Games in progress without a deadline are given one. Returns the ids of the games
+/// that were advanced. Deadlines are read from the session summaries, so only the games
+/// that need a change are loaded, saved and published.
+async fn expire_turns(state: &AppState) -> Vec<String> {
+    let summaries = match state.session_manager.current_summaries() {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            error!("Failed to list sessions for the turn timer: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut timed_out = Vec::new();
+    for summary in summaries {
+        if summary.status != GameStatus::InProgress
+            || summary
+                .turn_deadline
+                .is_some_and(|deadline| deadline > state.session_manager.now())
+        {
+            continue;
+        }
+        let id = summary.id;
+        let lock = state.session_manager.session_lock(&id);
+        let _guard = lock.lock().await;
+        let Ok(mut session) = state.session_manager.load_session(&id) else {
+            continue;
+        };
+        // A move may have come in since the summary was taken
+        if session.game.status != GameStatus::InProgress {
+            continue;
+        }
+        match session.turn_deadline {
+            Some(deadline) if deadline <= state.session_manager.now() => {
+                let player_id = session.game.current_turn;
+                match session.game.time_out_turn() {
+                    Ok(events) => events.iter().for_each(|event| session.record(event)),
+                    Err(e) => {
+                        error!(
+                            "Failed to time out player {} in game {}: {}",
+                            player_id, id, e
+                        );
+                        continue;
+                    }
+                }
+                info!("Player {} ran out of time in game: {}", player_id, id);
+                timed_out.push(id.clone());
+            }
+            Some(_) => continue,
+            None => {}
+        }
+        session.restart_turn_clock(state.turn_timeout, state.session_manager.now());
+        if let Err(e) = state.session_manager.save_session(&session) {
+            error!("Failed to save game state: {}", e);
+            continue;
+        }
+        state.metrics.move_applied(&session.game);
+        state.updates.publish(&session);
+    }
+    timed_out
+}
+
+pub async fn start_api_server(
+    sessions_dir: PathBuf,
+    config: ServerConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize logging; RUST_LOG overrides the default level
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+    info!("Starting Uno API server...");
+
+    let cors = cors_layer(&config.cors_origins, config.dev)?;
+    if config.dev {
+        info!("Dev mode: accepting requests from any origin");
+    }
+
+    let mut session_manager = match config.snapshot_dir {
+        Some(snapshot_dir) => SessionManager::with_snapshot_dir(sessions_dir, snapshot_dir)?,
+        None => SessionManager::new(sessions_dir)?,
+    };
+    if let Some(interval) = config.write_behind {
+        session_manager = session_manager.with_write_behind();
+        spawn_session_flush(session_manager.clone(), interval);
+    }
+    if let Some(cleanup) = config.session_cleanup {
+        spawn_session_cleanup(session_manager.clone(), cleanup);
+    }
+    let state = AppState {
+        session_manager: session_manager.clone(),
+        updates: GameUpdates::default(),
+        turn_timeout: config.turn_timeout,
+        metrics: Metrics::default(),
+        allow_redeal: config.allow_redeal,
+        max_active_games: config.max_active_games,
+        admin_token_hash: config.admin_token.as_deref().map(auth::hash_token),
+    };
+    if state.turn_timeout.is_some() {
+        spawn_turn_timer(state.clone());
+    }
+
+    // Create a trace layer for logging
+    let trace_layer = TraceLayer::new_for_http()
+        .make_span_with(request_span)
+        .on_response(DefaultOnResponse::new().include_headers(true));
+
+    let app = Router::new()
+        .route("/games", post(create_game))
+        .route("/games", get(list_games))
+        .route("/games/{id}", get(get_game))
+        .route("/players/{name}/games", get(list_player_games))
+        .route("/games/{id}/state", get(get_game_state))
+        .route("/games/{id}/state/at/{move_number}", get(get_state_at))
+        .route("/games/{id}/deck", get(get_deck))
+        .route("/games/{id}/discard", get(get_discard_pile))
+        .route("/games/{id}/hints", get(get_hints))
+        .route("/games/{id}", delete(delete_game))
+        .route("/games/{id}/reload", post(reload_game))
+        .route("/admin/reload-all", post(reload_all))
+        .route("/games/{id}/play", post(play_card))
+        .route("/games/{id}/draw", post(draw_card))
+        .route("/games/{id}/color", post(choose_color))
+        .route("/games/{id}/forfeit", post(forfeit))
+        .route("/games/{id}/events", get(game_updates))
+        .route("/openapi.json", get(get_openapi))
+        .route("/docs", get(get_docs))
+        .route("/games/{id}/pass", post(pass_turn))
+        .route("/games/{id}/challenge", post(challenge))
+        .route("/games/import", post(import_game))
+        .route("/games/{id}/export", get(export_game))
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .route("/games/{id}/players/{player_id}", patch(rename_player))
+        .route("/games/{id}/board", get(get_board))
+        .route("/games/{id}/stats", get(get_stats))
+        .route("/games/{id}/public", get(get_public_state))
+        .route("/games/import-replay", post(import_replay))
+        .route("/games/{id}/simulate", post(simulate_move))
+        .route("/games/{id}/join", post(join_game))
+        .route("/games/{id}/start", post(start_game))
+        .route("/games/{id}/take-penalty", post(take_penalty))
+        .route("/games/{id}/current", get(get_current_player))
+        .route("/metrics", get(get_metrics))
+        .route(
+            "/games/{id}/players/{player_id}/redeal",
+            post(redeal_player),
+        )
+        .route("/games/{id}/advance-bots", post(advance_bots))
+        .route("/games/{id}/kick", post(kick_player))
+        .route("/games/{id}/restart", post(restart_game))
+        .route("/games/{id}/players/{player_id}/sort", post(sort_hand))
+        .route("/games/{id}/deck/composition", get(get_deck_composition))
+        .route(
+            "/games/{id}/players/{player_id}/hand/{index}",
+            get(get_hand_card),
+        )
+        .route("/games", delete(purge_games))
+        .route("/games/{id}/deck/next", get(get_deck_next))
+        .route("/games/{id}/config", get(get_game_config))
+        .route("/games/{id}/moves", post(apply_moves))
+        .layer(RequestBodyLimitLayer::new(MAX_BODY_BYTES))
+        .layer(cors)
+        .layer(trace_layer)
+        // Marked before the trace layer sees them, so tokens are logged as `Sensitive`
+        .layer(SetSensitiveRequestHeadersLayer::new([
+            header::AUTHORIZATION,
+        ]))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+    info!("API server running on http://127.0.0.1:3000");
+    info!("Request/response logging enabled");
+    // Open connections, such as event streams, are dropped rather than waited for
+    tokio::select! {
+        result = axum::serve(listener, app) => result?,
+        () = shutdown_signal() => info!("Shutting down"),
+    }
+    // Save whatever write-behind hasn't yet
+    match session_manager.flush() {
+        Ok(written) => info!("Saved {} sessions before exiting", written),
+        Err(e) => error!("Failed to save sessions before exiting: {}", e),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::card::CardType;
+    use crate::uno_game::{Direction, Player};
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+    };
+    use serde_json::json;
+    use std::fs;
     use tempfile::tempdir;
     use tower::ServiceExt;
 
-    async fn setup_test_app() -> (Router, tempfile::TempDir) {
-        let temp_dir = tempdir().unwrap();
+    async fn setup_test_app() -> (Router, tempfile::TempDir) {
+        setup_test_app_with_config(ServerConfig::default()).await
+    }
+
+    async fn setup_test_app_with_config(config: ServerConfig) -> (Router, tempfile::TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let state = AppState {
+            session_manager,
+            updates: GameUpdates::default(),
+            turn_timeout: config.turn_timeout,
+            metrics: Metrics::default(),
+            allow_redeal: config.allow_redeal,
+            max_active_games: config.max_active_games,
+            admin_token_hash: config.admin_token.as_deref().map(auth::hash_token),
+        };
+
+        let cors = cors_layer(&config.cors_origins, config.dev).unwrap();
+        let trace_layer = TraceLayer::new_for_http()
+            .make_span_with(request_span)
+            .on_response(DefaultOnResponse::new().include_headers(true));
+
+        let app = Router::new()
+            .route("/games", post(create_game))
+            .route("/games", get(list_games))
+            .route("/games/{id}", get(get_game))
+            .route("/players/{name}/games", get(list_player_games))
+            .route("/games/{id}/state", get(get_game_state))
+            .route("/games/{id}/state/at/{move_number}", get(get_state_at))
+            .route("/games/{id}/deck", get(get_deck))
+            .route("/games/{id}/discard", get(get_discard_pile))
+            .route("/games/{id}/hints", get(get_hints))
+            .route("/games/{id}", delete(delete_game))
+            .route("/games/{id}/reload", post(reload_game))
+            .route("/admin/reload-all", post(reload_all))
+            .route("/games/{id}/play", post(play_card))
+            .route("/games/{id}/draw", post(draw_card))
+            .route("/games/{id}/color", post(choose_color))
+            .route("/games/{id}/forfeit", post(forfeit))
+            .route("/games/{id}/events", get(game_updates))
+            .route("/openapi.json", get(get_openapi))
+            .route("/docs", get(get_docs))
+            .route("/games/{id}/pass", post(pass_turn))
+            .route("/games/{id}/challenge", post(challenge))
+            .route("/games/import", post(import_game))
+            .route("/games/{id}/export", get(export_game))
+            .route("/health", get(health))
+            .route("/ready", get(ready))
+            .route("/games/{id}/players/{player_id}", patch(rename_player))
+            .route("/games/{id}/board", get(get_board))
+            .route("/games/{id}/stats", get(get_stats))
+            .route("/games/{id}/public", get(get_public_state))
+            .route("/games/import-replay", post(import_replay))
+            .route("/games/{id}/simulate", post(simulate_move))
+            .route("/games/{id}/join", post(join_game))
+            .route("/games/{id}/start", post(start_game))
+            .route("/games/{id}/take-penalty", post(take_penalty))
+            .route("/games/{id}/current", get(get_current_player))
+            .route("/metrics", get(get_metrics))
+            .route(
+                "/games/{id}/players/{player_id}/redeal",
+                post(redeal_player),
+            )
+            .route("/games/{id}/advance-bots", post(advance_bots))
+            .route("/games/{id}/kick", post(kick_player))
+            .route("/games/{id}/restart", post(restart_game))
+            .route("/games/{id}/players/{player_id}/sort", post(sort_hand))
+            .route("/games/{id}/deck/composition", get(get_deck_composition))
+            .route(
+                "/games/{id}/players/{player_id}/hand/{index}",
+                get(get_hand_card),
+            )
+            .route("/games", delete(purge_games))
+            .route("/games/{id}/deck/next", get(get_deck_next))
+            .route("/games/{id}/config", get(get_game_config))
+            .route("/games/{id}/moves", post(apply_moves))
+            .layer(RequestBodyLimitLayer::new(MAX_BODY_BYTES))
+            .layer(cors)
+            .layer(trace_layer)
+            .layer(SetSensitiveRequestHeadersLayer::new([
+                header::AUTHORIZATION,
+            ]))
+            .with_state(state);
+
+        (app, temp_dir)
+    }
+
+    /// Puts the cards a hand-built position leaves out at the bottom of its deck, so the
+    /// game holds a full deck and passes `UnoGame::validate` when it is loaded. The
+    /// hands, the discard pile and the top of the deck are unchanged.
+    fn with_full_deck(mut game: UnoGame) -> UnoGame {
+        let mut missing = UnoGame::unshuffled_deck(&game.config);
+        let in_play = game
+            .players
+            .iter()
+            .flat_map(|p| &p.hand)
+            .chain(&game.deck)
+            .chain(game.discard_pile.iter().map(|(card, _)| card));
+        for card in in_play {
+            if let Some(index) = missing.iter().position(|c| c == card) {
+                missing.swap_remove(index);
+            } else {
+                // More copies than a real deck has, so leave out something else instead
+                missing.pop();
+            }
+        }
+        game.deck.splice(0..0, missing);
+        game
+    }
+
+    #[tokio::test]
+    async fn test_create_game() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.players.len(), 2);
+        assert_eq!(response.players[0].name, "Alice");
+        assert_eq!(response.players[1].name, "Bob");
+        assert_eq!(response.current_turn, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_game_with_config() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let create = |config: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/games")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({ "player_names": ["Alice", "Bob"], "config": config }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(create(
+                json!({ "seven_zero_enabled": true, "max_hand_size": 20 }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let created: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        let config = get_json(&app, &format!("/games/{}/config", created.id)).await;
+        assert_eq!(config["seven_zero_enabled"], true);
+        assert_eq!(config["max_hand_size"], 20);
+        assert_eq!(config["starting_hand_size"], 7);
+        assert_matches_schema(&openapi::spec(), "GameConfig", &config);
+
+        let response = app
+            .clone()
+            .oneshot(create(json!({ "no_such_rule": true })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = app
+            .clone()
+            .oneshot(create(
+                json!({ "draw_then_play": true, "draw_until_playable": true }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_list_games() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        app.clone().oneshot(create_request).await.unwrap();
+
+        // Then list games
+        let list_request = Request::builder()
+            .method("GET")
+            .uri("/games")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(list_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let page: GamePage<String> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page.games.len(), 1);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.next_offset, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_game() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then get the specific game
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let retrieved_game: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(retrieved_game.id, game.id);
+    }
+
+    #[tokio::test]
+    async fn test_play_card() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Get the current game state
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(get_request).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game_state: GameResponse = serde_json::from_slice(&body).unwrap();
+        let tokens = game.tokens.unwrap();
+        let bearer = |player_id: usize| format!("Bearer {}", tokens[&player_id]);
+
+        // Verify that the current player has cards
+        assert!(
+            game_state.players[game_state.current_turn].hand_size > 0,
+            "Current player should have cards"
+        );
+
+        // If the top card is a Wild Draw Four, we need to choose a color first
+        if game_state.discard_pile_top.as_ref().unwrap().card_type == "WildDrawFour" {
+            let color_request = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/color", game.id))
+                .header("If-Match", "*")
+                .header("Authorization", bearer(game_state.current_turn))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "color": "red"
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+
+            let response = app.clone().oneshot(color_request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        // Draw until the current player has a playable card, then play it
+        let max_attempts = 20; // Limit the number of attempts to avoid infinite loops
+        for _ in 0..max_attempts {
+            let hints_request = Request::builder()
+                .method("GET")
+                .uri(format!("/games/{}/hints", game.id))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = app.clone().oneshot(hints_request).await.unwrap();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let hints: HintsResponse = serde_json::from_slice(&body).unwrap();
+            let current = get_json(&app, &format!("/games/{}", game.id)).await["current_turn"]
+                .as_u64()
+                .unwrap() as usize;
+
+            if let Some((card_index, _)) = hints.playable_cards.first() {
+                let play_request = Request::builder()
+                    .method("POST")
+                    .uri(format!("/games/{}/play", game.id))
+                    .header("If-Match", "*")
+                    .header("Authorization", bearer(current))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(
+                        json!({
+                            "card_index": card_index,
+                            "color": "red"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap();
+
+                let response = app.clone().oneshot(play_request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                return;
+            }
+
+            let draw_request = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/draw", game.id))
+                .header("If-Match", "*")
+                .header("Authorization", bearer(current))
+                .body(Body::empty())
+                .unwrap();
+
+            let response = app.clone().oneshot(draw_request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        panic!(
+            "Failed to find a playable card after {} attempts",
+            max_attempts
+        );
+    }
+
+    #[tokio::test]
+    async fn test_draw_card() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then draw a card
+        let token = &game.tokens.unwrap()[&game.current_turn];
+        let draw_request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/draw", game.id))
+            .header("If-Match", "*")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(draw_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_pass_turn() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.draw_then_play = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        *game.deck.last_mut().unwrap() = Card::new(Color::Red, CardType::Number(9));
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let post = |action: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/{}", session.id, action))
+                .header("If-Match", "*")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Passing before drawing is rejected
+        let response = app.clone().oneshot(post("pass")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.clone().oneshot(post("draw")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(post("pass")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.current_turn, 1);
+        assert_eq!(loaded.game.players[0].hand.len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_lobby_join_then_start() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let post = |uri: String, body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/games".to_string(),
+                json!({ "player_names": ["Alice"], "lobby": true }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = game["id"].as_str().unwrap();
+        assert_eq!(game["status"], "Lobby");
+        assert_eq!(game["discard_pile_top"], serde_json::Value::Null);
+        let start = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/start", id));
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+        let alice_token = game["tokens"]["0"].as_str().unwrap();
+
+        // One player isn't enough to start
+        let response = app.clone().oneshot(start(Some(alice_token))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                format!("/games/{}/join", id),
+                json!({ "name": "Bob" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let joined: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "JoinGameResponse", &joined);
+        assert_matches_schema(&spec, "GameStateResponse", &joined["state"]);
+        assert_eq!(joined["player_id"], 1);
+        assert_eq!(joined["state"]["players"].as_array().unwrap().len(), 2);
+
+        // No moves until the game starts
+        let token = game["tokens"]["0"].as_str().unwrap();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/games/{}/draw", id))
+                    .header("If-Match", "*")
+                    .header("Authorization", format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "GAME_NOT_STARTED");
+
+        // Only a seated player can start the game
+        let response = app.clone().oneshot(start(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "TOKEN_REQUIRED");
+        let response = app.clone().oneshot(start(Some("wrong"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "INVALID_TOKEN");
+
+        let response = app
+            .clone()
+            .oneshot(start(joined["token"].as_str()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let started: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(started["status"], "In Progress");
+        assert_eq!(started["players"][1]["hand_size"], 7);
+        assert!(started["discard_pile_top"].is_object());
+
+        let response = app
+            .oneshot(post(
+                format!("/games/{}/join", id),
+                json!({ "name": "Carol" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_move_saves_nothing() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(2)),
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Blue, CardType::Number(7)),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+        let before = get_json(&app, &format!("/games/{}/state", session.id)).await;
+
+        let simulate = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/simulate", session.id))
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(simulate(json!({ "action": "play", "card_index": 0 })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let simulated: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "SimulateResponse", &simulated);
+        assert_eq!(simulated["event"]["CardPlayed"]["player_id"], 0);
+        assert_eq!(simulated["state"]["current_turn"], 1);
+        assert_eq!(simulated["state"]["players"][0]["hand_size"], 2);
+
+        // Illegal moves are rejected with the same codes as the real endpoints
+        for (body, code) in [
+            (json!({ "action": "play", "card_index": 2 }), "INVALID_MOVE"),
+            (
+                json!({ "action": "play", "card_index": 1 }),
+                "COLOR_REQUIRED",
+            ),
+            (
+                json!({ "action": "play", "card_index": 1, "color": "pink" }),
+                "INVALID_COLOR",
+            ),
+        ] {
+            let response = app.clone().oneshot(simulate(body)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(error["code"], code);
+            if code == "INVALID_MOVE" {
+                assert_eq!(
+                    error["error"],
+                    "Cannot play Blue 7 on Red 5: colors and numbers differ"
+                );
+            }
+        }
+
+        let response = app
+            .clone()
+            .oneshot(simulate(json!({ "action": "draw" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let after = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(after, before);
+    }
+
+    #[tokio::test]
+    async fn test_moves_apply_all_or_nothing() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Number(1)),
+        ];
+        game.players[1].hand = vec![
+            Card::new(Color::Green, CardType::Number(7)),
+            Card::new(Color::Yellow, CardType::Number(2)),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+        let moves = |moves: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/moves", session.id))
+                .header("Content-Type", "application/json")
+                .header("If-Match", "*")
+                .body(Body::from(json!({ "moves": moves }).to_string()))
+                .unwrap()
+        };
+        let before = get_json(&app, &format!("/games/{}/state", session.id)).await;
+
+        // Alice's 7 is legal, but Bob's Yellow 2 can't go on it
+        let response = app
+            .clone()
+            .oneshot(moves(json!([
+                { "action": "play", "card_index": 0 },
+                { "action": "play", "card_index": 1 },
+            ])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, "INVALID_MOVE");
+        assert!(error.error.starts_with("Move 1: "));
+        let after = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(after, before);
+
+        let response = app
+            .clone()
+            .oneshot(moves(json!([
+                { "action": "play", "card_index": 0 },
+                { "action": "play", "card_index": 0 },
+            ])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let applied: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_matches_schema(&openapi::spec(), "MovesResponse", &applied);
+        assert_eq!(applied["events"].as_array().unwrap().len(), 2);
+        assert_eq!(applied["state"]["current_turn"], 0);
+        assert_eq!(
+            applied["state"]["version"],
+            before["version"].as_u64().unwrap() + 2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_current_player() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(7)),
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Green, CardType::Number(5)),
+            Card::new(Color::Yellow, CardType::DrawTwo),
+            Card::new(Color::Wild, CardType::Wild),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let current = get_json(&app, &format!("/games/{}/current", session.id)).await;
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "CurrentPlayerResponse", &current);
+        assert_eq!(current["player_id"], 0);
+        assert_eq!(current["name"], "Alice");
+        assert_eq!(current["effective_color"], "Red");
+        let flags: Vec<bool> = current["hand"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|card| card["playable"].as_bool().unwrap())
+            .collect();
+        assert_eq!(flags, vec![false, true, true, false, true]);
+        assert_eq!(current["hand"][4]["index"], 4);
+        assert_eq!(current["actions"], json!(["play", "draw"]));
+        assert_eq!(
+            current["legal_actions"],
+            json!([
+                { "action": "play", "index": 1 },
+                { "action": "play", "index": 2 },
+                { "action": "play", "index": 4 },
+                { "action": "draw" },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_take_penalty() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::DrawTwo),
+            Card::new(Color::Red, CardType::Number(2)),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let take_penalty = || {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/take-penalty", session.id))
+                .header("If-Match", "*")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Nothing to take before the Draw Two is played
+        let response = app.clone().oneshot(take_penalty()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "NO_PENALTY");
+
+        let play = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/play", session.id))
+            .header("Content-Type", "application/json")
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "card_index": 0 }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(play).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.clone().oneshot(take_penalty()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let penalty: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "PenaltyResponse", &penalty);
+        assert_eq!(penalty["cards_drawn"], 2);
+        assert_eq!(penalty["event"]["DrawTwo"]["player_id"], 1);
+        assert_eq!(penalty["discard_pile_top"]["card_type"], "DrawTwo");
+
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["pending_draws"], 0);
+        assert_eq!(state["current_turn"], 0);
+        assert_eq!(state["players"][1]["hand_size"], 9);
+    }
+
+    #[tokio::test]
+    async fn test_challenge() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::WildDrawFour),
+            Card::new(Color::Red, CardType::Number(2)),
+        ];
+        game.play_card(0, 0).unwrap();
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/challenge", session.id))
+            .header("If-Match", "*")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let event: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event["ChallengeResult"]["success"], true);
+        assert_eq!(event["ChallengeResult"]["drawer_id"], 0);
+
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.players[0].hand.len(), 5);
+        assert_eq!(loaded.game.pending_draws, 0);
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.draw_card(0).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let export = get_json(&app, &format!("/games/{}/export", session.id)).await;
+
+        let import = Request::builder()
+            .method("POST")
+            .uri("/games/import")
+            .header("Content-Type", "application/json")
+            .body(Body::from(export.to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(import).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let imported: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert_ne!(imported.id, session.id);
+
+        let loaded = session_manager.load_session(&imported.id).unwrap();
+        assert_eq!(loaded.game, session.game);
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_inconsistent_game() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let mut export = get_json(&app, &format!("/games/{}/export", session.id)).await;
+        export["game"]["current_turn"] = json!(7);
+
+        let import = Request::builder()
+            .method("POST")
+            .uri("/games/import")
+            .header("Content-Type", "application/json")
+            .body(Body::from(export.to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(import).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(session_manager.list_sessions().unwrap().len(), 1);
+
+        // A card copied into a hand is caught too
+        let mut export = get_json(&app, &format!("/games/{}/export", session.id)).await;
+        let card = export["game"]["players"][0]["hand"][0].clone();
+        export["game"]["players"][0]["hand"]
+            .as_array_mut()
+            .unwrap()
+            .push(card);
+        let import = Request::builder()
+            .method("POST")
+            .uri("/games/import")
+            .header("Content-Type", "application/json")
+            .body(Body::from(export.to_string()))
+            .unwrap();
+        let response = app.oneshot(import).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert!(
+            error.error.contains("Expected 108 cards"),
+            "{}",
+            error.error
+        );
+        assert_eq!(session_manager.list_sessions().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_choose_color() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then choose a color; the first card turned over is the current player's to color
+        let token = &game.tokens.unwrap()[&game.current_turn];
+        let color_request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/color", game.id))
+            .header("If-Match", "*")
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "color": "red"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(color_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_delete_game() {
+        let (app, _temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        let delete = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("DELETE")
+                .uri(format!("/games/{}", game.id));
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        // Deleting needs the admin token
+        let response = app.clone().oneshot(delete(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "TOKEN_REQUIRED");
+
+        let response = app.clone().oneshot(delete(Some("wrong"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "INVALID_TOKEN");
+
+        // Then delete the game
+        let response = app.clone().oneshot(delete(Some("s3cret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // Verify the game is deleted
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_error_cases() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // Test invalid game ID
+        let get_request = Request::builder()
+            .method("GET")
+            .uri("/games/invalid-id")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Test invalid color
+        let color_request = Request::builder()
+            .method("POST")
+            .uri("/games/some-id/color")
+            .header("If-Match", "*")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "color": "invalid"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(color_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_session_is_an_error_not_a_panic() {
+        let (app, temp_dir) = setup_test_app().await;
+        let mut session = GameSession::new(
+            "corrupt".to_string(),
+            UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap(),
+        );
+        session.game.discard_pile.clear();
+        session.save(temp_dir.path()).unwrap();
+
+        for uri in [
+            "/games/corrupt",
+            "/games/corrupt/state",
+            "/games/corrupt/public",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(
+                response.status(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "{}",
+                uri
+            );
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert!(error["error"]
+                .as_str()
+                .unwrap()
+                .contains("Discard pile must not be empty"));
+        }
+
+        let play = Request::builder()
+            .method("POST")
+            .uri("/games/corrupt/play")
+            .header("Content-Type", "application/json")
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "card_index": 0 }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(play).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        // The server carries on serving other games
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/games/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_play_errors_carry_their_kind() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let err = game.play_card(1, 0).unwrap_err();
+        let response = ApiError::from(err).into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            error,
+            json!({ "error": "Not your turn", "code": "NOT_YOUR_TURN" })
+        );
+
+        // Over HTTP, a card index outside the hand is reported as such
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/play", session.id))
+            .header("Content-Type", "application/json")
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "card_index": 99 }).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "CARD_NOT_IN_HAND");
+    }
+
+    #[tokio::test]
+    async fn test_get_game_state() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then get the game state
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/state", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game_state: GameStateResponse = serde_json::from_slice(&body).unwrap();
+
+        // Verify the game state
+        assert_eq!(game_state.id, game.id);
+        assert_eq!(game_state.current_turn, 0);
+        assert_eq!(game_state.direction, "Clockwise");
+        assert_eq!(game_state.players.len(), 2);
+        assert_eq!(game_state.players[0].name, "Alice");
+        assert_eq!(game_state.players[1].name, "Bob");
+        assert_eq!(game_state.players[0].hand.len(), 7); // Each player starts with 7 cards
+        assert_eq!(game_state.players[1].hand.len(), 7);
+
+        // Verify the top card has valid properties
+        assert!(!game_state
+            .discard_pile_top
+            .as_ref()
+            .unwrap()
+            .color
+            .is_empty());
+        assert!(!game_state
+            .discard_pile_top
+            .as_ref()
+            .unwrap()
+            .card_type
+            .is_empty());
+
+        assert!(game_state.deck_cards_remaining > 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_deck() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then get the deck
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/deck", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let deck: DeckResponse = serde_json::from_slice(&body).unwrap();
+
+        // Verify the deck
+        assert!(!deck.cards.is_empty(), "Deck should not be empty");
+        assert_eq!(
+            deck.cards.len(),
+            game.deck_cards_remaining,
+            "Deck size should match deck_cards_remaining"
+        );
+
+        // Verify each card has valid properties
+        for card in deck.cards {
+            assert!(!card.color.is_empty(), "Card color should not be empty");
+            assert!(!card.card_type.is_empty(), "Card type should not be empty");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deck_lists_next_draw_last() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let deck = get_json(&app, &format!("/games/{}/deck", session.id)).await;
+        let reversed = get_json(&app, &format!("/games/{}/deck?reversed=true", session.id)).await;
+        let cards = deck["cards"].as_array().unwrap();
+        let mut reversed_cards = reversed["cards"].as_array().unwrap().clone();
+        reversed_cards.reverse();
+        assert_eq!(*cards, reversed_cards);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/draw", session.id))
+            .header("If-Match", "*")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let event: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let drawn: Card = serde_json::from_value(event["CardDrawn"]["card"].clone()).unwrap();
+        let expected = serde_json::to_value(CardResponse::from_card(&drawn, None)).unwrap();
+        assert_eq!(cards.last().unwrap(), &expected);
+        assert_eq!(reversed["cards"][0], expected);
+    }
+
+    #[tokio::test]
+    async fn test_get_deck_composition() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.deck = vec![
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Blue, CardType::Skip),
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Skip),
+        ];
+        let mut game = with_full_deck(game);
+        let rest: Vec<Card> = game.deck.drain(..game.deck.len() - 4).collect();
+        game.discard_pile
+            .splice(0..0, rest.into_iter().map(|card| (card, usize::MAX)));
+        let session = session_manager.create_session(game).unwrap();
+
+        let composition = get_json(&app, &format!("/games/{}/deck/composition", session.id)).await;
+        assert_eq!(
+            composition,
+            json!({
+                "total": 4,
+                "cards": [
+                    { "color": "Red", "card_type": "Number(7)", "count": 1 },
+                    { "color": "Blue", "card_type": "Skip", "count": 2 },
+                    { "color": "Wild", "card_type": "Wild", "count": 1 },
+                ]
+            })
+        );
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "DeckCompositionResponse", &composition);
+        assert_matches_schema(&spec, "CardCountResponse", &composition["cards"][0]);
+    }
+
+    #[tokio::test]
+    async fn test_get_deck_next_requires_config() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.deck = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Skip),
+        ];
+        let mut game = with_full_deck(game);
+        let closed = session_manager.create_session(game.clone()).unwrap();
+        game.config.allow_deck_peek = true;
+        let open = session_manager.create_session(game.clone()).unwrap();
+        let deck = std::mem::take(&mut game.deck);
+        game.discard_pile
+            .splice(0..0, deck.into_iter().map(|card| (card, usize::MAX)));
+        let empty = session_manager.create_session(game).unwrap();
+
+        let peek = |id: &str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .uri(format!("/games/{}/deck/next", id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = peek(&closed.id).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, "PEEK_DISABLED");
+
+        let next = get_json(&app, &format!("/games/{}/deck/next", open.id)).await;
+        assert_eq!(next, json!({ "color": "Blue", "card_type": "Skip" }));
+
+        let response = peek(&empty.id).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_discard_pile() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        // First create a game
+        let create_request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "player_names": ["Alice", "Bob"]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let create_response = app.clone().oneshot(create_request).await.unwrap();
+        let body = to_bytes(create_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+
+        // Then get the discard pile
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/discard", game.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let discard_pile: DiscardPileResponse = serde_json::from_slice(&body).unwrap();
+
+        // Verify the discard pile
+        assert!(
+            !discard_pile.cards.is_empty(),
+            "Discard pile should not be empty"
+        );
+
+        // Verify each card has valid properties
+        for (card, player_id) in discard_pile.cards {
+            assert!(!card.color.is_empty(), "Card color should not be empty");
+            assert!(!card.card_type.is_empty(), "Card type should not be empty");
+            // The first card should have no player_id (it's the initial card)
+            if let Some(player_id) = player_id {
+                assert!(player_id < 2, "Player ID should be valid");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_hints() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // Build a game with a known top card and a known hand
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(3)),
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Green, CardType::Number(5)),
+            Card::new(Color::Yellow, CardType::DrawTwo),
+            Card::new(Color::Wild, CardType::Wild),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let get_request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/hints", session.id))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(get_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let hints: HintsResponse = serde_json::from_slice(&body).unwrap();
+
+        let indices: Vec<usize> = hints.playable_cards.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1, 2, 4]);
+        assert_eq!(hints.playable_cards[0].1.card_type, "Skip");
+        assert!(!hints.must_draw);
+        assert_eq!(hints.player_id, 0);
+    }
+
+    async fn next_sse_state(stream: &mut axum::body::BodyDataStream) -> GameStateResponse {
+        use futures_util::StreamExt;
+        let mut buffer = String::new();
+        loop {
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+                .await
+                .expect("timed out waiting for an update")
+                .expect("stream ended")
+                .unwrap();
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+            if let Some(data) = buffer.lines().find_map(|line| line.strip_prefix("data: ")) {
+                return serde_json::from_str(data).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_game_updates_stream() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand[0] = Card::new(Color::Red, CardType::Number(3));
+        let session = session_manager.create_session(game).unwrap();
+
+        let subscribe = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/events?player_id=1", session.id))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(subscribe).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut stream = response.into_body().into_data_stream();
+
+        // The current state arrives on connect, scoped to Bob
+        let initial = next_sse_state(&mut stream).await;
+        assert_eq!(initial.current_turn, 0);
+        assert!(initial.players[0].hand.is_empty());
+        assert_eq!(initial.players[0].hand_size, 7);
+        assert_eq!(initial.players[1].hand.len(), 7);
+
+        let play = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/play", session.id))
+            .header("If-Match", "*")
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({ "card_index": 0 }).to_string()))
+            .unwrap();
+        let response = app.oneshot(play).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let update = next_sse_state(&mut stream).await;
+        assert_eq!(update.current_turn, 1);
+        assert_eq!(update.players[0].hand_size, 6);
+        assert_eq!(update.discard_pile_top.unwrap().card_type, "Number(3)");
+    }
+
+    async fn get_json(app: &Router, uri: &str) -> serde_json::Value {
+        let request = Request::builder()
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn assert_matches_schema(spec: &serde_json::Value, schema: &str, value: &serde_json::Value) {
+        let properties = spec["components"]["schemas"][schema]["properties"]
+            .as_object()
+            .unwrap();
+        let mut documented: Vec<&String> = properties.keys().collect();
+        let mut actual: Vec<&String> = value.as_object().unwrap().keys().collect();
+        documented.sort();
+        actual.sort();
+        assert_eq!(documented, actual, "schema {} is out of date", schema);
+    }
+
+    #[tokio::test]
+    async fn test_state_at_replays_recorded_moves() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let create = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "player_names": ["Alice", "Bob"], "seed": 11 }).to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(create).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = game["id"].as_str().unwrap();
+        let dealt = get_json(&app, &format!("/games/{}/state", id)).await;
+
+        // Each player draws, passing if the drawn card leaves the turn with them
+        let current_turn = |state: &serde_json::Value| state["current_turn"].to_string();
+        let mut moves = 0;
+        for _ in 0..4 {
+            let player = current_turn(&get_json(&app, &format!("/games/{}/state", id)).await);
+            for action in ["draw", "pass"] {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri(format!("/games/{}/{}", id, action))
+                    .header("If-Match", "*")
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", game["tokens"][&player].as_str().unwrap()),
+                    )
+                    .body(Body::empty())
+                    .unwrap();
+                let response = app.clone().oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                moves += 1;
+                let state = get_json(&app, &format!("/games/{}/state", id)).await;
+                if current_turn(&state) != player {
+                    break;
+                }
+            }
+        }
+        assert!(moves >= 4);
+
+        // The first discard belongs to nobody, so the current player picks its color
+        let state = get_json(&app, &format!("/games/{}/state", id)).await;
+        let color = if state["discard_pile_top"]["color"] == "Green" {
+            "blue"
+        } else {
+            "green"
+        };
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/color", id))
+            .header("Content-Type", "application/json")
+            .header("If-Match", "*")
+            .header(
+                "Authorization",
+                format!(
+                    "Bearer {}",
+                    game["tokens"][&current_turn(&state)].as_str().unwrap()
+                ),
+            )
+            .body(Body::from(json!({ "color": color }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        moves += 1;
+        let current = get_json(&app, &format!("/games/{}/state", id)).await;
+
+        let start = get_json(&app, &format!("/games/{}/state/at/0", id)).await;
+        assert_eq!(start["players"], dealt["players"]);
+        let middle = get_json(&app, &format!("/games/{}/state/at/2", id)).await;
+        assert_matches_schema(&openapi::spec(), "GameStateResponse", &middle);
+        assert_ne!(middle["players"], current["players"]);
+        assert!(middle["version"].as_u64() < current["version"].as_u64());
+        let end = get_json(&app, &format!("/games/{}/state/at/{}", id, moves)).await;
+        assert_eq!(end, current);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("/games/{}/state/at/{}", id, moves + 1))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_moves_need_the_acting_players_token() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let create = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "player_names": ["Alice", "Bob"] }).to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(create).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_matches_schema(&openapi::spec(), "GameResponse", &game);
+        let id = game["id"].as_str().unwrap();
+        let current = game["current_turn"].as_u64().unwrap() as usize;
+        let token = |player_id: usize| game["tokens"][player_id.to_string()].as_str().unwrap();
+
+        // Tokens are handed out once; reading the game doesn't show them
+        let fetched = get_json(&app, &format!("/games/{}", id)).await;
+        assert_eq!(fetched["tokens"], serde_json::Value::Null);
+
+        let draw = |authorization: Option<String>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/draw", id))
+                .header("If-Match", "*");
+            if let Some(authorization) = authorization {
+                builder = builder.header("Authorization", authorization);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(draw(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "TOKEN_REQUIRED");
+
+        // The other player's token doesn't let them move out of turn
+        let other = format!("Bearer {}", token(1 - current));
+        let response = app.clone().oneshot(draw(Some(other))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "INVALID_TOKEN");
+        let state = get_json(&app, &format!("/games/{}/state", id)).await;
+        assert_eq!(state["version"], 0);
+
+        let own = format!("Bearer {}", token(current));
+        let response = app.clone().oneshot(draw(Some(own))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Routes for a named player check that player's token
+        let rename = |player_id: usize, authorization: String| {
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/games/{}/players/{}", id, player_id))
+                .header("Content-Type", "application/json")
+                .header("Authorization", authorization)
+                .body(Body::from(json!({ "name": "Zed" }).to_string()))
+                .unwrap()
+        };
+        let response = app
+            .clone()
+            .oneshot(rename(0, format!("Bearer {}", token(1))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app
+            .oneshot(rename(0, format!("Bearer {}", token(0))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_if_match_versions() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let get_state = || {
+            Request::builder()
+                .method("GET")
+                .uri(format!("/games/{}/state", session.id))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let draw = |if_match: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/draw", session.id));
+            if let Some(tag) = if_match {
+                builder = builder.header("If-Match", tag);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get_state()).await.unwrap();
+        let etag = response.headers()["ETag"].to_str().unwrap().to_string();
+        assert_eq!(etag, "\"0\"");
+
+        // Drawing against the current version succeeds and bumps it
+        let response = app.clone().oneshot(draw(Some(&etag))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["ETag"], "\"1\"");
+
+        // A second client still holding the old version is turned away
+        let response = app.clone().oneshot(draw(Some(&etag))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app.clone().oneshot(draw(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+
+        let response = app.oneshot(get_state()).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let state: GameStateResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(state.version, 1);
+        assert_eq!(state.current_turn, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cors_allows_only_configured_origins() {
+        let (app, _temp_dir) = setup_test_app_with_config(ServerConfig {
+            cors_origins: vec!["https://uno.example.com".to_string()],
+            ..ServerConfig::default()
+        })
+        .await;
+        let preflight = |origin: &str| {
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/games")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://uno.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://uno.example.com"
+        );
+        let allowed = response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap();
+        assert!(allowed.contains("authorization"));
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(!response
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[test]
+    fn test_cors_layer_rejects_invalid_origin() {
+        assert!(cors_layer(&["https://uno.example.com\n".to_string()], false).is_err());
+        assert!(cors_layer(&[], false).is_ok());
+        assert!(cors_layer(&["not\nvalid".to_string()], true).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_and_ready() {
+        let (app, _temp_dir) = setup_test_app().await;
+
+        let health = get_json(&app, "/health").await;
+        assert_eq!(health["status"], "ok");
+        assert_eq!(health["version"], env!("CARGO_PKG_VERSION"));
+
+        let ready = get_json(&app, "/ready").await;
+        assert_eq!(ready["status"], "ready");
+    }
+
+    #[tokio::test]
+    async fn test_reload_serves_a_game_edited_on_disk() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("letmein".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
         let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
-        let state = AppState { session_manager };
+        let id = session_manager
+            .create_session(UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap())
+            .unwrap()
+            .id;
+        let rename_on_disk = |name: &str| {
+            let mut session = GameSession::load(&id, temp_dir.path()).unwrap();
+            session.game.players[1].name = name.to_string();
+            session.save(temp_dir.path()).unwrap();
+        };
+        let bob = |state: &serde_json::Value| state["players"][1]["name"].clone();
+        let reload = |uri: String, token: Option<&str>| {
+            let mut builder = Request::builder().method("POST").uri(uri);
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
 
-        let cors = CorsLayer::permissive();
-        let trace_layer = TraceLayer::new_for_http()
-            .make_span_with(DefaultMakeSpan::new().include_headers(true))
-            .on_response(DefaultOnResponse::new().include_headers(true));
+        // The first read caches the game, so the edit isn't seen
+        get_json(&app, &format!("/games/{}/state", id)).await;
+        rename_on_disk("Robert");
+        let state = get_json(&app, &format!("/games/{}/state", id)).await;
+        assert_eq!(bob(&state), "Bob");
 
-        let app = Router::new()
-            .route("/games", post(create_game))
-            .route("/games", get(list_games))
-            .route("/games/{id}", get(get_game))
-            .route("/games/{id}/state", get(get_game_state))
-            .route("/games/{id}/deck", get(get_deck))
-            .route("/games/{id}/discard", get(get_discard_pile))
-            .route("/games/{id}", delete(delete_game))
-            .route("/games/{id}/play", post(play_card))
-            .route("/games/{id}/draw", post(draw_card))
-            .route("/games/{id}/color", post(choose_color))
-            .layer(cors)
-            .layer(trace_layer)
-            .with_state(state);
+        let uri = format!("/games/{}/reload", id);
+        let response = app
+            .clone()
+            .oneshot(reload(uri.clone(), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .clone()
+            .oneshot(reload(uri.clone(), Some("guess")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
 
-        (app, temp_dir)
-    }
+        let response = app
+            .clone()
+            .oneshot(reload(uri, Some("letmein")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let reloaded: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(bob(&reloaded), "Robert");
+        let state = get_json(&app, &format!("/games/{}/state", id)).await;
+        assert_eq!(bob(&state), "Robert");
 
-    #[tokio::test]
-    async fn test_create_game() {
-        let (app, _temp_dir) = setup_test_app().await;
+        // Clearing the whole cache does the same for every game
+        rename_on_disk("Bobby");
+        let response = app
+            .clone()
+            .oneshot(reload("/admin/reload-all".to_string(), Some("letmein")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let cleared: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_matches_schema(&openapi::spec(), "ReloadAllResponse", &cleared);
+        assert_eq!(cleared["dropped"], 1);
+        let state = get_json(&app, &format!("/games/{}/state", id)).await;
+        assert_eq!(bob(&state), "Bobby");
 
-        let request = Request::builder()
-            .method("POST")
-            .uri("/games")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
-            ))
+        // Without an admin token the routes are off
+        let (app, _temp_dir) = setup_test_app().await;
+        let response = app
+            .oneshot(reload("/admin/reload-all".to_string(), Some("letmein")))
+            .await
             .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "ADMIN_DISABLED");
+    }
 
-        let response = app.oneshot(request).await.unwrap();
+    #[tokio::test]
+    async fn test_create_game_refused_when_server_is_full() {
+        let (app, _temp_dir) = setup_test_app_with_config(ServerConfig {
+            max_active_games: Some(1),
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+        let create = || {
+            Request::builder()
+                .method("POST")
+                .uri("/games")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({ "player_names": ["Alice", "Bob"] }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(create()).await.unwrap();
         assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
+        let response = app.clone().oneshot(create()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response: GameResponse = serde_json::from_slice(&body).unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "SERVER_FULL");
 
-        assert_eq!(response.players.len(), 2);
-        assert_eq!(response.players[0].name, "Alice");
-        assert_eq!(response.players[1].name, "Bob");
-        assert_eq!(response.current_turn, 0);
+        // Deleting the game makes room again
+        let delete = Request::builder()
+            .method("DELETE")
+            .uri(format!("/games/{}", game["id"].as_str().unwrap()))
+            .header("Authorization", "Bearer s3cret")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(delete).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.oneshot(create()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
     }
 
     #[tokio::test]
-    async fn test_list_games() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_ready_fails_when_sessions_dir_is_not_writable() {
+        let (app, temp_dir) = setup_test_app().await;
+        // Removing the directory makes writes fail even when tests run as root,
+        // which ignores read-only permissions
+        fs::remove_dir_all(temp_dir.path()).unwrap();
 
-        // First create a game
-        let create_request = Request::builder()
-            .method("POST")
-            .uri("/games")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
-            ))
+        let request = Request::builder()
+            .method("GET")
+            .uri("/ready")
+            .body(Body::empty())
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
 
-        app.clone().oneshot(create_request).await.unwrap();
+        // Liveness is unaffected
+        let request = Request::builder()
+            .method("GET")
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_openapi_spec() {
+        let (app, temp_dir) = setup_test_app().await;
+        let spec = get_json(&app, "/openapi.json").await;
+        assert!(spec["paths"]["/games/{id}/play"]["post"].is_object());
+
+        // The documented fields must match what the handlers actually send
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let game = get_json(&app, &format!("/games/{}", session.id)).await;
+        assert_matches_schema(&spec, "GameResponse", &game);
+        assert_matches_schema(&spec, "PlayerResponse", &game["players"][0]);
+
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_matches_schema(&spec, "GameStateResponse", &state);
+        assert_matches_schema(&spec, "PlayerStateResponse", &state["players"][0]);
+
+        let hints = get_json(&app, &format!("/games/{}/hints", session.id)).await;
+        assert_matches_schema(&spec, "HintsResponse", &hints);
+
+        let page = get_json(&app, "/games?detailed=true").await;
+        assert_matches_schema(&spec, "GamePage", &page);
+        assert_matches_schema(&spec, "GameSummaryResponse", &page["games"][0]);
+
+        let public = get_json(&app, &format!("/games/{}/public", session.id)).await;
+        assert_matches_schema(&spec, "PublicStateResponse", &public);
+
+        let stats = get_json(&app, &format!("/games/{}/stats", session.id)).await;
+        assert_matches_schema(&spec, "PlayerStatsResponse", &stats[0]);
+        assert_matches_schema(&spec, "PlayerStats", &stats[0]["stats"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_draws_are_not_lost() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+        let initial_deck = session.game.deck.len();
+
+        // Fire several draw requests at the same game at once
+        let draws = 8;
+        let mut handles = Vec::new();
+        for _ in 0..draws {
+            let app = app.clone();
+            let uri = format!("/games/{}/draw", session.id);
+            handles.push(tokio::spawn(async move {
+                let request = Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("If-Match", "*")
+                    .body(Body::empty())
+                    .unwrap();
+                app.oneshot(request).await.unwrap().status()
+            }));
+        }
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), StatusCode::OK);
+        }
+
+        // Every draw must be reflected in the saved file
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.deck.len(), initial_deck - draws);
+        assert_eq!(loaded.game.players[0].hand.len(), 7 + draws / 2);
+        assert_eq!(loaded.game.players[1].hand.len(), 7 + draws / 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_games_detailed() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let session1 = session_manager
+            .create_session(UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap())
+            .unwrap();
+        let mut game = UnoGame::new(vec!["Charlie".to_string(), "David".to_string()]).unwrap();
+        game.status = GameStatus::Complete { winner_id: 0 };
+        let session2 = session_manager.create_session(game).unwrap();
 
-        // Then list games
         let list_request = Request::builder()
             .method("GET")
-            .uri("/games")
+            .uri("/games?detailed=true")
             .body(Body::empty())
             .unwrap();
 
@@ -622,481 +5145,1387 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let games: Vec<String> = serde_json::from_slice(&body).unwrap();
-        assert!(!games.is_empty());
+        let page: GamePage<GameSummaryResponse> = serde_json::from_slice(&body).unwrap();
+        let games = page.games;
+        assert_eq!(games.len(), 2);
+
+        let game1 = games.iter().find(|g| g.id == session1.id).unwrap();
+        assert_eq!(game1.player_names, vec!["Alice", "Bob"]);
+        assert_eq!(game1.status, "In Progress");
+
+        let game2 = games.iter().find(|g| g.id == session2.id).unwrap();
+        assert_eq!(game2.player_names, vec!["Charlie", "David"]);
+        assert_eq!(game2.status, "Complete");
     }
 
     #[tokio::test]
-    async fn test_get_game() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_list_games_filtered() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let playing = session_manager
+            .create_session(UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap())
+            .unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Carol".to_string()]).unwrap();
+        game.status = GameStatus::Complete { winner_id: 1 };
+        let finished = session_manager.create_session(game).unwrap();
+        session_manager
+            .create_session(UnoGame::new(vec!["Dave".to_string(), "Erin".to_string()]).unwrap())
+            .unwrap();
 
-        // First create a game
-        let create_request = Request::builder()
-            .method("POST")
-            .uri("/games")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
-            ))
+        let page = get_json(&app, "/games?status=in_progress&player=Alice").await;
+        assert_eq!(page["games"], json!([playing.id]));
+        assert_eq!(page["total"], 1);
+
+        let page = get_json(&app, "/games?status=complete&detailed=true").await;
+        assert_eq!(page["games"].as_array().unwrap().len(), 1);
+        assert_eq!(page["games"][0]["id"], finished.id);
+        assert_eq!(page["games"][0]["status"], "Complete");
+
+        let page = get_json(&app, "/games?player=alice").await;
+        assert_eq!(page["games"].as_array().unwrap().len(), 2);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/games?status=paused")
+            .body(Body::empty())
             .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
+    #[tokio::test]
+    async fn test_player_games_list_their_turn_first() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        let her_turn = session_manager
+            .create_session(UnoGame::new(names(&["Alice", "Bob"])).unwrap())
+            .unwrap();
+        // Updated later, so it would be listed first on recency alone
+        let mut game = UnoGame::new(names(&["Bob", "Alice"])).unwrap();
+        game.current_turn = 0;
+        let bobs_turn = session_manager.create_session(game).unwrap();
+        let mut game = UnoGame::new(names(&["Alice", "Carol"])).unwrap();
+        game.status = GameStatus::Complete { winner_id: 0 };
+        session_manager.create_session(game).unwrap();
+        session_manager
+            .create_session(UnoGame::new(names(&["Alicia", "Dave"])).unwrap())
+            .unwrap();
+
+        let page = get_json(&app, "/players/alice/games").await;
+        assert_matches_schema(&openapi::spec(), "PlayerGamesResponse", &page);
+        assert_eq!(
+            page["games"],
+            json!([
+                { "id": her_turn.id, "player_id": 0, "your_turn": true, "status": "In Progress" },
+                { "id": bobs_turn.id, "player_id": 1, "your_turn": false, "status": "In Progress" },
+            ])
+        );
+
+        let page = get_json(&app, "/players/Nobody/games").await;
+        assert_eq!(page["games"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_purge_games_by_status() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let names = || vec!["Alice".to_string(), "Bob".to_string()];
+        let mut finished = Vec::new();
+        for winner_id in [0, 1] {
+            let mut game = UnoGame::new(names()).unwrap();
+            game.status = GameStatus::Complete { winner_id };
+            finished.push(session_manager.create_session(game).unwrap().id);
+        }
+        let mut stalemate = UnoGame::new(names()).unwrap();
+        stalemate.status = GameStatus::Stalemate { winner_id: 0 };
+        let stalemate = session_manager.create_session(stalemate).unwrap();
+        let active = session_manager
+            .create_session(UnoGame::new(names()).unwrap())
+            .unwrap();
+
+        let purge = |uri: &str| {
+            Request::builder()
+                .method("DELETE")
+                .uri(uri)
+                .header("Authorization", "Bearer s3cret")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Purging needs the admin token
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/games?status=complete")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
             .await
             .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/games?status=complete")
+                    .header("Authorization", "Bearer wrong")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(session_manager.list_sessions().unwrap().len(), 4);
+
+        // Without a status nothing is deleted
+        let response = app.clone().oneshot(purge("/games")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, "STATUS_REQUIRED");
+        assert_eq!(session_manager.list_sessions().unwrap().len(), 4);
+
+        let response = app
+            .clone()
+            .oneshot(purge("/games?status=complete"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut purged: PurgeGamesResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(purged.count, 2);
+        purged.removed.sort();
+        finished.sort();
+        assert_eq!(purged.removed, finished);
+
+        let mut left = session_manager.list_sessions().unwrap();
+        left.sort();
+        let mut expected = vec![stalemate.id, active.id];
+        expected.sort();
+        assert_eq!(left, expected);
+    }
+
+    #[tokio::test]
+    async fn test_list_games_paginated() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut ids = Vec::new();
+        for _ in 0..5 {
+            let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+            ids.push(session_manager.create_session(game).unwrap().id);
+        }
+        // Touch the first game so it becomes the most recently updated
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let first = session_manager.load_session(&ids[0]).unwrap();
+        session_manager.save_session(&first).unwrap();
+
+        let page = get_json(&app, "/games?limit=2").await;
+        assert_eq!(page["total"], 5);
+        assert_eq!(page["next_offset"], 2);
+        assert_eq!(page["games"].as_array().unwrap().len(), 2);
+        assert_eq!(page["games"][0], ids[0]);
+
+        let mut seen: Vec<serde_json::Value> = page["games"].as_array().unwrap().clone();
+        let page = get_json(&app, "/games?limit=2&offset=2").await;
+        assert_eq!(page["next_offset"], 4);
+        seen.extend(page["games"].as_array().unwrap().clone());
+
+        // The last page is short and has no next offset
+        let page = get_json(&app, "/games?limit=2&offset=4").await;
+        assert_eq!(page["games"].as_array().unwrap().len(), 1);
+        assert_eq!(page["next_offset"], serde_json::Value::Null);
+        seen.extend(page["games"].as_array().unwrap().clone());
+
+        // Pages don't overlap and together cover every game
+        seen.sort_by_key(|id| id.as_str().unwrap().to_string());
+        seen.dedup();
+        assert_eq!(seen.len(), 5);
+
+        // A page that ends exactly at the last game has no next offset either
+        let page = get_json(&app, "/games?limit=5").await;
+        assert_eq!(page["games"].as_array().unwrap().len(), 5);
+        assert_eq!(page["next_offset"], serde_json::Value::Null);
+
+        let page = get_json(&app, "/games?offset=10").await;
+        assert_eq!(page["games"], json!([]));
+        assert_eq!(page["total"], 5);
+        assert_eq!(page["next_offset"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_forfeit() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ])
+        .unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let forfeit_request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/forfeit", session.id))
+            .header("Content-Type", "application/json")
+            .body(Body::from(json!({ "player_id": 0 }).to_string()))
+            .unwrap();
+
+        let response = app.clone().oneshot(forfeit_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
 
-        // Then get the specific game
         let get_request = Request::builder()
             .method("GET")
-            .uri(format!("/games/{}", game.id))
+            .uri(format!("/games/{}", session.id))
             .body(Body::empty())
             .unwrap();
 
         let response = app.oneshot(get_request).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert!(!game.players[0].active);
+        assert_eq!(game.players[0].hand_size, 0);
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.status, "In Progress");
+    }
+
+    #[tokio::test]
+    async fn test_restart_finished_game() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.players[1].hand.clear();
+        game.players[1].stats.rounds_won = 1;
+        game.status = GameStatus::Complete { winner_id: 1 };
+        let (session, tokens) = session_manager
+            .create_session_with_tokens(with_full_deck(game))
+            .unwrap();
+
+        let restart = |uri: String, token: Option<&str>| {
+            let mut builder = Request::builder().method("POST").uri(uri);
+            if let Some(token) = token {
+                builder = builder.header("Authorization", format!("Bearer {}", token));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        // Only a seated player can restart the game
+        let uri = format!("/games/{}/restart", session.id);
+        let response = app
+            .clone()
+            .oneshot(restart(uri.clone(), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app
+            .clone()
+            .oneshot(restart(uri.clone(), Some("wrong")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .clone()
+            .oneshot(restart(uri, Some(&tokens[&0])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let state: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(state["id"], session.id);
+        assert_eq!(state["status"], "In Progress");
+        assert_eq!(state["current_turn"], 0);
+        assert_eq!(state["players"][1]["name"], "Bob");
+        assert_eq!(state["players"][0]["hand_size"], 7);
+        assert_eq!(state["players"][1]["hand_size"], 7);
+        assert_eq!(state["players"][1]["stats"]["rounds_won"], 1);
+        assert_eq!(state["deck_cards_remaining"], 108 - 2 * 7 - 1);
+
+        // Only a finished game can be restarted
+        let response = app
+            .oneshot(restart(
+                format!("/games/{}/restart?reset_stats=true", session.id),
+                Some(&tokens[&1]),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_kick_player() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ])
+        .unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let kick = |player_id: usize| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/kick", session.id))
+                .header("Content-Type", "application/json")
+                .header(header::AUTHORIZATION, "Bearer s3cret")
+                .header(header::IF_MATCH, "*")
+                .body(Body::from(json!({ "player_id": player_id }).to_string()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(kick(0)).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let event: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event, json!({ "PlayerKicked": { "player_id": 0 } }));
+
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["players"][0]["active"], false);
+        assert_eq!(state["current_turn"], 1);
+
+        let response = app.clone().oneshot(kick(2)).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let event: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event, json!({ "PlayerWins": { "player_id": 1 } }));
+
+        let response = app.oneshot(kick(1)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_kick_player_needs_admin_token_and_version() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let kick = |token: Option<&str>, version: &str| {
+            let mut request = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/kick", session.id))
+                .header("Content-Type", "application/json")
+                .header(header::IF_MATCH, version);
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            request
+                .body(Body::from(json!({ "player_id": 1 }).to_string()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(kick(None, "*")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.clone().oneshot(kick(Some("guess"), "*")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app
+            .clone()
+            .oneshot(kick(Some("s3cret"), "\"7\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["players"][1]["active"], true);
 
-        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let retrieved_game: GameResponse = serde_json::from_slice(&body).unwrap();
-        assert_eq!(retrieved_game.id, game.id);
+        // Without an admin token the server refuses every kick
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/kick", session.id))
+            .header("Content-Type", "application/json")
+            .header(header::IF_MATCH, "*")
+            .body(Body::from(json!({ "player_id": 1 }).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
     #[tokio::test]
-    async fn test_play_card() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_create_game_rejects_duplicate_names() {
+        let (app, temp_dir) = setup_test_app().await;
 
-        // First create a game
-        let create_request = Request::builder()
+        let request = Request::builder()
             .method("POST")
             .uri("/games")
             .header("Content-Type", "application/json")
             .body(Body::from(
                 json!({
-                    "player_names": ["Alice", "Bob"]
+                    "player_names": ["Bob", "BOB"]
                 })
                 .to_string(),
             ))
             .unwrap();
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
-            .await
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.error, "Duplicate player name: BOB");
+        assert_eq!(error.code, "GAME_ERROR");
+
+        // Nothing was persisted
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        assert!(session_manager.list_sessions().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_json_with_a_code() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        // Passing without having drawn is an invalid move
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/pass", session.id))
+            .header("If-Match", "*")
+            .body(Body::empty())
             .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()["Content-Type"], "application/json");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            error,
+            json!({ "error": "Invalid move", "code": "INVALID_MOVE" })
+        );
 
-        // Get the current game state
-        let get_request = Request::builder()
+        let request = Request::builder()
             .method("GET")
-            .uri(format!("/games/{}", game.id))
+            .uri("/games/missing/state")
             .body(Body::empty())
             .unwrap();
-
-        let response = app.clone().oneshot(get_request).await.unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let game_state: GameResponse = serde_json::from_slice(&body).unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, "NOT_FOUND");
+    }
 
-        // Verify that the current player has cards
-        assert!(
-            game_state.players[game_state.current_turn].hand_size > 0,
-            "Current player should have cards"
-        );
+    #[tokio::test]
+    async fn test_rename_player() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bbo".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
 
-        // If the top card is a Wild Draw Four, we need to choose a color first
-        if game_state.discard_pile_top.card_type == "WildDrawFour" {
-            let color_request = Request::builder()
-                .method("POST")
-                .uri(format!("/games/{}/color", game.id))
+        let rename = |player_id: usize, name: &str| {
+            Request::builder()
+                .method("PATCH")
+                .uri(format!("/games/{}/players/{}", session.id, player_id))
                 .header("Content-Type", "application/json")
-                .body(Body::from(
-                    json!({
-                        "color": "red"
-                    })
-                    .to_string(),
-                ))
-                .unwrap();
+                .body(Body::from(json!({ "name": name }).to_string()))
+                .unwrap()
+        };
 
-            let response = app.clone().oneshot(color_request).await.unwrap();
-            assert_eq!(response.status(), StatusCode::OK);
-        }
+        let response = app.clone().oneshot(rename(1, "Bob")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let state: GameStateResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(state.players[1].name, "Bob");
+
+        // Another player's name is taken
+        let response = app.clone().oneshot(rename(1, "alice")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app.oneshot(rename(5, "Carol")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.players[1].name, "Bob");
+    }
+
+    #[tokio::test]
+    async fn test_draw_refused_when_must_play_if_able() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.must_play_if_able = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(1)),
+            Card::new(Color::Red, CardType::Number(9)),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let current = get_json(&app, &format!("/games/{}/current", session.id)).await;
+        assert_eq!(current["actions"], json!(["play"]));
 
-        // First try to play a card from the player's hand
-        let play_request = Request::builder()
+        let request = Request::builder()
             .method("POST")
-            .uri(format!("/games/{}/play", game.id))
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "card_index": 0
-                })
-                .to_string(),
-            ))
+            .uri(format!("/games/{}/draw", session.id))
+            .header("If-Match", "*")
+            .body(Body::empty())
             .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            error,
+            json!({
+                "error": "You must play instead of drawing; playable hand indices: 1",
+                "code": "INVALID_MOVE"
+            })
+        );
 
-        let response = app.clone().oneshot(play_request).await.unwrap();
-        if response.status() == StatusCode::OK {
-            // Successfully played a card from hand
-            return;
-        }
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.players[0].hand.len(), 2);
+    }
 
-        // If we couldn't play from hand, try drawing cards until we find a playable one
-        let mut attempts = 0;
-        let max_attempts = 5; // Limit the number of attempts to avoid infinite loops
-        while attempts < max_attempts {
-            // Draw a card
-            let draw_request = Request::builder()
-                .method("POST")
-                .uri(format!("/games/{}/draw", game.id))
-                .body(Body::empty())
-                .unwrap();
+    #[tokio::test]
+    async fn test_get_hand_card() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Skip),
+            Card::new(Color::Red, CardType::Number(7)),
+        ];
+        game.players[1].hand = vec![Card::new(Color::Red, CardType::Number(3))];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
 
-            let response = app.clone().oneshot(draw_request).await.unwrap();
-            assert_eq!(response.status(), StatusCode::OK);
+        let card = get_json(&app, &format!("/games/{}/players/0/hand/1", session.id)).await;
+        assert_eq!(
+            card,
+            json!({
+                "index": 1,
+                "color": "Red",
+                "card_type": "Number(7)",
+                "display": "Red 7",
+                "points": 7,
+                "playable": true,
+            })
+        );
+        assert_matches_schema(&openapi::spec(), "CardDetailResponse", &card);
+
+        let card = get_json(&app, &format!("/games/{}/players/0/hand/0", session.id)).await;
+        assert_eq!(card["display"], "Blue Skip");
+        assert_eq!(card["points"], 20);
+        assert_eq!(card["playable"], false);
 
-            // Get the current game state
-            let get_request = Request::builder()
+        // Bob's card matches, but it isn't his turn
+        let card = get_json(&app, &format!("/games/{}/players/1/hand/0", session.id)).await;
+        assert_eq!(card["playable"], false);
+
+        for uri in ["players/0/hand/2", "players/5/hand/0"] {
+            let request = Request::builder()
                 .method("GET")
-                .uri(format!("/games/{}", game.id))
+                .uri(format!("/games/{}/{}", session.id, uri))
                 .body(Body::empty())
                 .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", uri);
+        }
+    }
 
-            let response = app.clone().oneshot(get_request).await.unwrap();
-            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let game_state: GameResponse = serde_json::from_slice(&body).unwrap();
-
-            // Try to play the last card we drew
-            let play_request = Request::builder()
-                .method("POST")
-                .uri(format!("/games/{}/play", game.id))
-                .header("Content-Type", "application/json")
-                .body(Body::from(
-                    json!({
-                        "card_index": game_state.players[game_state.current_turn].hand_size - 1
-                    })
-                    .to_string(),
-                ))
-                .unwrap();
+    #[tokio::test]
+    async fn test_get_hand_card_needs_the_players_token() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            admin_token: Some("s3cret".to_string()),
+            ..ServerConfig::default()
+        })
+        .await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut session = session_manager.create_session(game).unwrap();
+        let alice = session.issue_token(0);
+        let bob = session.issue_token(1);
+        session_manager.save_session(&session).unwrap();
 
-            let response = app.clone().oneshot(play_request).await.unwrap();
-            if response.status() == StatusCode::OK {
-                // Successfully played a card
-                return;
+        let look = |authorization: Option<String>| {
+            let mut builder = Request::builder()
+                .method("GET")
+                .uri(format!("/games/{}/players/0/hand/0", session.id));
+            if let Some(authorization) = authorization {
+                builder = builder.header(header::AUTHORIZATION, authorization);
             }
+            builder.body(Body::empty()).unwrap()
+        };
 
-            attempts += 1;
+        let response = app.clone().oneshot(look(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Bob can't peek at Alice's cards
+        let response = app
+            .clone()
+            .oneshot(look(Some(format!("Bearer {}", bob))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "INVALID_TOKEN");
+
+        for authorization in [format!("Bearer {}", alice), "Bearer s3cret".to_string()] {
+            let response = app
+                .clone()
+                .oneshot(look(Some(authorization)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
         }
+    }
 
-        panic!(
-            "Failed to find a playable card after {} attempts",
-            max_attempts
+    #[tokio::test]
+    async fn test_sort_hand() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.players[1].hand = vec![
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Yellow, CardType::Number(2)),
+            Card::new(Color::Red, CardType::Reverse),
+            Card::new(Color::Red, CardType::Number(4)),
+        ];
+        let alice_hand = game.players[0].hand.clone();
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+
+        let sort = |player_id: usize| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/players/{}/sort", session.id, player_id))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(sort(1)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // Indices have moved, so the old ETag no longer matches
+        assert_ne!(response.headers()[header::ETAG], etag(&session));
+
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(
+            loaded.game.players[1].hand,
+            vec![
+                Card::new(Color::Red, CardType::Number(4)),
+                Card::new(Color::Red, CardType::Reverse),
+                Card::new(Color::Yellow, CardType::Number(2)),
+                Card::new(Color::Wild, CardType::Wild),
+            ]
         );
+        // Only the named player's hand is touched
+        assert_eq!(loaded.game.players[0].hand, alice_hand);
+
+        let response = app.oneshot(sort(5)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn test_draw_card() {
+    async fn test_metrics_count_created_games() {
         let (app, _temp_dir) = setup_test_app().await;
-
-        // First create a game
-        let create_request = Request::builder()
+        let request = Request::builder()
             .method("POST")
             .uri("/games")
             .header("Content-Type", "application/json")
             .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
+                json!({ "player_names": ["Alice", "Bob"] }).to_string(),
             ))
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
-
-        // Then draw a card
-        let draw_request = Request::builder()
-            .method("POST")
-            .uri(format!("/games/{}/draw", game.id))
+        let request = Request::builder()
+            .method("GET")
+            .uri("/metrics")
             .body(Body::empty())
             .unwrap();
-
-        let response = app.oneshot(draw_request).await.unwrap();
+        let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["Content-Type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let metrics = String::from_utf8(body.to_vec()).unwrap();
+        assert!(metrics.contains("uno_games_created_total 1\n"));
+        assert!(metrics.contains("uno_cards_played_total 0\n"));
+        assert!(metrics.contains("uno_active_sessions 1\n"));
     }
 
     #[tokio::test]
-    async fn test_choose_color() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_failed_save_keeps_previous_state() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Number(1)),
+        ];
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+        let before = get_json(&app, &format!("/games/{}/state", session.id)).await;
 
-        // First create a game
-        let create_request = Request::builder()
+        // A directory where the save writes its temporary file makes every save fail
+        fs::create_dir(temp_dir.path().join(format!("{}.json.tmp", session.id))).unwrap();
+        let request = Request::builder()
             .method("POST")
-            .uri("/games")
+            .uri(format!("/games/{}/play", session.id))
             .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
-            ))
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "card_index": 0 }).to_string()))
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
+        // Neither the server nor the disk has the move
+        let after = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(after["version"], before["version"]);
+        assert_eq!(after["current_turn"], 0);
+        assert_eq!(after["players"][0]["hand"].as_array().unwrap().len(), 2);
+        let saved = SessionManager::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .load_session(&session.id)
+            .unwrap();
+        assert_eq!(saved.game.players[0].hand.len(), 2);
+        assert_eq!(saved.game.version, session.game.version);
+
+        let metrics = app
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
             .await
             .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        let body = to_bytes(metrics.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8(body.to_vec())
+            .unwrap()
+            .contains("uno_cards_played_total 0\n"));
+    }
 
-        // Then choose a color
-        let color_request = Request::builder()
+    #[tokio::test]
+    async fn test_redeal_player() {
+        let (app, temp_dir) = setup_test_app_with_config(ServerConfig {
+            allow_redeal: true,
+            ..ServerConfig::default()
+        })
+        .await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new_with_seed(vec!["Alice".to_string(), "Bob".to_string()], 7).unwrap();
+        let before = game.players[1].hand.clone();
+        let session = session_manager.create_session(game).unwrap();
+
+        let request = Request::builder()
             .method("POST")
-            .uri(format!("/games/{}/color", game.id))
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "color": "red"
-                })
-                .to_string(),
-            ))
+            .uri(format!("/games/{}/players/1/redeal", session.id))
+            .body(Body::empty())
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
 
-        let response = app.oneshot(color_request).await.unwrap();
+        let export = get_json(&app, &format!("/games/{}/export", session.id)).await;
+        let after: GameSession = serde_json::from_value(export).unwrap();
+        assert_ne!(after.game.players[1].hand, before);
+        assert_eq!(after.game.players[1].hand.len(), 7);
+        assert_eq!(after.game.version, 1);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/players/5/redeal", session.id))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_redeal_is_off_by_default() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/players/0/redeal", session.id))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "REDEAL_DISABLED");
+    }
+
+    #[tokio::test]
+    async fn test_advance_bots_plays_until_a_human_must_act() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut players: Vec<Player> = ["Alice", "Bot 1", "Bot 2", "Carol"]
+            .iter()
+            .enumerate()
+            .map(|(id, name)| Player::new(id, name.to_string()))
+            .collect();
+        players[0].hand = vec![Card::new(Color::Green, CardType::Number(1)); 3];
+        players[1].hand = vec![
+            Card::new(Color::Red, CardType::Number(3)),
+            Card::new(Color::Blue, CardType::Number(9)),
+        ];
+        players[2].hand = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Green, CardType::Number(2)),
+        ];
+        players[3].hand = vec![Card::new(Color::Yellow, CardType::Number(4)); 3];
+        players[1].is_bot = true;
+        players[2].is_bot = true;
+        let game = UnoGame::from_parts(
+            players,
+            vec![Card::new(Color::Yellow, CardType::Number(9)); 10],
+            vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)],
+            1,
+            Direction::Clockwise,
+        )
+        .unwrap();
+        let session = session_manager
+            .create_session(with_full_deck(game))
+            .unwrap();
+        let advance = |version: String| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/advance-bots", session.id))
+                .header(header::IF_MATCH, version)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // A client that hasn't seen the latest state can't set the bots off
+        let stale = format!("\"{}\"", session.game.version + 1);
+        let response = app.clone().oneshot(advance(stale)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let current = format!("\"{}\"", session.game.version);
+        let response = app.clone().oneshot(advance(current)).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let advanced: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let spec = openapi::spec();
+        assert_matches_schema(&spec, "AdvanceBotsResponse", &advanced);
+
+        let events = advanced["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["CardPlayed"]["player_id"], 1);
+        assert_eq!(events[1]["CardPlayed"]["player_id"], 2);
+        assert_eq!(advanced["state"]["current_turn"], 3);
+        assert_eq!(advanced["state"]["players"][1]["is_bot"], true);
+        assert_eq!(advanced["state"]["players"][3]["is_bot"], false);
+
+        // Nothing to do on a human's turn
+        let response = app.oneshot(advance(etag)).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let advanced: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(advanced["events"], json!([]));
     }
 
     #[tokio::test]
-    async fn test_delete_game() {
+    async fn test_create_game_with_bots() {
         let (app, _temp_dir) = setup_test_app().await;
+        let request = Request::builder()
+            .method("POST")
+            .uri("/games")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "player_names": ["Alice", "Bot"], "bots": [1] }).to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let game: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(game["players"][0]["is_bot"], false);
+        assert_eq!(game["players"][1]["is_bot"], true);
 
-        // First create a game
-        let create_request = Request::builder()
+        let request = Request::builder()
             .method("POST")
             .uri("/games")
             .header("Content-Type", "application/json")
             .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
+                json!({ "player_names": ["Alice", "Bot"], "bots": [2] }).to_string(),
             ))
             .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
-
-        // Then delete the game
-        let delete_request = Request::builder()
-            .method("DELETE")
-            .uri(format!("/games/{}", game.id))
-            .body(Body::empty())
+    #[tokio::test]
+    async fn test_recent_discards() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut alice = Player::new(0, "Alice".to_string());
+        alice.hand = vec![
+            Card::new(Color::Red, CardType::Number(5)),
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Number(1)),
+        ];
+        let mut bob = Player::new(1, "Bob".to_string());
+        bob.hand = vec![
+            Card::new(Color::Red, CardType::Number(6)),
+            Card::new(Color::Green, CardType::Number(2)),
+        ];
+        let discards = (1..=4)
+            .map(|n| (Card::new(Color::Red, CardType::Number(n)), usize::MAX))
+            .collect();
+        let game = UnoGame::from_parts(
+            vec![alice, bob],
+            vec![Card::new(Color::Yellow, CardType::Number(9)); 10],
+            discards,
+            0,
+            Direction::Clockwise,
+        )
+        .unwrap();
+        let session = session_manager
+            .create_session(with_full_deck(game))
             .unwrap();
 
-        let response = app.clone().oneshot(delete_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
-
-        // Verify the game is deleted
-        let get_request = Request::builder()
-            .method("GET")
-            .uri(format!("/games/{}", game.id))
-            .body(Body::empty())
-            .unwrap();
+        // Alice plays Red 5, Bob plays Red 6, then Alice plays Red 7
+        for card_index in [0, 0, 0] {
+            let request = Request::builder()
+                .method("POST")
+                .uri(format!("/games/{}/play", session.id))
+                .header("If-Match", "*")
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({ "card_index": card_index }).to_string()))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
 
-        let response = app.oneshot(get_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(
+            state["recent_discards"],
+            json!([
+                { "color": "Red", "card_type": "Number(7)", "player_id": 0 },
+                { "color": "Red", "card_type": "Number(6)", "player_id": 1 },
+                { "color": "Red", "card_type": "Number(5)", "player_id": 0 },
+                { "color": "Red", "card_type": "Number(4)" },
+                { "color": "Red", "card_type": "Number(3)" },
+            ])
+        );
     }
 
     #[tokio::test]
-    async fn test_error_cases() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_get_board() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Skip), usize::MAX)];
+        let session = session_manager.create_session(game).unwrap();
 
-        // Test invalid game ID
-        let get_request = Request::builder()
+        let request = Request::builder()
             .method("GET")
-            .uri("/games/invalid-id")
+            .uri(format!("/games/{}/board", session.id))
             .body(Body::empty())
             .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["Content-Type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
 
-        let response = app.clone().oneshot(get_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let board = String::from_utf8(body.to_vec()).unwrap();
+        assert!(board.contains("Top card: Red Skip"));
+        assert!(board.contains("> Alice (7 cards)"));
+    }
 
-        // Test invalid color
-        let color_request = Request::builder()
+    #[tokio::test]
+    async fn test_get_stats() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        let request = Request::builder()
             .method("POST")
-            .uri("/games/some-id/color")
+            .uri(format!("/games/{}/draw", session.id))
             .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "color": "invalid"
-                })
-                .to_string(),
-            ))
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "player_id": 0 }).to_string()))
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
 
-        let response = app.oneshot(color_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let stats = get_json(&app, &format!("/games/{}/stats", session.id)).await;
+        assert_eq!(stats[0]["name"], "Alice");
+        assert_eq!(stats[0]["stats"]["cards_drawn"], 1);
+        assert_eq!(stats[1]["stats"]["cards_drawn"], 0);
+
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["players"][0]["stats"]["cards_drawn"], 1);
     }
 
     #[tokio::test]
-    async fn test_get_game_state() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_recolored_wild_reports_effective_color() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand[0] = Card::new(Color::Wild, CardType::Wild);
+        let session = session_manager.create_session(game).unwrap();
 
-        // First create a game
-        let create_request = Request::builder()
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["discard_pile_top"]["effective_color"], "Red");
+        assert_eq!(state["discard_pile_top"]["is_wild_active"], false);
+
+        let request = Request::builder()
             .method("POST")
-            .uri("/games")
+            .uri(format!("/games/{}/play", session.id))
             .header("Content-Type", "application/json")
+            .header("If-Match", "*")
             .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
+                json!({ "card_index": 0, "color": "Blue" }).to_string(),
             ))
             .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        let top = &state["discard_pile_top"];
+        assert_eq!(top["card_type"], "Wild");
+        assert_eq!(top["effective_color"], "Blue");
+        assert_eq!(top["is_wild_active"], true);
+    }
 
-        // Then get the game state
-        let get_request = Request::builder()
-            .method("GET")
-            .uri(format!("/games/{}/state", game.id))
-            .body(Body::empty())
+    #[tokio::test]
+    async fn test_turn_timer_follows_manager_clock() {
+        let temp_dir = tempdir().unwrap();
+        use crate::uno_game::clock::{Clock, MockClock};
+        let clock = MockClock::default();
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_clock(Arc::new(clock.clone()));
+        let state = AppState {
+            session_manager: session_manager.clone(),
+            updates: GameUpdates::default(),
+            turn_timeout: Some(chrono::Duration::seconds(30)),
+            metrics: Metrics::default(),
+            allow_redeal: false,
+            max_active_games: None,
+            admin_token_hash: None,
+        };
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
+
+        // The first pass starts the clock for the current player
+        assert!(expire_turns(&state).await.is_empty());
+        let deadline = session_manager
+            .load_session(&session.id)
+            .unwrap()
+            .turn_deadline
             .unwrap();
+        assert_eq!(deadline, clock.now() + chrono::Duration::seconds(30));
 
-        let response = app.oneshot(get_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        clock.advance(chrono::Duration::seconds(29));
+        assert!(expire_turns(&state).await.is_empty());
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(expire_turns(&state).await, vec![session.id.clone()]);
+        let loaded = session_manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.current_turn, 1);
+    }
 
-        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let game_state: GameStateResponse = serde_json::from_slice(&body).unwrap();
+    #[tokio::test]
+    async fn test_turn_timer_leaves_untimed_games_alone() {
+        let temp_dir = tempdir().unwrap();
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let state = AppState {
+            session_manager: session_manager.clone(),
+            updates: GameUpdates::default(),
+            turn_timeout: Some(chrono::Duration::seconds(30)),
+            metrics: Metrics::default(),
+            allow_redeal: false,
+            max_active_games: None,
+            admin_token_hash: None,
+        };
+        let game =
+            UnoGame::new_lobby(vec!["Alice".to_string()], GameConfig::default(), None).unwrap();
+        let lobby = session_manager.create_session(game).unwrap();
+        let game = UnoGame::new(vec!["Carol".to_string(), "Dave".to_string()]).unwrap();
+        let running = session_manager.create_session(game).unwrap();
+        let mut lobby_updates = state.updates.subscribe(&lobby.id);
+        let mut running_updates = state.updates.subscribe(&running.id);
 
-        // Verify the game state
-        assert_eq!(game_state.id, game.id);
-        assert_eq!(game_state.current_turn, 0);
-        assert_eq!(game_state.direction, "Clockwise");
-        assert_eq!(game_state.players.len(), 2);
-        assert_eq!(game_state.players[0].name, "Alice");
-        assert_eq!(game_state.players[1].name, "Bob");
-        assert_eq!(game_state.players[0].hand.len(), 7); // Each player starts with 7 cards
-        assert_eq!(game_state.players[1].hand.len(), 7);
+        // The running game gets its clock once, and nothing changes after that
+        assert!(expire_turns(&state).await.is_empty());
+        assert!(running_updates.try_recv().is_ok());
+        assert!(expire_turns(&state).await.is_empty());
+        assert!(running_updates.try_recv().is_err());
 
-        // Verify the top card has valid properties
-        assert!(!game_state.discard_pile_top.color.is_empty());
-        assert!(!game_state.discard_pile_top.card_type.is_empty());
+        assert!(lobby_updates.try_recv().is_err());
+        let stored =
+            fs::read_to_string(temp_dir.path().join(format!("{}.json", lobby.id))).unwrap();
+        let stored: GameSession = serde_json::from_str(&stored).unwrap();
+        assert_eq!(stored.turn_deadline, None);
+        assert_eq!(stored.last_updated, lobby.last_updated);
+    }
 
-        assert!(game_state.deck_cards_remaining > 0);
+    #[tokio::test]
+    async fn test_turn_timer_draws_for_idle_player() {
+        let temp_dir = tempdir().unwrap();
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let state = AppState {
+            session_manager: session_manager.clone(),
+            updates: GameUpdates::default(),
+            turn_timeout: Some(chrono::Duration::seconds(30)),
+            metrics: Metrics::default(),
+            allow_redeal: false,
+            max_active_games: None,
+            admin_token_hash: None,
+        };
+
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut idle = session_manager.create_session(game).unwrap();
+        idle.turn_deadline = Some(chrono::Utc::now() - chrono::Duration::seconds(1));
+        session_manager.save_session(&idle).unwrap();
+
+        let game = UnoGame::new(vec!["Carol".to_string(), "Dave".to_string()]).unwrap();
+        let fresh = session_manager.create_session(game).unwrap();
+
+        let timed_out = expire_turns(&state).await;
+        assert_eq!(timed_out, vec![idle.id.clone()]);
+
+        let idle = session_manager.load_session(&idle.id).unwrap();
+        assert_eq!(idle.game.current_turn, 1);
+        assert_eq!(idle.game.players[0].hand.len(), 8);
+        assert!(idle.turn_deadline.unwrap() > chrono::Utc::now());
+
+        // A game without a deadline is given one rather than timed out
+        let fresh = session_manager.load_session(&fresh.id).unwrap();
+        assert_eq!(fresh.game.current_turn, 0);
+        assert!(fresh.turn_deadline.is_some());
+
+        let app = Router::new()
+            .route("/games/{id}/state", get(get_game_state))
+            .with_state(state);
+        let response = get_json(&app, &format!("/games/{}/state", idle.id)).await;
+        let remaining = response["turn_seconds_remaining"].as_i64().unwrap();
+        assert!((28..=30).contains(&remaining));
     }
 
     #[tokio::test]
-    async fn test_get_deck() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_public_state_hides_hands() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = session_manager.create_session(game).unwrap();
 
-        // First create a game
-        let create_request = Request::builder()
-            .method("POST")
-            .uri("/games")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
-            ))
-            .unwrap();
+        let public = get_json(&app, &format!("/games/{}/public", session.id)).await;
+        let players = public["players"].as_array().unwrap();
+        assert_eq!(players.len(), 2);
+        for player in players {
+            assert_eq!(player["hand_size"], 7);
+            assert!(player.get("hand").is_none());
+        }
+        assert!(!public.to_string().contains("\"hand\""));
+        assert_eq!(public["direction"], "Clockwise");
+        assert!(public["discard_pile_top"].get("effective_color").is_some());
+    }
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+    #[tokio::test]
+    async fn test_import_replay() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let names = vec!["Alice".to_string(), "Bob".to_string()];
 
-        // Then get the deck
-        let get_request = Request::builder()
-            .method("GET")
-            .uri(format!("/games/{}/deck", game.id))
-            .body(Body::empty())
-            .unwrap();
+        let mut game = UnoGame::new_with_seed(names.clone(), 5).unwrap();
+        let mut events = Vec::new();
+        for _ in 0..10 {
+            let player_id = game.current_turn;
+            let bot_move = crate::uno_game::bot::choose_move(&game, player_id);
+            events.push(crate::uno_game::bot::apply_move(&mut game, player_id, &bot_move).unwrap());
+        }
 
-        let response = app.oneshot(get_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
+        let replay = |events: &[GameEvent]| {
+            Request::builder()
+                .method("POST")
+                .uri("/games/import-replay")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({ "player_names": names, "seed": 5, "events": events }).to_string(),
+                ))
+                .unwrap()
+        };
 
+        let response = app.clone().oneshot(replay(&events)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let deck: DeckResponse = serde_json::from_slice(&body).unwrap();
-
-        // Verify the deck
-        assert!(!deck.cards.is_empty(), "Deck should not be empty");
+        let replayed: ReplayResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(replayed.steps_applied, 10);
+        assert_eq!(replayed.state.version, game.version);
+        assert_eq!(replayed.state.current_turn, game.current_turn);
         assert_eq!(
-            deck.cards.len(),
-            game.deck_cards_remaining,
-            "Deck size should match deck_cards_remaining"
+            replayed.state.players[0].hand_size,
+            game.players[0].hand.len()
         );
 
-        // Verify each card has valid properties
-        for card in deck.cards {
-            assert!(!card.color.is_empty(), "Card color should not be empty");
-            assert!(!card.card_type.is_empty(), "Card type should not be empty");
-        }
+        // Swapping the first two moves makes Bob play out of turn
+        events.swap(0, 1);
+        let response = app.clone().oneshot(replay(&events)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: ApiError = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.code, "INVALID_REPLAY");
+        assert!(error.error.starts_with("Step 0"));
     }
 
     #[tokio::test]
-    async fn test_get_discard_pile() {
-        let (app, _temp_dir) = setup_test_app().await;
+    async fn test_state_reports_next_turn() {
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        game.direction = crate::uno_game::Direction::CounterClockwise;
+        let session = session_manager.create_session(game).unwrap();
 
-        // First create a game
-        let create_request = Request::builder()
+        let state = get_json(&app, &format!("/games/{}/state", session.id)).await;
+        assert_eq!(state["current_turn"], 0);
+        assert_eq!(state["next_turn"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_game_rejects_oversized_body() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let huge_name = "A".repeat(MAX_BODY_BYTES);
+        let request = Request::builder()
             .method("POST")
             .uri("/games")
             .header("Content-Type", "application/json")
             .body(Body::from(
-                json!({
-                    "player_names": ["Alice", "Bob"]
-                })
-                .to_string(),
+                json!({ "player_names": [huge_name, "Bob"] }).to_string(),
             ))
             .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
 
-        let create_response = app.clone().oneshot(create_request).await.unwrap();
-        let body = to_bytes(create_response.into_body(), usize::MAX)
+    #[tokio::test]
+    async fn test_create_game_validates_player_names() {
+        let (app, _temp_dir) = setup_test_app().await;
+        let create = |names: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/games")
+                .header("Content-Type", "application/json")
+                .body(Body::from(json!({ "player_names": names }).to_string()))
+                .unwrap()
+        };
+
+        let too_many: Vec<String> = (0..=UnoGame::MAX_PLAYERS)
+            .map(|i| format!("Player {}", i))
+            .collect();
+        let response = app.clone().oneshot(create(json!(too_many))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let too_long = "A".repeat(UnoGame::MAX_NAME_LEN + 1);
+        let response = app
+            .clone()
+            .oneshot(create(json!([too_long, "Bob"])))
             .await
             .unwrap();
-        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        // Then get the discard pile
-        let get_request = Request::builder()
-            .method("GET")
-            .uri(format!("/games/{}/discard", game.id))
-            .body(Body::empty())
+        let response = app
+            .clone()
+            .oneshot(create(json!(["   ", "Bob"])))
+            .await
             .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        let response = app.oneshot(get_request).await.unwrap();
-        assert_eq!(response.status(), StatusCode::OK);
-
+        // Names are stored trimmed
+        let response = app
+            .clone()
+            .oneshot(create(json!(["  Alice ", "Bob"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let discard_pile: DiscardPileResponse = serde_json::from_slice(&body).unwrap();
+        let game: GameResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(game.players[0].name, "Alice");
+    }
 
-        // Verify the discard pile
-        assert!(
-            !discard_pile.cards.is_empty(),
-            "Discard pile should not be empty"
-        );
+    // Collects formatted log output so tests can inspect it
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
 
-        // Verify each card has valid properties
-        for (card, player_id) in discard_pile.cards {
-            assert!(!card.color.is_empty(), "Card color should not be empty");
-            assert!(!card.card_type.is_empty(), "Card type should not be empty");
-            // The first card should have no player_id (it's the initial card)
-            if player_id.is_some() {
-                assert!(player_id.unwrap() < 2, "Player ID should be valid");
-            }
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
         }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_logs_carry_game_and_request_ids() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let (app, temp_dir) = setup_test_app().await;
+        let session_manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand[0] = Card::new(Color::Red, CardType::Number(7));
+        let session = session_manager.create_session(game).unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/games/{}/play", session.id))
+            .header("Content-Type", "application/json")
+            .header("If-Match", "*")
+            .body(Body::from(json!({ "card_index": 0 }).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let played = output
+            .lines()
+            .find(|line| line.contains("Successfully played card"))
+            .unwrap();
+        assert!(played.contains(&format!("game_id={}", session.id)));
+        assert!(played.contains("request_id="));
+        assert!(played.contains("event=\"CardPlayed\""));
+        assert!(output.contains("card_index=0"));
     }
 }
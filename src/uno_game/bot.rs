@@ -0,0 +1,239 @@
+use super::card::{Card, CardType, Color};
+use super::game::{GameEvent, GameStatus, UnoGame};
+
+/// A move chosen by the computer player.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BotMove {
+    /// Play the card at this index, with the color to choose for a wild card.
+    Play(usize, Option<Color>),
+    Draw,
+}
+
+/// Picks a move for `player_id`.
+/// Prefers a card matching the top card's color, then one matching its number,
+/// then any other playable action card, and only then a wild card (choosing the
+/// color the bot holds most of). Draws when nothing is playable.
+pub fn choose_move(game: &UnoGame, player_id: usize) -> BotMove {
+    if game.pending_draws > 0 {
+        return BotMove::Draw;
+    }
+    if game.pending_skips > 0 {
+        // Pass stacked Skips on if possible, otherwise take them
+        return match game.playable_indices().first() {
+            Some(&index) => BotMove::Play(index, None),
+            None => BotMove::Draw,
+        };
+    }
+
+    let top_card = match game.discard_pile.last() {
+        Some((card, _)) => card,
+        None => return BotMove::Draw,
+    };
+    let hand = &game.players[player_id].hand;
+    let playable: Vec<(usize, &Card)> = hand
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| card.can_play_on(top_card))
+        .collect();
+
+    let matching_color = playable
+        .iter()
+        .find(|(_, card)| !card.is_wild() && card.color == top_card.color);
+    let matching_number = playable.iter().find(|(_, card)| {
+        matches!((&card.card_type, &top_card.card_type), (CardType::Number(n), CardType::Number(m)) if n == m)
+    });
+    let action = playable.iter().find(|(_, card)| {
+        matches!(
+            card.card_type,
+            CardType::Skip | CardType::Reverse | CardType::DrawTwo
+        )
+    });
+    let other = playable.iter().find(|(_, card)| !card.is_wild());
+
+    if let Some((index, _)) = matching_color.or(matching_number).or(action).or(other) {
+        return BotMove::Play(*index, None);
+    }
+
+    match playable.first() {
+        Some((index, _)) => BotMove::Play(*index, Some(favourite_color(hand))),
+        None => BotMove::Draw,
+    }
+}
+
+/// Returns the color the hand holds most of, ignoring wild cards.
+fn favourite_color(hand: &[Card]) -> Color {
+    Color::playable()
+        .into_iter()
+        .max_by_key(|color| hand.iter().filter(|card| card.color == *color).count())
+        .unwrap()
+}
+
+/// Applies a bot move to the game for `player_id`.
+/// Under the Seven-Zero rule a 7 swaps hands with the opponent holding the fewest cards.
+pub fn apply_move(
+    game: &mut UnoGame,
+    player_id: usize,
+    bot_move: &BotMove,
+) -> Result<GameEvent, String> {
+    match bot_move {
+        BotMove::Play(index, color) => {
+            if let Some(color) = color {
+                game.players[player_id].hand[*index].color = *color;
+            }
+            let target = game
+                .players
+                .iter()
+                .filter(|p| p.active && p.id != player_id)
+                .min_by_key(|p| p.hand.len())
+                .map(|p| p.id);
+            game.play_card_with_target(player_id, *index, target)
+                .map_err(|e| e.to_string())
+        }
+        BotMove::Draw => game.draw_card(player_id).map_err(|e| e.to_string()),
+    }
+}
+
+/// Most bot moves `advance_bots` makes in one call, so a game of bots alone can't keep
+/// it busy forever. Play simply stops; a later call carries on.
+pub const MAX_BOT_MOVES: usize = 200;
+
+/// Plays for bots while it's a bot's turn and the game is in progress, stopping when a
+/// human must act, the game ends or `MAX_BOT_MOVES` have been made. Returns every event,
+/// in order.
+pub fn advance_bots(game: &mut UnoGame) -> Result<Vec<GameEvent>, String> {
+    let mut events = Vec::new();
+    while events.len() < MAX_BOT_MOVES
+        && game.status == GameStatus::InProgress
+        && game.players[game.current_turn].is_bot
+    {
+        let player_id = game.current_turn;
+        let bot_move = choose_move(game, player_id);
+        events.push(apply_move(game, player_id, &bot_move)?);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::{Direction, Player};
+
+    fn game_with(top: Card, hand: Vec<Card>) -> UnoGame {
+        let mut alice = Player::new(0, "Alice".to_string());
+        alice.hand = hand;
+        let mut bot = Player::new(1, "Bot".to_string());
+        bot.hand = vec![Card::new(Color::Green, CardType::Number(1)); 7];
+        let deck = vec![Card::new(Color::Yellow, CardType::Number(9)); 10];
+        UnoGame::from_parts(
+            vec![alice, bot],
+            deck,
+            vec![(top, usize::MAX)],
+            0,
+            Direction::Clockwise,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bot_prefers_matching_color() {
+        let game = game_with(
+            Card::new(Color::Red, CardType::Number(5)),
+            vec![
+                Card::new(Color::Wild, CardType::Wild),
+                Card::new(Color::Blue, CardType::Number(5)),
+                Card::new(Color::Red, CardType::Number(2)),
+            ],
+        );
+        assert_eq!(choose_move(&game, 0), BotMove::Play(2, None));
+    }
+
+    #[test]
+    fn test_bot_prefers_number_over_wild() {
+        let game = game_with(
+            Card::new(Color::Red, CardType::Number(5)),
+            vec![
+                Card::new(Color::Wild, CardType::WildDrawFour),
+                Card::new(Color::Blue, CardType::Number(5)),
+            ],
+        );
+        assert_eq!(choose_move(&game, 0), BotMove::Play(1, None));
+    }
+
+    #[test]
+    fn test_bot_plays_wild_with_most_held_color() {
+        let game = game_with(
+            Card::new(Color::Red, CardType::Number(5)),
+            vec![
+                Card::new(Color::Green, CardType::Number(1)),
+                Card::new(Color::Wild, CardType::Wild),
+                Card::new(Color::Yellow, CardType::Number(2)),
+                Card::new(Color::Yellow, CardType::Skip),
+            ],
+        );
+        assert_eq!(choose_move(&game, 0), BotMove::Play(1, Some(Color::Yellow)));
+    }
+
+    #[test]
+    fn test_bot_draws_when_nothing_playable() {
+        let game = game_with(
+            Card::new(Color::Red, CardType::Number(5)),
+            vec![
+                Card::new(Color::Green, CardType::Number(1)),
+                Card::new(Color::Blue, CardType::Skip),
+            ],
+        );
+        assert_eq!(choose_move(&game, 0), BotMove::Draw);
+
+        let mut game = game_with(
+            Card::new(Color::Red, CardType::Number(5)),
+            vec![Card::new(Color::Red, CardType::Number(1))],
+        );
+        game.pending_draws = 2;
+        assert_eq!(choose_move(&game, 0), BotMove::Draw);
+    }
+
+    #[test]
+    fn test_bot_moves_are_always_legal() {
+        let scenarios = vec![
+            (
+                Card::new(Color::Blue, CardType::Skip),
+                vec![
+                    Card::new(Color::Red, CardType::Skip),
+                    Card::new(Color::Green, CardType::Number(3)),
+                ],
+            ),
+            (
+                Card::new(Color::Wild, CardType::Wild),
+                vec![
+                    Card::new(Color::Yellow, CardType::Reverse),
+                    Card::new(Color::Wild, CardType::WildDrawFour),
+                ],
+            ),
+            (
+                Card::new(Color::Green, CardType::Number(7)),
+                vec![
+                    Card::new(Color::Wild, CardType::WildDrawFour),
+                    Card::new(Color::Blue, CardType::DrawTwo),
+                ],
+            ),
+            (
+                Card::new(Color::Yellow, CardType::DrawTwo),
+                vec![
+                    Card::new(Color::Yellow, CardType::Number(7)),
+                    Card::new(Color::Red, CardType::DrawTwo),
+                ],
+            ),
+        ];
+
+        for (top, hand) in scenarios {
+            let mut game = game_with(top, hand);
+            game.config.seven_zero_enabled = true;
+            let bot_move = choose_move(&game, 0);
+            assert!(
+                apply_move(&mut game, 0, &bot_move).is_ok(),
+                "{:?} was not legal",
+                bot_move
+            );
+        }
+    }
+}
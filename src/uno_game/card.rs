@@ -1,6 +1,10 @@
+use super::game::GameError;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Colors order as declared, the same order as `Color::all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Color {
     Red,
     Green,
@@ -9,7 +13,8 @@ pub enum Color {
     Wild,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Card types order as declared, numbers from low to high, the same order as `CardType::all`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CardType {
     Number(u8),
     Skip,
@@ -19,6 +24,57 @@ pub enum CardType {
     WildDrawFour,
 }
 
+impl Color {
+    /// The four colors a card can be played as, in deck order.
+    pub fn playable() -> [Color; 4] {
+        [Color::Red, Color::Green, Color::Blue, Color::Yellow]
+    }
+
+    /// Every color, including `Wild` for uncolored wild cards.
+    pub fn all() -> [Color; 5] {
+        [
+            Color::Red,
+            Color::Green,
+            Color::Blue,
+            Color::Yellow,
+            Color::Wild,
+        ]
+    }
+}
+
+impl CardType {
+    /// Every card type in a standard deck: the numbers 0 to 9, the actions and the wilds.
+    pub fn all() -> [CardType; 15] {
+        [
+            CardType::Number(0),
+            CardType::Number(1),
+            CardType::Number(2),
+            CardType::Number(3),
+            CardType::Number(4),
+            CardType::Number(5),
+            CardType::Number(6),
+            CardType::Number(7),
+            CardType::Number(8),
+            CardType::Number(9),
+            CardType::Skip,
+            CardType::Reverse,
+            CardType::DrawTwo,
+            CardType::Wild,
+            CardType::WildDrawFour,
+        ]
+    }
+
+    /// The colored action cards.
+    pub fn actions() -> [CardType; 3] {
+        [CardType::Skip, CardType::Reverse, CardType::DrawTwo]
+    }
+
+    /// True for Wild and Wild Draw Four.
+    pub fn is_wild(&self) -> bool {
+        matches!(self, CardType::Wild | CardType::WildDrawFour)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Card {
     pub color: Color,
@@ -35,4 +91,310 @@ impl Card {
             player_id: None,
         }
     }
+
+    /// Builds a number card, refusing anything a real deck doesn't have: numbers above 9
+    /// or the `Wild` color.
+    pub fn number(color: Color, n: u8) -> Result<Self, GameError> {
+        let card = Self::new(color, CardType::Number(n));
+        card.check()?;
+        Ok(card)
+    }
+
+    /// Checks that the card is one a real deck could hold. Only number cards can be
+    /// impossible, since `CardType::Number` takes any `u8`.
+    pub fn check(&self) -> Result<(), GameError> {
+        match self.card_type {
+            CardType::Number(n) if n > 9 => Err(GameError::Other(format!(
+                "Card numbers run from 0 to 9, got {}",
+                n
+            ))),
+            CardType::Number(_) if self.color == Color::Wild => {
+                Err(GameError::Other("Number cards can't be Wild".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the scoring value of the card: face value for numbers,
+    /// 20 for action cards and 50 for wild cards.
+    pub fn points(&self) -> u32 {
+        match self.card_type {
+            CardType::Number(n) => n as u32,
+            CardType::Skip | CardType::Reverse | CardType::DrawTwo => 20,
+            CardType::Wild | CardType::WildDrawFour => 50,
+        }
+    }
+
+    /// True for Wild and Wild Draw Four, whether or not a color has been chosen.
+    pub fn is_wild(&self) -> bool {
+        self.card_type.is_wild()
+    }
+
+    /// The color chosen for a played wild. A wild keeps its card type once colored, so
+    /// `None` means either a colored card or a wild still waiting for its color.
+    pub fn chosen_color(&self) -> Option<Color> {
+        (self.is_wild() && self.color != Color::Wild).then_some(self.color)
+    }
+
+    /// Returns a wild to its uncolored state, as it is before being played.
+    pub fn clear_chosen_color(&mut self) {
+        if self.is_wild() {
+            self.color = Color::Wild;
+        }
+    }
+
+    /// Whether this card can be played on `top`: a wild goes on anything, anything goes
+    /// on an uncolored wild, and otherwise the colors or numbers must match.
+    pub fn can_play_on(&self, top: &Card) -> bool {
+        if self.color == Color::Wild || self.is_wild() {
+            return true;
+        }
+        if top.color == Color::Wild {
+            return true;
+        }
+        self.color == top.color
+            || match (&self.card_type, &top.card_type) {
+                (CardType::Number(n), CardType::Number(m)) => n == m,
+                // Action cards (Skip, Reverse, DrawTwo) can only be played on matching colors
+                _ => false,
+            }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Red => write!(f, "Red"),
+            Color::Green => write!(f, "Green"),
+            Color::Blue => write!(f, "Blue"),
+            Color::Yellow => write!(f, "Yellow"),
+            Color::Wild => write!(f, "Wild"),
+        }
+    }
+}
+
+impl fmt::Display for CardType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardType::Number(n) => write!(f, "{}", n),
+            CardType::Skip => write!(f, "Skip"),
+            CardType::Reverse => write!(f, "Reverse"),
+            CardType::DrawTwo => write!(f, "Draw Two"),
+            CardType::Wild => write!(f, "Wild"),
+            CardType::WildDrawFour => write!(f, "Wild Draw Four"),
+        }
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.card_type, self.color) {
+            // An uncolored wild is just its type; a played wild shows the chosen color
+            (CardType::Wild | CardType::WildDrawFour, Color::Wild) => {
+                write!(f, "{}", self.card_type)
+            }
+            (CardType::Wild | CardType::WildDrawFour, color) => {
+                write!(f, "{} ({})", self.card_type, color)
+            }
+            (card_type, color) => write!(f, "{} {}", color, card_type),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parses one of the four playable colors, ignoring case and surrounding whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Color::playable()
+            .into_iter()
+            .find(|color| color.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Invalid color: {}", s.trim()))
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    /// Parses cards written like "red 5", "blue skip", "green draw two", "wild"
+    /// or "wild draw four", ignoring case and extra whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<String> = s.split_whitespace().map(|w| w.to_lowercase()).collect();
+        let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+        let invalid = || format!("Invalid card: {}", s.trim());
+
+        match words.as_slice() {
+            ["wild"] => Ok(Card::new(Color::Wild, CardType::Wild)),
+            ["wild", "draw", "four" | "4"] => Ok(Card::new(Color::Wild, CardType::WildDrawFour)),
+            [color, rest @ ..] => {
+                let color: Color = color.parse().map_err(|_| invalid())?;
+                let card_type = match rest {
+                    ["skip"] => CardType::Skip,
+                    ["reverse"] => CardType::Reverse,
+                    ["draw", "two" | "2"] => CardType::DrawTwo,
+                    [n] => {
+                        let n = n.parse::<u8>().map_err(|_| invalid())?;
+                        return Card::number(color, n).map_err(|_| invalid());
+                    }
+                    _ => return Err(invalid()),
+                };
+                Ok(Card::new(color, card_type))
+            }
+            [] => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_play_on() {
+        let red_one = Card::new(Color::Red, CardType::Number(1));
+        let blue_one = Card::new(Color::Blue, CardType::Number(1));
+        let blue_two = Card::new(Color::Blue, CardType::Number(2));
+        let wild = Card::new(Color::Wild, CardType::Wild);
+        let red_wild = Card::new(Color::Red, CardType::WildDrawFour);
+
+        // Same color, or same number in another color
+        assert!(red_one.can_play_on(&red_one));
+        assert!(red_one.can_play_on(&blue_one));
+        assert!(!red_one.can_play_on(&blue_two));
+        // Wilds go on anything, and anything goes on an uncolored wild
+        assert!(wild.can_play_on(&red_one));
+        assert!(red_one.can_play_on(&wild));
+        // A played wild takes its chosen color
+        assert!(red_one.can_play_on(&red_wild));
+        assert!(!blue_two.can_play_on(&red_wild));
+
+        // Action cards need a matching color, even on the same action
+        let red_skip = Card::new(Color::Red, CardType::Skip);
+        let blue_skip = Card::new(Color::Blue, CardType::Skip);
+        assert!(red_skip.can_play_on(&red_one));
+        assert!(!red_skip.can_play_on(&blue_skip));
+        assert!(!blue_skip.can_play_on(&red_one));
+    }
+
+    #[test]
+    fn test_card_display() {
+        let cases = vec![
+            (Card::new(Color::Red, CardType::Number(5)), "Red 5"),
+            (Card::new(Color::Yellow, CardType::Number(0)), "Yellow 0"),
+            (Card::new(Color::Blue, CardType::Skip), "Blue Skip"),
+            (Card::new(Color::Green, CardType::Reverse), "Green Reverse"),
+            (Card::new(Color::Red, CardType::DrawTwo), "Red Draw Two"),
+            (Card::new(Color::Wild, CardType::Wild), "Wild"),
+            (
+                Card::new(Color::Wild, CardType::WildDrawFour),
+                "Wild Draw Four",
+            ),
+            (Card::new(Color::Red, CardType::Wild), "Wild (Red)"),
+            (
+                Card::new(Color::Blue, CardType::WildDrawFour),
+                "Wild Draw Four (Blue)",
+            ),
+        ];
+
+        for (card, expected) in cases {
+            assert_eq!(card.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_color_and_card_type_display() {
+        assert_eq!(Color::Yellow.to_string(), "Yellow");
+        assert_eq!(Color::Wild.to_string(), "Wild");
+        assert_eq!(CardType::Number(7).to_string(), "7");
+        assert_eq!(CardType::DrawTwo.to_string(), "Draw Two");
+        assert_eq!(CardType::WildDrawFour.to_string(), "Wild Draw Four");
+    }
+
+    #[test]
+    fn test_chosen_color() {
+        let mut wild = Card::new(Color::Wild, CardType::WildDrawFour);
+        assert_eq!(wild.chosen_color(), None);
+
+        wild.color = Color::Blue;
+        assert_eq!(wild.chosen_color(), Some(Color::Blue));
+        assert_eq!(wild.points(), 50);
+
+        wild.clear_chosen_color();
+        assert_eq!(wild, Card::new(Color::Wild, CardType::WildDrawFour));
+
+        let mut five = Card::new(Color::Red, CardType::Number(5));
+        assert!(!five.is_wild());
+        assert_eq!(five.chosen_color(), None);
+        five.clear_chosen_color();
+        assert_eq!(five.color, Color::Red);
+    }
+
+    #[test]
+    fn test_color_lists() {
+        assert_eq!(
+            Color::playable(),
+            [Color::Red, Color::Green, Color::Blue, Color::Yellow]
+        );
+        assert_eq!(Color::all()[..4], Color::playable());
+        assert_eq!(Color::all()[4], Color::Wild);
+
+        let wilds: Vec<CardType> = CardType::all()
+            .into_iter()
+            .filter(CardType::is_wild)
+            .collect();
+        assert_eq!(wilds, vec![CardType::Wild, CardType::WildDrawFour]);
+        assert!(CardType::actions()
+            .iter()
+            .all(|action| CardType::all().contains(action)));
+    }
+
+    #[test]
+    fn test_number_card_range() {
+        for n in 0..=9 {
+            let card = Card::number(Color::Green, n).unwrap();
+            assert_eq!(card, Card::new(Color::Green, CardType::Number(n)));
+        }
+        assert!(Card::number(Color::Green, 10).is_err());
+        assert!(Card::number(Color::Wild, 5).is_err());
+        assert!(Card::new(Color::Red, CardType::Number(42)).check().is_err());
+        assert!(Card::new(Color::Red, CardType::Wild).check().is_ok());
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!("red".parse::<Color>(), Ok(Color::Red));
+        assert_eq!("  GREEN ".parse::<Color>(), Ok(Color::Green));
+        assert_eq!("Blue".parse::<Color>(), Ok(Color::Blue));
+        assert_eq!("yellow\n".parse::<Color>(), Ok(Color::Yellow));
+        assert!("wild".parse::<Color>().is_err());
+        assert!("purple".parse::<Color>().is_err());
+        assert!("".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let cases = vec![
+            ("red 5", Card::new(Color::Red, CardType::Number(5))),
+            ("  Blue   Skip ", Card::new(Color::Blue, CardType::Skip)),
+            ("GREEN reverse", Card::new(Color::Green, CardType::Reverse)),
+            (
+                "yellow draw two",
+                Card::new(Color::Yellow, CardType::DrawTwo),
+            ),
+            ("red draw 2", Card::new(Color::Red, CardType::DrawTwo)),
+            ("Wild", Card::new(Color::Wild, CardType::Wild)),
+            (
+                "wild draw four",
+                Card::new(Color::Wild, CardType::WildDrawFour),
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(text.parse::<Card>(), Ok(expected), "parsing {:?}", text);
+        }
+
+        for text in ["", "red", "red 10", "purple 5", "wild skip", "blue draw"] {
+            assert!(text.parse::<Card>().is_err(), "parsing {:?}", text);
+        }
+    }
 }
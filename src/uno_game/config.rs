@@ -0,0 +1,115 @@
+use super::game::GameError;
+use serde::{Deserialize, Serialize};
+
+/// Rules and setup options for a game. Options left out take their defaults, and
+/// unknown ones are refused rather than silently ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    /// Number of cards dealt to each player at the start.
+    pub starting_hand_size: usize,
+    /// Number of decks shuffled together.
+    pub deck_copies: usize,
+    /// Wild cards in each deck.
+    pub wild_count: usize,
+    /// Wild Draw Four cards in each deck.
+    pub wild_draw_four_count: usize,
+    /// When set, a player who draws a playable card keeps the turn and may play it.
+    pub draw_then_play: bool,
+    /// When set, a player with nothing to play keeps drawing until they draw a
+    /// playable card, and may then play it.
+    pub draw_until_playable: bool,
+    /// When set, playing a 7 swaps hands with a chosen player and playing a 0
+    /// passes every hand one seat in the direction of play.
+    pub seven_zero_enabled: bool,
+    /// Most cards a player may hold. A player at the limit can't draw, and their turn
+    /// passes instead. `None` means no limit.
+    pub max_hand_size: Option<usize>,
+    /// When set, a player holding a card they can play must play instead of drawing.
+    pub must_play_if_able: bool,
+    /// When set, anyone may look at the next card in the deck through
+    /// `GET /games/{id}/deck/next`. Meant for teaching and variant games.
+    pub allow_deck_peek: bool,
+    /// When set, a Skip doesn't skip straight away. The next player may pass it on with a
+    /// Skip of their own, in any color; whoever can't, or won't, loses a turn for every
+    /// Skip in the chain.
+    pub skip_stacking: bool,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            starting_hand_size: 7,
+            deck_copies: 1,
+            wild_count: 4,
+            wild_draw_four_count: 4,
+            draw_then_play: false,
+            draw_until_playable: false,
+            seven_zero_enabled: false,
+            max_hand_size: None,
+            must_play_if_able: false,
+            allow_deck_peek: false,
+            skip_stacking: false,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Checks that the options make sense together.
+    pub fn validate(&self) -> Result<(), GameError> {
+        let invalid = |msg: &str| Err(GameError::Other(msg.to_string()));
+        if self.deck_copies == 0 {
+            return invalid("At least one deck is required");
+        }
+        if self.draw_then_play && self.draw_until_playable {
+            return invalid(
+                "draw_then_play and draw_until_playable can't both be set; draw_until_playable already lets the drawn card be played",
+            );
+        }
+        if let Some(max) = self.max_hand_size {
+            if max < self.starting_hand_size {
+                return Err(GameError::Other(format!(
+                    "max_hand_size {} is smaller than the starting hand of {}",
+                    max, self.starting_hand_size
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_conflicting_options() {
+        assert!(GameConfig::default().validate().is_ok());
+
+        let both_draw_rules = GameConfig {
+            draw_then_play: true,
+            draw_until_playable: true,
+            ..GameConfig::default()
+        };
+        assert!(both_draw_rules.validate().is_err());
+
+        let small_limit = GameConfig {
+            max_hand_size: Some(5),
+            ..GameConfig::default()
+        };
+        assert!(small_limit.validate().is_err());
+
+        let no_decks = GameConfig {
+            deck_copies: 0,
+            ..GameConfig::default()
+        };
+        assert!(no_decks.validate().is_err());
+    }
+
+    #[test]
+    fn test_unknown_options_are_refused() {
+        let config: GameConfig = serde_json::from_str(r#"{"seven_zero_enabled": true}"#).unwrap();
+        assert!(config.seven_zero_enabled);
+        assert!(serde_json::from_str::<GameConfig>(r#"{"stacking": true}"#).is_err());
+    }
+}
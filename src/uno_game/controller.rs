@@ -1,17 +1,31 @@
-use super::game::{GameEvent, UnoGame};
+use super::bot;
+use super::game::{GameEvent, ReshuffleLog, UnoGame};
+use super::session::{GameSession, SessionManager};
 use super::ui::ConsoleUI;
 
 pub struct GameController {
     game: UnoGame,
     ui: ConsoleUI,
+    reshuffles: ReshuffleLog,
+}
+
+impl Default for GameController {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameController {
     pub fn new() -> Self {
         let mut ui = ConsoleUI::new();
         let player_names = ui.get_player_names();
-        let game = UnoGame::new(player_names).unwrap();
-        Self { game, ui }
+        let mut game = UnoGame::new(player_names).unwrap();
+        let reshuffles = ReshuffleLog::attach(&mut game);
+        Self {
+            game,
+            ui,
+            reshuffles,
+        }
     }
 
     pub fn run(&mut self) {
@@ -46,9 +60,17 @@ impl GameController {
                                             let (top_card, _) =
                                                 self.game.discard_pile.last_mut().unwrap();
                                             top_card.color = color;
-                                            self.ui.handle_game_event(&event, &self.game);
+                                            self.ui.handle_move(
+                                                &self.reshuffles,
+                                                &event,
+                                                &self.game,
+                                            );
                                         }
-                                        _ => self.ui.handle_game_event(&event, &self.game),
+                                        _ => self.ui.handle_move(
+                                            &self.reshuffles,
+                                            &event,
+                                            &self.game,
+                                        ),
                                     }
 
                                     if let GameEvent::PlayerWins { player_id: _ } = event {
@@ -56,7 +78,7 @@ impl GameController {
                                     }
                                 }
                                 Err(e) => {
-                                    println!("Error: {:?}", e);
+                                    println!("Error: {}", e);
                                     println!("Please try again.");
                                     continue; // Repeat the turn
                                 }
@@ -71,7 +93,7 @@ impl GameController {
                 "2" => {
                     // Draw a card
                     match self.game.draw_card(self.game.current_turn) {
-                        Ok(event) => self.ui.handle_game_event(&event, &self.game),
+                        Ok(event) => self.ui.handle_move(&self.reshuffles, &event, &self.game),
                         Err(e) => println!("Error: {:?}", e),
                     }
                 }
@@ -83,3 +105,155 @@ impl GameController {
         }
     }
 }
+
+/// Plays a single turn of a saved session and saves the result.
+/// Returns true once the game is over.
+pub fn play_turn(session: &mut GameSession, manager: &SessionManager, ui: &mut ConsoleUI) -> bool {
+    if session.game.status.is_over() {
+        println!("This game is already over.");
+        return true;
+    }
+
+    println!(
+        "\n=== Current Turn: Player {} ===",
+        session.game.current_turn
+    );
+    ui.display_game_state(&session.game);
+
+    let reshuffles = ReshuffleLog::attach(&mut session.game);
+    let player_name = session.game.players[session.game.current_turn].name.clone();
+    let player = &session.game.players[session.game.current_turn];
+
+    let event = if player.is_bot {
+        let player_id = player.id;
+        let bot_move = bot::choose_move(&session.game, player_id);
+        match bot::apply_move(&mut session.game, player_id, &bot_move) {
+            Ok(event) => event,
+            Err(e) => {
+                println!("Error: {}", e);
+                return false;
+            }
+        }
+    } else {
+        ui.display_player_hand(&player.name, &player.hand);
+        match play_human_turn(session, ui) {
+            Some(event) => event,
+            None => return false,
+        }
+    };
+
+    ui.handle_move(&reshuffles, &event, &session.game);
+
+    if let Err(e) = manager.save_session(session) {
+        println!("Failed to save game state: {}", e);
+    }
+
+    if let GameEvent::PlayerWins { player_id: _ } = event {
+        println!("Game Over! Player {} wins!", player_name);
+    }
+    session.game.status.is_over()
+}
+
+/// Asks the current player for their action and applies it.
+/// Returns `None` if the input was invalid or the move was rejected.
+fn play_human_turn(session: &mut GameSession, ui: &mut ConsoleUI) -> Option<GameEvent> {
+    let player = &session.game.players[session.game.current_turn];
+    let choice = ui.get_player_action();
+    let event = match choice.as_str() {
+        "1" => {
+            let (index, color) = match ui.get_card_play(&player.hand) {
+                Ok(result) => result,
+                Err(e) => {
+                    println!("{}", e);
+                    return None;
+                }
+            };
+
+            if let Some(color) = color {
+                session.game.players[session.game.current_turn].hand[index].color = color;
+            }
+
+            match session.game.play_card(session.game.current_turn, index) {
+                Ok(event) => event,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    return None;
+                }
+            }
+        }
+        "2" => match session.game.draw_card(session.game.current_turn) {
+            Ok(event) => event,
+            Err(e) => {
+                println!("Error: {:?}", e);
+                return None;
+            }
+        },
+        _ => {
+            println!("Invalid choice. Please enter 1 or 2.");
+            return None;
+        }
+    };
+    Some(event)
+}
+
+/// Plays turns of a saved session until the game ends or the user
+/// chooses to return to the menu. The session is saved after every turn.
+pub fn play_session(session: &mut GameSession, manager: &SessionManager, ui: &mut ConsoleUI) {
+    loop {
+        let bot_turn = session.game.players[session.game.current_turn].is_bot;
+        if play_turn(session, manager, ui) {
+            return;
+        }
+        // Bots play on without asking; only humans are offered a break
+        if !bot_turn && !ui.prompt_continue() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::card::{Card, CardType, Color};
+    use std::io::Cursor;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_play_session_until_user_quits() {
+        let temp_dir = tempdir().unwrap();
+        let manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut session = manager.create_session(game).unwrap();
+
+        // Alice draws, continue, Bob draws, then save and return to the menu
+        let input = Cursor::new("2\n1\n2\n2\n");
+        let mut ui = ConsoleUI::with_streams(Box::new(input), Box::new(Vec::new()));
+        play_session(&mut session, &manager, &mut ui);
+
+        let loaded = manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.current_turn, 0);
+        assert_eq!(loaded.game.players[0].hand.len(), 8);
+        assert_eq!(loaded.game.players[1].hand.len(), 8);
+    }
+
+    #[test]
+    fn test_play_session_runs_bot_turns() {
+        let temp_dir = tempdir().unwrap();
+        let manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bot 1".to_string()]).unwrap();
+        game.players[1].is_bot = true;
+        // Give the bot plain cards so its turn can't skip Alice or make her draw extra
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[1].hand = vec![Card::new(Color::Red, CardType::Number(3)); 7];
+        let mut session = manager.create_session(game).unwrap();
+
+        // Alice draws and continues; the bot plays without prompting; Alice then quits
+        let input = Cursor::new("2\n1\n2\n2\n");
+        let mut ui = ConsoleUI::with_streams(Box::new(input), Box::new(Vec::new()));
+        play_session(&mut session, &manager, &mut ui);
+
+        let loaded = manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game.players[0].hand.len(), 9);
+        assert_eq!(loaded.game.players[1].hand.len(), 6);
+    }
+}
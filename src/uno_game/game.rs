@@ -1,31 +1,120 @@
 use super::card::{Card, CardType, Color};
+use super::config::GameConfig;
 use super::player::Player;
-use rand::seq::SliceRandom; // Import the shuffle functionality
+use super::shuffle::{RandomShuffler, SeededShuffler, Shuffler};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameStatus {
+    /// Waiting for players to join; no cards have been dealt yet.
+    Lobby,
     InProgress,
-    Complete { winner_id: usize },
+    Complete {
+        winner_id: usize,
+    },
+    /// No one could move; the player with the lowest hand score is the winner.
+    Stalemate {
+        winner_id: usize,
+    },
+}
+
+impl GameStatus {
+    /// Returns true if the game has finished, either with a winner or in a stalemate.
+    pub fn is_over(&self) -> bool {
+        matches!(
+            self,
+            GameStatus::Complete { .. } | GameStatus::Stalemate { .. }
+        )
+    }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnoGame {
     pub players: Vec<Player>,
+    /// Bottom first: cards are drawn by popping the last one.
     pub deck: Vec<Card>,
     pub discard_pile: Vec<(Card, usize)>, // (Card, player_id) tuples
     pub current_turn: usize,
     pub direction: Direction,
     pub pending_draws: usize, // Number of cards the current player must draw
+    /// Skips stacked on the current player under `skip_stacking`, each costing a turn
+    /// unless they pass the chain on.
+    #[serde(default)]
+    pub pending_skips: usize,
     pub status: GameStatus,
+    #[serde(default)]
+    pub config: GameConfig,
+    /// Incremented on every change to the game, so clients can detect stale views.
+    #[serde(default)]
+    pub version: u64,
+    /// Whether the current player has already drawn a card this turn.
+    #[serde(default)]
+    pub has_drawn_this_turn: bool,
+    /// Under `draw_then_play`, the hand index of the card just drawn, which is the only
+    /// card the player may now play.
+    #[serde(default)]
+    pub drawn_card_index: Option<usize>,
+    /// The Wild Draw Four the current player may challenge, if one was just played.
+    #[serde(default)]
+    pub challengeable_draw_four: Option<WildDrawFourPlay>,
+    /// Set for seeded games, whose deal and reshuffles are reproducible.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(skip)]
+    on_event: EventHandler,
+}
+
+/// The callback given to `UnoGame::set_event_handler`. It is not saved or compared, and
+/// a clone of a game starts without one, so moves tried on a copy aren't reported.
+#[derive(Default)]
+struct EventHandler(Option<EventCallback>);
+
+type EventCallback = Box<dyn FnMut(&GameEvent) + Send>;
+
+impl std::fmt::Debug for EventHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = if self.0.is_some() { "set" } else { "unset" };
+        write!(f, "EventHandler({})", state)
+    }
+}
+
+impl Clone for EventHandler {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl PartialEq for EventHandler {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// What is needed to judge a challenge to a Wild Draw Four.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WildDrawFourPlay {
+    pub player_id: usize,
+    /// The color in play before the Wild Draw Four.
+    pub previous_color: Color,
+    /// Whether the player still held a card of `previous_color` when they played it.
+    pub had_matching_color: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum GameError {
     InvalidMove,
+    /// Someone other than the current player tried to play.
+    NotYourTurn,
+    /// The card can't be played now; the message from `explain_playability` says why.
+    UnplayableCard(String),
     CardNotInHand,
     GameAlreadyOver,
+    /// The game is still in the lobby.
+    NotStarted,
     EmptyDeck,
+    /// The game has no top card to play on, which only a corrupt game can reach.
+    EmptyDiscardPile,
     Other(String),
 }
 
@@ -33,15 +122,19 @@ impl std::fmt::Display for GameError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GameError::InvalidMove => write!(f, "Invalid move"),
+            GameError::NotYourTurn => write!(f, "Not your turn"),
+            GameError::UnplayableCard(reason) => write!(f, "{}", reason),
             GameError::CardNotInHand => write!(f, "Card not in hand"),
             GameError::GameAlreadyOver => write!(f, "Game is already over"),
+            GameError::NotStarted => write!(f, "Game has not started"),
             GameError::EmptyDeck => write!(f, "Deck is empty"),
+            GameError::EmptyDiscardPile => write!(f, "Discard pile is empty"),
             GameError::Other(msg) => write!(f, "{}", msg),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameEvent {
     CardPlayed {
         player_id: usize,
@@ -52,6 +145,17 @@ pub enum GameEvent {
         player_id: usize,
         card: Card,
     },
+    CardDrawnPlayable {
+        player_id: usize,
+        card: Card,
+        index: usize,
+    },
+    /// Cards drawn under the draw-until-playable rule; the last one is playable
+    /// unless the deck ran out first.
+    CardsDrawnUntilPlayable {
+        player_id: usize,
+        cards: Vec<Card>,
+    },
     Skip {
         player_id: usize,
     },
@@ -73,6 +177,143 @@ pub enum GameEvent {
     PlayerWins {
         player_id: usize,
     },
+    PlayerForfeited {
+        player_id: usize,
+    },
+    /// A moderator removed the player from the game.
+    PlayerKicked {
+        player_id: usize,
+    },
+    HandSwap {
+        player_id: usize,
+        target_player_id: usize,
+    },
+    HandRotate {
+        direction: Direction,
+    },
+    Stalemate {
+        winner_id: usize,
+    },
+    TurnPassed {
+        player_id: usize,
+    },
+    /// The player was at the hand size limit, so drew nothing and their turn passed.
+    HandFull {
+        player_id: usize,
+    },
+    ChallengeResult {
+        success: bool,
+        drawer_id: usize,
+        cards: Vec<Card>,
+    },
+    /// The player ended a chain of stacked Skips, losing this turn and one more for
+    /// each further Skip in it.
+    TurnsSkipped {
+        player_id: usize,
+        turns: usize,
+    },
+    /// The deck ran out during a draw, so the discard pile below the top card was
+    /// shuffled back into it. Only reported to the event handler, just before the
+    /// event of the move that drew.
+    DeckReshuffled {
+        cards_recycled: usize,
+    },
+}
+
+/// Collects the `DeckReshuffled` events a game reports to its event handler, so callers
+/// that only see each move's own event can show reshuffles too. Attaching replaces any
+/// handler the game had.
+#[derive(Debug, Clone, Default)]
+pub struct ReshuffleLog(std::sync::Arc<std::sync::Mutex<Vec<GameEvent>>>);
+
+impl ReshuffleLog {
+    pub fn attach(game: &mut UnoGame) -> Self {
+        let log = Self::default();
+        let sink = log.clone();
+        game.set_event_handler(move |event| {
+            if let GameEvent::DeckReshuffled { .. } = event {
+                sink.0.lock().unwrap().push(event.clone());
+            }
+        });
+        log
+    }
+
+    /// The reshuffles since the last call, oldest first.
+    pub fn take(&self) -> Vec<GameEvent> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Where a player finished, from `UnoGame::standings`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Standing {
+    pub player_id: usize,
+    pub name: String,
+    /// 1 for the winner.
+    pub position: usize,
+    /// Points left in the player's hand when the game ended.
+    pub hand_score: u32,
+}
+
+/// A move open to the current player, from `UnoGame::legal_actions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    /// Play the card at `index` in their hand.
+    Play {
+        index: usize,
+    },
+    Draw,
+    /// Draw the cards owed from a Draw Two or Wild Draw Four.
+    TakePenalty,
+    /// End the turn after drawing.
+    Pass,
+    /// Pick a color for the wild on top of the discard pile.
+    ChooseColor,
+    /// Challenge the Wild Draw Four just played on them.
+    Challenge,
+}
+
+impl Action {
+    /// The action's name without its details, e.g. `"play"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Play { .. } => "play",
+            Action::Draw => "draw",
+            Action::TakePenalty => "take_penalty",
+            Action::Pass => "pass",
+            Action::ChooseColor => "choose_color",
+            Action::Challenge => "challenge",
+        }
+    }
+}
+
+impl GameEvent {
+    /// The variant name, e.g. `"CardPlayed"`, for logs and metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameEvent::CardPlayed { .. } => "CardPlayed",
+            GameEvent::CardDrawn { .. } => "CardDrawn",
+            GameEvent::CardDrawnPlayable { .. } => "CardDrawnPlayable",
+            GameEvent::CardsDrawnUntilPlayable { .. } => "CardsDrawnUntilPlayable",
+            GameEvent::Skip { .. } => "Skip",
+            GameEvent::Reverse => "Reverse",
+            GameEvent::DrawTwo { .. } => "DrawTwo",
+            GameEvent::WildColorChosen { .. } => "WildColorChosen",
+            GameEvent::WildDrawFour { .. } => "WildDrawFour",
+            GameEvent::PlayerWins { .. } => "PlayerWins",
+            GameEvent::PlayerForfeited { .. } => "PlayerForfeited",
+            GameEvent::PlayerKicked { .. } => "PlayerKicked",
+            GameEvent::HandSwap { .. } => "HandSwap",
+            GameEvent::HandRotate { .. } => "HandRotate",
+            GameEvent::Stalemate { .. } => "Stalemate",
+            GameEvent::TurnPassed { .. } => "TurnPassed",
+            GameEvent::HandFull { .. } => "HandFull",
+            GameEvent::ChallengeResult { .. } => "ChallengeResult",
+            GameEvent::TurnsSkipped { .. } => "TurnsSkipped",
+            GameEvent::DeckReshuffled { .. } => "DeckReshuffled",
+        }
+    }
 }
 
 /// Represents the direction of play.
@@ -91,18 +332,256 @@ impl Direction {
     }
 }
 
+/// Shuffles as usual, then moves `top` to just below the cards that will be dealt, so
+/// the first of them is flipped to start the discard pile and the rest are drawn next.
+struct PreparedTopShuffler {
+    top: Vec<Card>,
+    dealt: usize,
+}
+
+impl Shuffler for PreparedTopShuffler {
+    fn shuffle(&mut self, cards: &mut Vec<Card>) {
+        RandomShuffler.shuffle(cards);
+        for card in &self.top {
+            if let Some(index) = cards.iter().position(|c| c == card) {
+                cards.remove(index);
+            }
+        }
+        // Cards are dealt from the end of the deck
+        let split = cards.len().saturating_sub(self.dealt);
+        cards.splice(split..split, self.top.iter().rev().cloned());
+    }
+}
+
 impl UnoGame {
     pub fn new(player_names: Vec<String>) -> Result<Self, GameError> {
-        let mut deck = UnoGame::initialize_deck();
+        UnoGame::new_with_config(player_names, GameConfig::default())
+    }
+
+    pub const MIN_PLAYERS: usize = 2;
+    pub const MAX_PLAYERS: usize = 10;
+    /// Longest player name allowed, in characters.
+    pub const MAX_NAME_LEN: usize = 32;
+
+    pub fn new_with_config(
+        player_names: Vec<String>,
+        config: GameConfig,
+    ) -> Result<Self, GameError> {
+        UnoGame::deal(player_names, config, &mut RandomShuffler, None)
+    }
+
+    /// Like `new`, but `shuffler` decides the order of the deck before dealing.
+    pub fn new_with_shuffler(
+        player_names: Vec<String>,
+        shuffler: &mut dyn Shuffler,
+    ) -> Result<Self, GameError> {
+        UnoGame::deal(player_names, GameConfig::default(), shuffler, None)
+    }
+
+    /// Like `new`, but shuffles with `seed`, so the same names and seed always give the
+    /// same deal. Later reshuffles are seeded too, so replaying the same moves reproduces
+    /// the game exactly.
+    pub fn new_with_seed(player_names: Vec<String>, seed: u64) -> Result<Self, GameError> {
+        UnoGame::new_with_config_and_seed(player_names, GameConfig::default(), seed)
+    }
+
+    /// Like `new_with_seed`, with house rules from `config`.
+    pub fn new_with_config_and_seed(
+        player_names: Vec<String>,
+        config: GameConfig,
+        seed: u64,
+    ) -> Result<Self, GameError> {
+        let mut shuffler = SeededShuffler::new(seed);
+        UnoGame::deal(player_names, config, &mut shuffler, Some(seed))
+    }
+
+    /// Like `new`, but `top_cards[0]` is flipped to start the discard pile and the rest
+    /// are the next cards drawn, in order; the rest of the deck is shuffled as usual. For
+    /// tutorials that need action cards to come up early. Each card must be one the
+    /// standard deck has, and asked for no more times than the deck has it.
+    pub fn new_with_prepared_top(
+        player_names: Vec<String>,
+        top_cards: Vec<Card>,
+    ) -> Result<Self, GameError> {
+        let config = GameConfig::default();
+        let mut available = UnoGame::unshuffled_deck(&config);
+        for card in &top_cards {
+            let index = available.iter().position(|c| c == card).ok_or_else(|| {
+                GameError::Other(format!(
+                    "{} is not in the deck as many times as it was asked for",
+                    card
+                ))
+            })?;
+            available.swap_remove(index);
+        }
+        let dealt = player_names.len() * config.starting_hand_size;
+        if available.len() < dealt {
+            return Err(GameError::Other(format!(
+                "Too many prepared cards: {} players need {} cards dealt from the rest of the deck",
+                player_names.len(),
+                dealt
+            )));
+        }
+        let mut shuffler = PreparedTopShuffler {
+            top: top_cards,
+            dealt,
+        };
+        UnoGame::deal(player_names, config, &mut shuffler, None)
+    }
+
+    /// Opens a game in the lobby with the players who are already there. More can `join`
+    /// until the game is started with `start`, which deals the cards. `seed`, if given,
+    /// is used for that deal as with `new_with_seed`.
+    pub fn new_lobby(
+        player_names: Vec<String>,
+        config: GameConfig,
+        seed: Option<u64>,
+    ) -> Result<Self, GameError> {
+        if player_names.is_empty() || player_names.len() > UnoGame::MAX_PLAYERS {
+            return Err(GameError::Other(format!(
+                "A lobby needs between 1 and {} players, got {}",
+                UnoGame::MAX_PLAYERS,
+                player_names.len()
+            )));
+        }
+        UnoGame::check_names(&player_names)?;
+        config.validate()?;
+
+        let players = player_names
+            .into_iter()
+            .enumerate()
+            .map(|(id, name)| Player::new(id, name.trim().to_string()))
+            .collect();
+        Ok(Self {
+            players,
+            deck: Vec::new(),
+            discard_pile: Vec::new(),
+            current_turn: 0,
+            direction: Direction::Clockwise,
+            pending_draws: 0,
+            pending_skips: 0,
+            status: GameStatus::Lobby,
+            config,
+            version: 0,
+            has_drawn_this_turn: false,
+            drawn_card_index: None,
+            challengeable_draw_four: None,
+            seed,
+            on_event: EventHandler::default(),
+        })
+    }
+
+    /// Adds a player to a game in the lobby. Returns the new player's id.
+    pub fn join(&mut self, name: String) -> Result<usize, GameError> {
+        if self.status != GameStatus::Lobby {
+            return Err(GameError::Other("The game has already started".to_string()));
+        }
+        if self.players.len() >= UnoGame::MAX_PLAYERS {
+            return Err(GameError::Other(format!(
+                "The game already has {} players",
+                UnoGame::MAX_PLAYERS
+            )));
+        }
+        let mut names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
+        names.push(name.clone());
+        UnoGame::check_names(&names)?;
+
+        let id = self.players.len();
+        self.players.push(Player::new(id, name.trim().to_string()));
+        self.version += 1;
+        Ok(id)
+    }
+
+    /// Cards needed to start a game: a full hand for every player plus one card to start
+    /// the discard pile.
+    pub fn required_cards(num_players: usize, hand_size: usize) -> usize {
+        num_players * hand_size + 1
+    }
+
+    /// Deals the cards and flips the first discard for a game in the lobby.
+    /// The player count is checked here rather than when the lobby opens.
+    pub fn start(&mut self) -> Result<(), GameError> {
+        if self.status != GameStatus::Lobby {
+            return Err(GameError::Other("The game has already started".to_string()));
+        }
+        let names = self.players.iter().map(|p| p.name.clone()).collect();
+        let config = self.config.clone();
+        let mut dealt = match self.seed {
+            Some(seed) => UnoGame::deal(names, config, &mut SeededShuffler::new(seed), Some(seed)),
+            None => UnoGame::deal(names, config, &mut RandomShuffler, None),
+        }?;
+        for (player, waiting) in dealt.players.iter_mut().zip(&self.players) {
+            player.is_bot = waiting.is_bot;
+        }
+        dealt.version = self.version + 1;
+        dealt.on_event = std::mem::take(&mut self.on_event);
+        *self = dealt;
+        Ok(())
+    }
+
+    /// Deals a new round to the same players once the game is over: a fresh deck, new
+    /// hands and a new first discard, with play starting again from player 0. Players who
+    /// left are dealt back in and bot flags are kept. Running stats are kept too unless
+    /// `keep_stats` is false.
+    pub fn restart(&mut self, keep_stats: bool) -> Result<(), GameError> {
+        if !self.status.is_over() {
+            return Err(GameError::Other("The game is not over yet".to_string()));
+        }
+        let names = self.players.iter().map(|p| p.name.clone()).collect();
+        let config = self.config.clone();
+        let mut dealt = match self.seed {
+            // Mixing in the version gives each round of a seeded game its own deal
+            Some(seed) => UnoGame::deal(
+                names,
+                config,
+                &mut SeededShuffler::new(seed ^ self.version),
+                Some(seed),
+            ),
+            None => UnoGame::deal(names, config, &mut RandomShuffler, None),
+        }?;
+        for (player, previous) in dealt.players.iter_mut().zip(&self.players) {
+            player.is_bot = previous.is_bot;
+            if keep_stats {
+                player.stats = previous.stats.clone();
+            }
+        }
+        dealt.version = self.version + 1;
+        dealt.on_event = std::mem::take(&mut self.on_event);
+        *self = dealt;
+        Ok(())
+    }
+
+    fn deal(
+        player_names: Vec<String>,
+        config: GameConfig,
+        shuffler: &mut dyn Shuffler,
+        seed: Option<u64>,
+    ) -> Result<Self, GameError> {
+        UnoGame::validate_player_names(&player_names)?;
+        config.validate()?;
+
+        let mut deck = UnoGame::unshuffled_deck(&config);
+        shuffler.shuffle(&mut deck);
+
+        let cards_needed = UnoGame::required_cards(player_names.len(), config.starting_hand_size);
+        if deck.len() < cards_needed {
+            return Err(GameError::Other(format!(
+                "Not enough cards to deal {} cards to {} players: need {}, deck has {}",
+                config.starting_hand_size,
+                player_names.len(),
+                cards_needed,
+                deck.len()
+            )));
+        }
 
         let mut players = player_names
             .into_iter()
             .enumerate()
-            .map(|(id, name)| Player::new(id, name))
+            .map(|(id, name)| Player::new(id, name.trim().to_string()))
             .collect::<Vec<_>>();
 
-        // Deal 7 cards to each player
-        for _ in 0..7 {
+        // Deal the starting hand to each player
+        for _ in 0..config.starting_hand_size {
             for player in players.iter_mut() {
                 if let Some(card) = deck.pop() {
                     player.add_card(card);
@@ -123,133 +602,1038 @@ impl UnoGame {
             current_turn: 0,
             direction: Direction::Clockwise,
             pending_draws: 0,
+            pending_skips: 0,
             status: GameStatus::InProgress,
+            config,
+            version: 0,
+            has_drawn_this_turn: false,
+            drawn_card_index: None,
+            challengeable_draw_four: None,
+            seed,
+            on_event: EventHandler::default(),
         })
     }
 
-    pub fn initialize_deck() -> Vec<Card> {
+    /// Builds a game from exact parts instead of shuffling and dealing,
+    /// e.g. to set up a known position. The parts are checked with `validate_state`.
+    pub fn from_parts(
+        players: Vec<Player>,
+        deck: Vec<Card>,
+        discard_pile: Vec<(Card, usize)>,
+        current_turn: usize,
+        direction: Direction,
+    ) -> Result<Self, GameError> {
+        let game = Self {
+            players,
+            deck,
+            discard_pile,
+            current_turn,
+            direction,
+            pending_draws: 0,
+            pending_skips: 0,
+            status: GameStatus::InProgress,
+            config: GameConfig::default(),
+            version: 0,
+            has_drawn_this_turn: false,
+            drawn_card_index: None,
+            challengeable_draw_four: None,
+            seed: None,
+            on_event: EventHandler::default(),
+        };
+        game.validate_state()?;
+        Ok(game)
+    }
+
+    /// Checks that there are between 2 and 10 players, each with a non-empty name of at most
+    /// `MAX_NAME_LEN` characters, and that no two names are the same (ignoring case).
+    /// Names are compared after trimming surrounding whitespace.
+    pub fn validate_player_names(player_names: &[String]) -> Result<(), GameError> {
+        if player_names.len() < UnoGame::MIN_PLAYERS || player_names.len() > UnoGame::MAX_PLAYERS {
+            return Err(GameError::Other(format!(
+                "A game needs between {} and {} players, got {}",
+                UnoGame::MIN_PLAYERS,
+                UnoGame::MAX_PLAYERS,
+                player_names.len()
+            )));
+        }
+        UnoGame::check_names(player_names)
+    }
+
+    /// Checks each name is non-empty and short enough, and that no two are the same.
+    fn check_names(player_names: &[String]) -> Result<(), GameError> {
+        let mut seen = std::collections::HashSet::new();
+        for name in player_names {
+            if name.trim().is_empty() {
+                return Err(GameError::Other(
+                    "Player names must not be empty".to_string(),
+                ));
+            }
+            if name.trim().chars().count() > UnoGame::MAX_NAME_LEN {
+                return Err(GameError::Other(format!(
+                    "Player names must be at most {} characters",
+                    UnoGame::MAX_NAME_LEN
+                )));
+            }
+            if !seen.insert(name.trim().to_lowercase()) {
+                return Err(GameError::Other(format!(
+                    "Duplicate player name: {}",
+                    name.trim()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks every invariant of a dealt game, run on games loaded from disk or imported:
+    /// everything `validate_state` and `validate_turn` check, plus that no card has been
+    /// lost or duplicated (hands, deck and discard pile add up to the full deck for the
+    /// config) and that `pending_draws` is no more than a Wild Draw Four can give.
+    ///
+    /// Stricter than `validate_state`, so hand-built positions with trimmed decks fail it.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_state().map_err(|e| e.to_string())?;
+        self.validate_turn().map_err(|e| e.to_string())?;
+        if self.status == GameStatus::Lobby {
+            return Ok(());
+        }
+
+        let expected = UnoGame::unshuffled_deck(&self.config).len();
+        let in_hands: usize = self.players.iter().map(|p| p.hand.len()).sum();
+        let total = in_hands + self.deck.len() + self.discard_pile.len();
+        if total != expected {
+            return Err(format!(
+                "Expected {} cards in the game but found {} ({} in hands, {} in the deck, {} discarded)",
+                expected,
+                total,
+                in_hands,
+                self.deck.len(),
+                self.discard_pile.len()
+            ));
+        }
+        if self.pending_draws > 4 {
+            return Err(format!(
+                "{} pending draws is more than any card gives",
+                self.pending_draws
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that a game loaded from outside (e.g. an imported file) is internally consistent:
+    /// valid player names, player ids matching their seats, every index in range, and a
+    /// non-empty discard pile.
+    pub fn validate_state(&self) -> Result<(), GameError> {
+        let names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
+        if self.status == GameStatus::Lobby {
+            // Nothing is dealt yet, and the player count is only checked at the start
+            return UnoGame::check_names(&names);
+        }
+        UnoGame::validate_player_names(&names)?;
+
+        let num_players = self.players.len();
+        let invalid = |msg: String| Err(GameError::Other(msg));
+        if let Some((seat, player)) = self
+            .players
+            .iter()
+            .enumerate()
+            .find(|(seat, p)| p.id != *seat)
+        {
+            return invalid(format!(
+                "Player {} has id {} but sits at position {}",
+                player.name, player.id, seat
+            ));
+        }
+        if self.current_turn >= num_players {
+            return invalid(format!(
+                "Current turn {} is out of range for {} players",
+                self.current_turn, num_players
+            ));
+        }
+        if self.discard_pile.is_empty() {
+            return invalid("Discard pile must not be empty".to_string());
+        }
+        if let Some((_, player_id)) = self
+            .discard_pile
+            .iter()
+            .find(|(_, id)| *id != usize::MAX && *id >= num_players)
+        {
+            return invalid(format!(
+                "Discard pile refers to unknown player {}",
+                player_id
+            ));
+        }
+        match self.status {
+            GameStatus::Complete { winner_id } | GameStatus::Stalemate { winner_id }
+                if winner_id >= num_players =>
+            {
+                return invalid(format!("Winner {} is not a player", winner_id));
+            }
+            GameStatus::InProgress if !self.players[self.current_turn].active => {
+                return invalid("The current player has left the game".to_string());
+            }
+            _ => {}
+        }
+        let hands = self.players.iter().flat_map(|p| &p.hand);
+        let discards = self.discard_pile.iter().map(|(card, _)| card);
+        for card in self.deck.iter().chain(hands).chain(discards) {
+            card.check()?;
+        }
+        if let Some(play) = &self.challengeable_draw_four {
+            if play.player_id >= num_players {
+                return invalid(format!(
+                    "Wild Draw Four was played by unknown player {}",
+                    play.player_id
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the game in progress can carry on from the current turn: the current
+    /// player hasn't already gone out, and any pending draws or skips can be taken. A
+    /// game that fails would otherwise go wrong on the next move rather than when loaded.
+    pub fn validate_turn(&self) -> Result<(), GameError> {
+        if self.status != GameStatus::InProgress {
+            return Ok(());
+        }
+        let invalid = |msg: String| Err(GameError::Other(msg));
+        let player = &self.players[self.current_turn];
+        if player.hand.is_empty() {
+            return invalid(format!(
+                "It is {}'s turn, but they have no cards left and should have won",
+                player.name
+            ));
+        }
+        // Only the cards below the top of the discard pile can be shuffled back in
+        let drawable = self.deck.len() + self.discard_pile.len().saturating_sub(1);
+        if self.pending_draws > 0 && drawable == 0 {
+            return invalid(format!(
+                "{} pending draws, but the deck and discard pile have no cards to draw",
+                self.pending_draws
+            ));
+        }
+        if self.pending_skips > 0 && !self.config.skip_stacking {
+            return invalid(format!(
+                "{} pending skips, but skip_stacking is off",
+                self.pending_skips
+            ));
+        }
+        if self.pending_draws > 0 && self.pending_skips > 0 {
+            return invalid("Draws and skips can't both be pending".to_string());
+        }
+        Ok(())
+    }
+
+    /// Counts the cards left in the deck by color and card type, e.g. for card-counting
+    /// bots or showing what is left. Wilds in the deck are uncolored, so they are
+    /// counted under `Color::Wild`.
+    pub fn deck_composition(&self) -> HashMap<(Color, CardType), usize> {
+        let mut counts = HashMap::new();
+        for card in &self.deck {
+            *counts
+                .entry((card.color, card.card_type.clone()))
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The card the next draw will take, without taking it. `None` when the deck is
+    /// empty, even if the discard pile could be reshuffled into it.
+    pub fn peek_top_deck(&self) -> Option<&Card> {
+        self.deck.last()
+    }
+
+    /// Changes a player's name, applying the same rules as when the game was created.
+    pub fn rename_player(&mut self, player_id: usize, name: &str) -> Result<(), GameError> {
+        if player_id >= self.players.len() {
+            return Err(GameError::Other(format!(
+                "Invalid player id: {}",
+                player_id
+            )));
+        }
+        let mut names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
+        names[player_id] = name.to_string();
+        UnoGame::validate_player_names(&names)?;
+
+        self.players[player_id].name = name.trim().to_string();
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Sorts a player's hand with `Player::sort_hand`. Card indices change, so this
+    /// counts as a new version and stale `If-Match` moves are refused.
+    pub fn sort_hand(&mut self, player_id: usize) -> Result<(), GameError> {
+        let player = self
+            .players
+            .get_mut(player_id)
+            .ok_or_else(|| GameError::Other(format!("Invalid player id: {}", player_id)))?;
+        let drawn_card = match self.drawn_card_index {
+            Some(index) if player_id == self.current_turn => player.hand.get(index).cloned(),
+            _ => None,
+        };
+        player.sort_hand();
+        // The drawn card stays the one that may be played; copies of it are interchangeable
+        if let Some(card) = drawn_card {
+            self.drawn_card_index = player.hand.iter().position(|c| *c == card);
+        }
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Seats the players in a new order: `order[seat]` is the id of the player who moves
+    /// to `seat`. Players keep their hands and stats but take their new seat as their id,
+    /// and every reference to a player (the turn, the discard pile, a challengeable Wild
+    /// Draw Four and the winner) follows them. `order` must list every id exactly once.
+    pub fn reseat(&mut self, order: Vec<usize>) -> Result<(), GameError> {
+        let num_players = self.players.len();
+        let mut new_ids = vec![usize::MAX; num_players];
+        let is_order = order.len() == num_players
+            && order.iter().enumerate().all(|(seat, &id)| {
+                id < num_players && std::mem::replace(&mut new_ids[id], seat) == usize::MAX
+            });
+        if !is_order {
+            return Err(GameError::Other(format!(
+                "Seat order {:?} is not an order of the {} players",
+                order, num_players
+            )));
+        }
+
+        let mut seats: Vec<Option<Player>> = self.players.drain(..).map(Some).collect();
+        self.players = order
+            .iter()
+            .enumerate()
+            .map(|(seat, &id)| {
+                let mut player = seats[id].take().expect("each id is seated once");
+                player.id = seat;
+                player
+            })
+            .collect();
+        let remap = |id: usize| new_ids.get(id).copied().unwrap_or(id);
+        self.current_turn = remap(self.current_turn);
+        for (_, player_id) in &mut self.discard_pile {
+            *player_id = remap(*player_id);
+        }
+        if let Some(play) = &mut self.challengeable_draw_four {
+            play.player_id = remap(play.player_id);
+        }
+        match &mut self.status {
+            GameStatus::Complete { winner_id } | GameStatus::Stalemate { winner_id } => {
+                *winner_id = remap(*winner_id);
+            }
+            GameStatus::Lobby | GameStatus::InProgress => {}
+        }
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Builds and shuffles `config.deck_copies` decks.
+    pub fn initialize_deck(config: &GameConfig) -> Vec<Card> {
+        let mut deck = UnoGame::unshuffled_deck(config);
+        RandomShuffler.shuffle(&mut deck);
+        deck
+    }
+
+    pub(crate) fn unshuffled_deck(config: &GameConfig) -> Vec<Card> {
+        (0..config.deck_copies)
+            .flat_map(|_| UnoGame::single_deck(config))
+            .collect()
+    }
+
+    /// Builds one unshuffled deck: the 100 colored cards plus the configured numbers of
+    /// Wild and Wild Draw Four cards, 108 cards with the default config.
+    fn single_deck(config: &GameConfig) -> Vec<Card> {
         let mut deck = Vec::new();
 
         // Create standard cards
-        for &color in &[Color::Red, Color::Green, Color::Blue, Color::Yellow] {
+        for color in Color::playable() {
+            let number = |n| Card::number(color, n).expect("0 to 9 are card numbers");
+
             // Add one copy of the 0 card
-            deck.push(Card::new(color.clone(), CardType::Number(0)));
+            deck.push(number(0));
 
             // Add two copies of each numbered card (1–9)
-            for number in 1..=9 {
-                deck.push(Card::new(color.clone(), CardType::Number(number)));
-                deck.push(Card::new(color.clone(), CardType::Number(number)));
+            for n in 1..=9 {
+                deck.push(number(n));
+                deck.push(number(n));
             }
 
             // Add Skip, Reverse, and Draw Two (two copies each)
             for _ in 0..2 {
-                deck.push(Card::new(color.clone(), CardType::Skip));
-                deck.push(Card::new(color.clone(), CardType::Reverse));
-                deck.push(Card::new(color.clone(), CardType::DrawTwo));
+                for action in CardType::actions() {
+                    deck.push(Card::new(color, action));
+                }
             }
         }
 
         // Add Wild and Wild Draw Four cards
-        for _ in 0..4 {
+        for _ in 0..config.wild_count {
             deck.push(Card::new(Color::Wild, CardType::Wild));
+        }
+        for _ in 0..config.wild_draw_four_count {
             deck.push(Card::new(Color::Wild, CardType::WildDrawFour));
         }
 
-        // Shuffle the deck
-        let mut rng = rand::rng();
-        deck.shuffle(&mut rng);
-
         deck
     }
 
-    /// Updates the current turn based on the direction of play.
+    /// Updates the current turn based on the direction of play,
+    /// skipping players who are no longer active.
     pub fn next_turn(&mut self) {
+        self.has_drawn_this_turn = false;
+        self.drawn_card_index = None;
+        self.challengeable_draw_four = None;
+        self.current_turn = self.seat_after(self.current_turn);
+        // Players who lost a Skip chain sit out the turns they still owe
+        while self.players[self.current_turn].turns_to_miss > 0 {
+            self.players[self.current_turn].turns_to_miss -= 1;
+            self.current_turn = self.seat_after(self.current_turn);
+        }
+    }
+
+    /// Returns who `next_turn` would move play to, without changing anything.
+    pub fn peek_next_player(&self) -> usize {
+        // The same walk as `next_turn`, on a copy of the turns each player still owes
+        let mut turns_to_miss: Vec<usize> = self.players.iter().map(|p| p.turns_to_miss).collect();
+        let mut seat = self.seat_after(self.current_turn);
+        while turns_to_miss[seat] > 0 {
+            turns_to_miss[seat] -= 1;
+            seat = self.seat_after(seat);
+        }
+        seat
+    }
+
+    /// Returns who would play next if the next player were skipped.
+    pub fn peek_player_after_skip(&self) -> usize {
+        self.seat_after(self.seat_after(self.current_turn))
+    }
+
+    /// The next active player after `seat` in the direction of play.
+    fn seat_after(&self, seat: usize) -> usize {
         let num_players = self.players.len();
-        match self.direction {
-            Direction::Clockwise => {
-                self.current_turn = (self.current_turn + 1) % num_players;
-            }
-            Direction::CounterClockwise => {
-                self.current_turn = (self.current_turn + num_players - 1) % num_players;
+        let mut seat = seat;
+        for _ in 0..num_players {
+            seat = match self.direction {
+                Direction::Clockwise => (seat + 1) % num_players,
+                Direction::CounterClockwise => (seat + num_players - 1) % num_players,
+            };
+            if self.players[seat].active {
+                break;
             }
         }
+        seat
     }
 
-    /// Reverses the direction of play.
-    pub fn reverse_direction(&mut self) {
-        self.direction = self.direction.reverse();
+    /// Renders a plain-text summary of the table: direction, top card, deck size,
+    /// pending draws and each player's hand size, with `>` marking whose turn it is.
+    pub fn render_board(&self) -> String {
+        let arrow = match self.direction {
+            Direction::Clockwise => "->",
+            Direction::CounterClockwise => "<-",
+        };
+        let mut board = format!("Direction: {:?} {}\n", self.direction, arrow);
+        if let Some((top_card, _)) = self.discard_pile.last() {
+            board.push_str(&format!("Top card: {}\n", top_card));
+        }
+        board.push_str(&format!("Deck: {} cards\n", self.deck.len()));
+        board.push_str(&format!("Pending draws: {}\n", self.pending_draws));
+        if self.pending_skips > 0 {
+            board.push_str(&format!("Pending skips: {}\n", self.pending_skips));
+        }
+        match self.status {
+            GameStatus::Lobby => board.push_str("Waiting for players\n"),
+            GameStatus::InProgress => {}
+            GameStatus::Complete { winner_id } => {
+                board.push_str(&format!("Winner: {}\n", self.players[winner_id].name));
+            }
+            GameStatus::Stalemate { winner_id } => {
+                board.push_str(&format!(
+                    "Stalemate, lowest hand: {}\n",
+                    self.players[winner_id].name
+                ));
+            }
+        }
+        board.push_str("Players:\n");
+        for player in &self.players {
+            let marker = if player.id == self.current_turn && self.status == GameStatus::InProgress
+            {
+                ">"
+            } else {
+                " "
+            };
+            let cards = if player.active {
+                format!("{} cards", player.hand.len())
+            } else {
+                "left".to_string()
+            };
+            board.push_str(&format!("{} {} ({})\n", marker, player.name, cards));
+        }
+        board
     }
 
-    /// Checks if a card can be played on top of another card.
-    pub fn can_play_card(card: &Card, top_card: &Card) -> bool {
-        // Wild cards can always be played
-        if card.color == Color::Wild
-            || matches!(card.card_type, CardType::Wild | CardType::WildDrawFour)
-        {
-            return true;
+    /// Describes an event in one sentence, naming the players, e.g.
+    /// "Alice played Blue Skip, skipping Bob." Who a Skip skipped is worked out from the
+    /// current seating, so describe a move straight after it is made.
+    pub fn describe_event(&self, event: &GameEvent) -> String {
+        let name = |id: usize| {
+            self.players
+                .get(id)
+                .map_or("Unknown player", |p| p.name.as_str())
+        };
+        let list = |cards: &[Card]| {
+            cards
+                .iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let count = |cards: &[Card]| match cards.len() {
+            1 => "1 card".to_string(),
+            n => format!("{} cards", n),
+        };
+        match event {
+            GameEvent::CardPlayed {
+                player_id,
+                player_name,
+                card,
+            } => match card.card_type {
+                CardType::Skip if *player_id < self.players.len() => format!(
+                    "{} played {}, skipping {}.",
+                    player_name,
+                    card,
+                    name(self.seat_after(*player_id))
+                ),
+                _ => format!("{} played {}.", player_name, card),
+            },
+            GameEvent::CardDrawn { player_id, card } => {
+                format!("{} drew {}.", name(*player_id), card)
+            }
+            GameEvent::CardDrawnPlayable {
+                player_id,
+                card,
+                index,
+            } => format!(
+                "{} drew {}, which can be played now (card {}).",
+                name(*player_id),
+                card,
+                index
+            ),
+            GameEvent::CardsDrawnUntilPlayable { player_id, cards } => format!(
+                "{} drew until they could play: {}.",
+                name(*player_id),
+                list(cards)
+            ),
+            GameEvent::Skip { player_id } => format!("{} is skipped.", name(*player_id)),
+            GameEvent::Reverse => "Direction reversed.".to_string(),
+            GameEvent::DrawTwo { player_id, cards } => format!(
+                "{} draws {}: {}.",
+                name(*player_id),
+                count(cards),
+                list(cards)
+            ),
+            GameEvent::WildColorChosen { player_id, color } => {
+                format!("{} chose {}.", name(*player_id), color)
+            }
+            GameEvent::WildDrawFour {
+                player_id,
+                next_player_id,
+                cards,
+                color,
+            } => format!(
+                "{} played Wild Draw Four and chose {}; {} draws {}: {}.",
+                name(*player_id),
+                color,
+                name(*next_player_id),
+                count(cards),
+                list(cards)
+            ),
+            GameEvent::PlayerWins { player_id } => {
+                format!("{} has won the game!", name(*player_id))
+            }
+            GameEvent::PlayerForfeited { player_id } => {
+                format!("{} has left the game.", name(*player_id))
+            }
+            GameEvent::PlayerKicked { player_id } => {
+                format!("{} was removed from the game.", name(*player_id))
+            }
+            GameEvent::HandSwap {
+                player_id,
+                target_player_id,
+            } => format!(
+                "{} swapped hands with {}.",
+                name(*player_id),
+                name(*target_player_id)
+            ),
+            GameEvent::HandRotate { direction } => format!("All hands passed {:?}.", direction),
+            GameEvent::Stalemate { winner_id } => format!(
+                "No one can move. {} wins with the lowest hand!",
+                name(*winner_id)
+            ),
+            GameEvent::TurnPassed { player_id } => format!("{} passed.", name(*player_id)),
+            GameEvent::HandFull { player_id } => format!(
+                "{} can't hold any more cards, so their turn passes.",
+                name(*player_id)
+            ),
+            GameEvent::ChallengeResult {
+                success,
+                drawer_id,
+                cards,
+            } => format!(
+                "Challenge {}! {} draws {}.",
+                if *success { "succeeded" } else { "failed" },
+                name(*drawer_id),
+                count(cards)
+            ),
+            GameEvent::TurnsSkipped { player_id, turns } => match turns {
+                1 => format!("{} is skipped.", name(*player_id)),
+                n => format!(
+                    "{} is skipped and misses their next {} turns.",
+                    name(*player_id),
+                    n - 1
+                ),
+            },
+            GameEvent::DeckReshuffled { cards_recycled } => format!(
+                "The deck ran out; {} from the discard pile shuffled back in.",
+                match cards_recycled {
+                    1 => "1 card".to_string(),
+                    n => format!("{} cards", n),
+                }
+            ),
         }
+    }
 
-        // If the top card is wild, any card can be played on it
-        if top_card.color == Color::Wild {
-            return true;
+    /// Ends the player's turn without playing a card.
+    /// Only allowed once they have drawn this turn.
+    pub fn pass_turn(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
+        if player_id != self.current_turn || !self.has_drawn_this_turn {
+            return Err(GameError::InvalidMove);
         }
 
-        // Same color or same number
-        card.color == top_card.color
-            || match card.card_type {
-                CardType::Number(n) => match top_card.card_type {
-                    CardType::Number(m) => n == m,
-                    _ => false,
-                },
-                // Action cards (Skip, Reverse, DrawTwo) can only be played on matching colors
-                _ => false,
-            }
+        self.next_turn();
+        self.version += 1;
+        Ok(GameEvent::TurnPassed { player_id })
     }
 
-    /// Handles playing a card.
-    pub fn play_card(&mut self, player_id: usize, card_index: usize) -> Result<GameEvent, String> {
-        if matches!(self.status, GameStatus::Complete { .. }) {
-            return Err("Game is already over".to_string());
+    /// Moves play on from a player who ran out of time. They draw as if they had chosen to,
+    /// and if that leaves the turn with them they pass without playing the card.
+    pub fn time_out_turn(&mut self) -> Result<Vec<GameEvent>, GameError> {
+        let player_id = self.current_turn;
+        if self.has_drawn_this_turn {
+            return Ok(vec![self.pass_turn(player_id)?]);
         }
 
-        if player_id != self.current_turn {
-            return Err("Not your turn".to_string());
+        // A timed-out player draws even if `must_play_if_able` would stop them
+        let mut events = vec![self.apply_draw(player_id)?];
+        self.version += 1;
+        if !self.status.is_over() && self.current_turn == player_id && self.has_drawn_this_turn {
+            events.push(self.pass_turn(player_id)?);
         }
+        Ok(events)
+    }
 
-        let player = &mut self.players[player_id];
-        if card_index >= player.hand.len() {
-            return Err("Invalid card index".to_string());
+    /// Challenges the Wild Draw Four just played against `challenger_id`.
+    /// If its player held a card of the previous color, they draw the four cards instead
+    /// and the challenger takes their turn as normal. Otherwise the challenger draws six
+    /// and loses their turn.
+    pub fn challenge_wild_draw_four(
+        &mut self,
+        challenger_id: usize,
+    ) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
+        if challenger_id != self.current_turn {
+            return Err(GameError::InvalidMove);
         }
+        let play = self
+            .challengeable_draw_four
+            .take()
+            .ok_or(GameError::InvalidMove)?;
 
-        // Get the top card of the discard pile
-        let top_card = &self.discard_pile.last().unwrap().0;
+        let (success, drawer_id, count) = if play.had_matching_color {
+            (true, play.player_id, 4)
+        } else {
+            (false, challenger_id, 6)
+        };
+        let mut cards = Vec::new();
+        for _ in 0..count {
+            let card = self.take_from_deck().ok_or(GameError::EmptyDeck)?;
+            self.players[drawer_id].hand.push(card.clone());
+            self.players[drawer_id].stats.cards_drawn += 1;
+            cards.push(card);
+        }
+        self.pending_draws = 0;
+        if !success {
+            self.next_turn();
+        }
+        self.version += 1;
 
-        // Get the card to be played
-        let card_to_play = &player.hand[card_index];
+        Ok(GameEvent::ChallengeResult {
+            success,
+            drawer_id,
+            cards,
+        })
+    }
 
-        // Validate the play
-        if !UnoGame::can_play_card(card_to_play, top_card) {
-            return Err("Card must match color or number of top card".to_string());
-        }
+    /// Removes a player from the game.
+    /// Their cards go to the bottom of the deck and they no longer take turns.
+    /// If only one active player remains, that player wins.
+    pub fn forfeit(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        let event = self.apply_forfeit(player_id)?;
+        self.version += 1;
+        Ok(event)
+    }
 
-        let card = player.hand.remove(card_index);
-        let card_type = card.card_type.clone();
-        let player_name = player.name.clone();
-        let is_hand_empty = player.hand.is_empty();
+    /// Removes a player on a moderator's say-so, e.g. for stalling. It has the same effect
+    /// as `forfeit`, but is reported as `PlayerKicked` unless it decides the game.
+    pub fn kick(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        let event = match self.apply_forfeit(player_id)? {
+            GameEvent::PlayerForfeited { player_id } => GameEvent::PlayerKicked { player_id },
+            event => event,
+        };
+        self.version += 1;
+        Ok(event)
+    }
 
-        // Add card to discard pile
-        self.discard_pile.push((card.clone(), player_id));
+    fn apply_forfeit(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
 
-        // Check if player has won
-        if is_hand_empty {
-            self.status = GameStatus::Complete {
-                winner_id: player_id,
+        let player = self
+            .players
+            .get_mut(player_id)
+            .ok_or_else(|| GameError::Other("Invalid player id".to_string()))?;
+        if !player.active {
+            return Err(GameError::InvalidMove);
+        }
+
+        player.active = false;
+        let hand: Vec<Card> = player.hand.drain(..).collect();
+        // The deck is drawn from the end, so the front is the bottom
+        self.deck.splice(0..0, hand);
+
+        if let Some(winner_id) = self.settle_if_decided() {
+            return Ok(GameEvent::PlayerWins {
+                player_id: winner_id,
+            });
+        }
+
+        if self.current_turn == player_id {
+            // Any penalty was aimed at the player who left
+            self.pending_draws = 0;
+            self.pending_skips = 0;
+            self.next_turn();
+        }
+
+        Ok(GameEvent::PlayerForfeited { player_id })
+    }
+
+    /// Returns a player's hand to the bottom of the deck, shuffles the deck and deals them a
+    /// fresh hand of `starting_hand_size` cards. A house rule for hopeless hands; it is
+    /// easy to abuse, so the server only allows it when started with `--allow-redeal`.
+    /// The discard pile is reshuffled in if the deck runs short.
+    pub fn redeal_player(&mut self, player_id: usize) -> Result<(), GameError> {
+        self.check_in_progress()?;
+        let player = self
+            .players
+            .get(player_id)
+            .ok_or_else(|| GameError::Other(format!("Invalid player id: {}", player_id)))?;
+        if !player.active {
+            return Err(GameError::InvalidMove);
+        }
+
+        let hand_size = self.config.starting_hand_size;
+        let available =
+            self.deck.len() + player.hand.len() + self.discard_pile.len().saturating_sub(1);
+        if available < hand_size {
+            return Err(GameError::EmptyDeck);
+        }
+
+        let hand = std::mem::take(&mut self.players[player_id].hand);
+        // The deck is drawn from the end, so the front is the bottom
+        self.deck.splice(0..0, hand);
+        if self.deck.len() < hand_size {
+            self.reshuffle_discard_pile();
+        }
+        let mut deck = std::mem::take(&mut self.deck);
+        self.shuffle(&mut deck);
+        let split = deck.len() - hand_size;
+        self.players[player_id].hand = deck.split_off(split);
+        self.deck = deck;
+        if player_id == self.current_turn {
+            // The card they drew went back with the rest of the hand
+            self.drawn_card_index = None;
+        }
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Returns the winner once the game is decided: it has already ended, an active player
+    /// has emptied their hand, or everyone else has left. Returns `None` while play goes on,
+    /// and in the lobby.
+    pub fn is_decided(&self) -> Option<usize> {
+        match self.status {
+            GameStatus::Complete { winner_id } | GameStatus::Stalemate { winner_id } => {
+                return Some(winner_id)
+            }
+            GameStatus::Lobby => return None,
+            GameStatus::InProgress => {}
+        }
+        if let Some(player) = self.players.iter().find(|p| p.active && p.hand.is_empty()) {
+            return Some(player.id);
+        }
+        let mut active = self.players.iter().filter(|p| p.active);
+        match (active.next(), active.next()) {
+            (Some(player), None) => Some(player.id),
+            _ => None,
+        }
+    }
+
+    /// Completes a game in progress that `is_decided` has a winner for, crediting them
+    /// with the round. Returns the winner if the game was ended.
+    fn settle_if_decided(&mut self) -> Option<usize> {
+        if self.status != GameStatus::InProgress {
+            return None;
+        }
+        let winner_id = self.is_decided()?;
+        self.players[winner_id].stats.rounds_won += 1;
+        self.status = GameStatus::Complete { winner_id };
+        Some(winner_id)
+    }
+
+    /// Fails unless the game is in progress, i.e. dealt and not yet over.
+    fn check_in_progress(&self) -> Result<(), GameError> {
+        match self.status {
+            GameStatus::InProgress => Ok(()),
+            GameStatus::Lobby => Err(GameError::NotStarted),
+            GameStatus::Complete { .. } | GameStatus::Stalemate { .. } => {
+                Err(GameError::GameAlreadyOver)
+            }
+        }
+    }
+
+    /// Reverses the direction of play.
+    pub fn reverse_direction(&mut self) {
+        self.direction = self.direction.reverse();
+    }
+
+    /// Checks if a card can be played on top of another card. Kept for existing callers;
+    /// see `Card::can_play_on`.
+    pub fn can_play_card(card: &Card, top_card: &Card) -> bool {
+        card.can_play_on(top_card)
+    }
+
+    /// Like `can_play_card`, but says why a card can't be played, for showing to players.
+    pub fn explain_playability(card: &Card, top_card: &Card) -> Result<(), String> {
+        if UnoGame::can_play_card(card, top_card) {
+            return Ok(());
+        }
+        let reason = match (&card.card_type, &top_card.card_type) {
+            (CardType::Number(_), CardType::Number(_)) => "colors and numbers differ",
+            (CardType::Number(_), _) => "colors differ",
+            _ => "an action card must match the color",
+        };
+        Err(format!("Cannot play {} on {}: {}", card, top_card, reason))
+    }
+
+    /// Passes every active player's hand to the next active player in the direction of play.
+    fn rotate_hands(&mut self) {
+        let mut seats: Vec<usize> = self
+            .players
+            .iter()
+            .filter(|p| p.active)
+            .map(|p| p.id)
+            .collect();
+        if self.direction == Direction::CounterClockwise {
+            seats.reverse();
+        }
+
+        let hands: Vec<Vec<Card>> = seats
+            .iter()
+            .map(|&id| std::mem::take(&mut self.players[id].hand))
+            .collect();
+        for (i, hand) in hands.into_iter().enumerate() {
+            let receiver = seats[(i + 1) % seats.len()];
+            self.players[receiver].hand = hand;
+        }
+    }
+
+    /// Returns the indices of the cards in the current player's hand that can
+    /// legally be played on the top of the discard pile.
+    /// Returns an empty list while a draw penalty is pending, since the penalty
+    /// must be taken before any card can be played, and only Skips, of any color, while
+    /// Skips are stacked.
+    pub fn playable_indices(&self) -> Vec<usize> {
+        if self.pending_draws > 0 {
+            return Vec::new();
+        }
+
+        let top_card = match self.discard_pile.last() {
+            Some((card, _)) => card,
+            None => return Vec::new(),
+        };
+
+        self.players[self.current_turn]
+            .hand
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.drawn_card_index.is_none_or(|drawn| drawn == *index))
+            .filter(|(_, card)| match self.pending_skips {
+                0 => UnoGame::can_play_card(card, top_card),
+                // Any Skip passes a chain on, whatever its color
+                _ => card.card_type == CardType::Skip,
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Handles playing a card.
+    pub fn play_card(
+        &mut self,
+        player_id: usize,
+        card_index: usize,
+    ) -> Result<GameEvent, GameError> {
+        self.play_card_with_target(player_id, card_index, None)
+    }
+
+    /// Handles playing a card that may need a target player.
+    /// Under the Seven-Zero rule a 7 requires `target_player_id`, the player to swap hands with.
+    pub fn play_card_with_target(
+        &mut self,
+        player_id: usize,
+        card_index: usize,
+        target_player_id: Option<usize>,
+    ) -> Result<GameEvent, GameError> {
+        let event = self.apply_play(player_id, card_index, target_player_id)?;
+        self.version += 1;
+        self.emit(&event);
+        Ok(event)
+    }
+
+    /// Calls `handler` with the event of every successful `play_card` and `draw_card`
+    /// from now on, replacing any handler set before. For bots, loggers and the like
+    /// that want to follow a game without polling it.
+    pub fn set_event_handler(&mut self, handler: impl FnMut(&GameEvent) + Send + 'static) {
+        self.on_event = EventHandler(Some(Box::new(handler)));
+    }
+
+    fn emit(&mut self, event: &GameEvent) {
+        if let Some(handler) = self.on_event.0.as_mut() {
+            handler(event);
+        }
+    }
+
+    fn apply_play(
+        &mut self,
+        player_id: usize,
+        card_index: usize,
+        target_player_id: Option<usize>,
+    ) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
+
+        if player_id != self.current_turn {
+            return Err(GameError::NotYourTurn);
+        }
+
+        let player = &mut self.players[player_id];
+        if card_index >= player.hand.len() {
+            return Err(GameError::CardNotInHand);
+        }
+        if self
+            .drawn_card_index
+            .is_some_and(|drawn| drawn != card_index)
+        {
+            return Err(GameError::UnplayableCard(
+                "Only the card you just drew can be played".to_string(),
+            ));
+        }
+
+        // Get the top card of the discard pile
+        let Some((top_card, _)) = self.discard_pile.last() else {
+            return Err(GameError::EmptyDiscardPile);
+        };
+
+        // Get the card to be played
+        let card_to_play = &player.hand[card_index];
+
+        // Validate the play
+        if self.pending_draws > 0 {
+            return Err(GameError::UnplayableCard(format!(
+                "You must take the {} pending cards first",
+                self.pending_draws
+            )));
+        }
+        if self.pending_skips > 0 {
+            if card_to_play.card_type != CardType::Skip {
+                return Err(GameError::UnplayableCard(format!(
+                    "You must play a Skip or take the {} pending skips",
+                    self.pending_skips
+                )));
+            }
+        } else {
+            UnoGame::explain_playability(card_to_play, top_card)
+                .map_err(GameError::UnplayableCard)?;
+        }
+
+        let swap_target =
+            if self.config.seven_zero_enabled && card_to_play.card_type == CardType::Number(7) {
+                let target = target_player_id.ok_or_else(|| {
+                    GameError::Other("Target player required when playing a 7".to_string())
+                })?;
+                if target == player_id || !self.players.get(target).is_some_and(|p| p.active) {
+                    return Err(GameError::Other("Invalid target player".to_string()));
+                }
+                Some(target)
+            } else {
+                None
             };
-            return Ok(GameEvent::PlayerWins { player_id });
+
+        let previous_color = top_card.color;
+        let player = &mut self.players[player_id];
+        let card = player.hand.remove(card_index);
+        player.stats.record_play(&card);
+        let card_type = card.card_type.clone();
+        let player_name = player.name.clone();
+
+        // Add card to discard pile
+        self.discard_pile.push((card.clone(), player_id));
+
+        // Check if player has won
+        if let Some(winner_id) = self.settle_if_decided() {
+            return Ok(GameEvent::PlayerWins {
+                player_id: winner_id,
+            });
+        }
+
+        if let Some(target_player_id) = swap_target {
+            let hand = std::mem::take(&mut self.players[player_id].hand);
+            self.players[player_id].hand =
+                std::mem::replace(&mut self.players[target_player_id].hand, hand);
+            self.next_turn();
+            return Ok(GameEvent::HandSwap {
+                player_id,
+                target_player_id,
+            });
+        }
+
+        if self.config.seven_zero_enabled && card_type == CardType::Number(0) {
+            self.rotate_hands();
+            self.next_turn();
+            return Ok(GameEvent::HandRotate {
+                direction: self.direction,
+            });
         }
 
         // Handle special card effects
         match card_type {
+            CardType::Skip if self.config.skip_stacking => {
+                // The next player may answer with a Skip of their own
+                self.pending_skips += 1;
+                self.next_turn();
+                Ok(GameEvent::CardPlayed {
+                    player_id,
+                    player_name,
+                    card,
+                })
+            }
             CardType::Skip => {
                 // Skip the next player
                 self.next_turn();
@@ -284,10 +1668,21 @@ impl UnoGame {
                 })
             }
             CardType::WildDrawFour => {
+                // Only legal if the player had no card of the color in play
+                let had_matching_color = previous_color != Color::Wild
+                    && self.players[player_id]
+                        .hand
+                        .iter()
+                        .any(|c| c.color == previous_color);
                 // Set pending draws first
                 self.pending_draws = 4;
                 // Then move to the next player who must draw
                 self.next_turn();
+                self.challengeable_draw_four = Some(WildDrawFourPlay {
+                    player_id,
+                    previous_color,
+                    had_matching_color,
+                });
                 Ok(GameEvent::CardPlayed {
                     player_id,
                     player_name,
@@ -306,47 +1701,339 @@ impl UnoGame {
         }
     }
 
+    /// Shuffles all but the top card of the discard pile back into the deck.
+    /// Wild cards have their chosen color cleared. Returns the number of cards recycled.
+    pub fn reshuffle_discard_pile(&mut self) -> usize {
+        if self.discard_pile.len() <= 1 {
+            return 0;
+        }
+
+        let top = self.discard_pile.pop().unwrap();
+        let mut recycled: Vec<Card> = self
+            .discard_pile
+            .drain(..)
+            .map(|(mut card, _)| {
+                card.clear_chosen_color();
+                card
+            })
+            .collect();
+        self.discard_pile.push(top);
+
+        let count = recycled.len();
+        self.shuffle(&mut recycled);
+        recycled.append(&mut self.deck);
+        self.deck = recycled;
+        count
+    }
+
+    /// Shuffles `cards` mid-game, reproducibly if the game is seeded.
+    fn shuffle(&self, cards: &mut Vec<Card>) {
+        match self.seed {
+            // Mixing in the version gives each shuffle of a seeded game its own order
+            Some(seed) => SeededShuffler::new(seed ^ self.version).shuffle(cards),
+            None => RandomShuffler.shuffle(cards),
+        }
+    }
+
+    /// Takes the next card from the deck, reshuffling the discard pile into it if it's empty.
+    /// A reshuffle is reported to the event handler as `DeckReshuffled`.
+    fn take_from_deck(&mut self) -> Option<Card> {
+        if self.deck.is_empty() {
+            let cards_recycled = self.reshuffle_discard_pile();
+            if cards_recycled > 0 {
+                self.emit(&GameEvent::DeckReshuffled { cards_recycled });
+            }
+        }
+        self.deck.pop()
+    }
+
+    /// Returns true when the current player can neither draw nor play:
+    /// the deck is empty, there are no discards to recycle, and none of their cards are playable.
+    /// Also true when every active player is at the hand size limit and none can play.
+    pub fn is_stalemate(&self) -> bool {
+        let out_of_cards = self.deck.is_empty()
+            && self.discard_pile.len() <= 1
+            && self.playable_indices().is_empty();
+        out_of_cards || self.all_hands_full_and_stuck()
+    }
+
+    /// True if the player may not draw because of the hand size limit.
+    pub fn is_hand_full(&self, player_id: usize) -> bool {
+        self.config
+            .max_hand_size
+            .is_some_and(|max| self.players[player_id].hand.len() >= max)
+    }
+
+    fn all_hands_full_and_stuck(&self) -> bool {
+        let Some((top_card, _)) = self.discard_pile.last() else {
+            return false;
+        };
+        self.players.iter().filter(|p| p.active).all(|p| {
+            self.is_hand_full(p.id)
+                && !p
+                    .hand
+                    .iter()
+                    .any(|card| UnoGame::can_play_card(card, top_card))
+        })
+    }
+
+    /// Ends the game in a stalemate, awarding it to the active player with the lowest hand score.
+    fn declare_stalemate(&mut self) -> GameEvent {
+        let winner_id = self
+            .players
+            .iter()
+            .filter(|p| p.active)
+            .min_by_key(|p| p.hand_score())
+            .map(|p| p.id)
+            .unwrap_or(self.current_turn);
+        self.players[winner_id].stats.rounds_won += 1;
+        self.status = GameStatus::Stalemate { winner_id };
+        GameEvent::Stalemate { winner_id }
+    }
+
     /// Handles drawing a card.
+    /// If the deck is empty the discard pile is reshuffled into it. If that isn't possible
+    /// and the player has nothing to play, the game ends in a stalemate.
     pub fn draw_card(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
-        let player = &mut self.players[player_id];
+        // One draw a turn: after it the player plays the drawn card or passes
+        let drawn_again = self.has_drawn_this_turn && !self.has_penalty();
+        if drawn_again || self.must_play(player_id) {
+            return Err(GameError::InvalidMove);
+        }
+        let event = self.apply_draw(player_id)?;
+        self.version += 1;
+        self.emit(&event);
+        Ok(event)
+    }
+
+    /// True when `must_play_if_able` is set and it is `player_id`'s turn with a card they
+    /// can play, so `draw_card` refuses to draw.
+    pub fn must_play(&self, player_id: usize) -> bool {
+        self.config.must_play_if_able
+            && self.status == GameStatus::InProgress
+            && player_id == self.current_turn
+            && !self.playable_indices().is_empty()
+    }
+
+    /// The finishing order once the game is over, or `None` before then. The winner comes
+    /// first, then the players still in the game by their hand score, lowest first, then
+    /// those who left. Hands aren't touched once a game ends, so the scores are the ones
+    /// it ended with. Ties keep seat order.
+    pub fn standings(&self) -> Option<Vec<Standing>> {
+        let winner_id = match self.status {
+            GameStatus::Complete { winner_id } | GameStatus::Stalemate { winner_id } => winner_id,
+            GameStatus::Lobby | GameStatus::InProgress => return None,
+        };
+        let mut order: Vec<&Player> = self.players.iter().collect();
+        order.sort_by_key(|p| (p.id != winner_id, !p.active, p.hand_score()));
+        Some(
+            order
+                .into_iter()
+                .enumerate()
+                .map(|(rank, player)| Standing {
+                    player_id: player.id,
+                    name: player.name.clone(),
+                    position: rank + 1,
+                    hand_score: player.hand_score(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Everything the current player may do now, so clients don't have to work out the
+    /// rules themselves. Empty unless the game is in progress. While a penalty is owed
+    /// the only moves are taking it, challenging a Wild Draw Four and passing stacked
+    /// Skips on with another Skip.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.status != GameStatus::InProgress {
+            return Vec::new();
+        }
+        let mut actions = Vec::new();
+        if matches!(self.discard_pile.last(), Some((card, _)) if card.color == Color::Wild) {
+            actions.push(Action::ChooseColor);
+        }
+        actions.extend(
+            self.playable_indices()
+                .into_iter()
+                .map(|index| Action::Play { index }),
+        );
+        if self.has_penalty() {
+            actions.push(Action::TakePenalty);
+        }
+        if self.challengeable_draw_four.is_some() {
+            actions.push(Action::Challenge);
+        }
+        if !self.has_penalty() {
+            if self.has_drawn_this_turn {
+                actions.push(Action::Pass);
+            } else if !self.must_play(self.current_turn) {
+                actions.push(Action::Draw);
+            }
+        }
+        actions
+    }
+
+    fn apply_draw(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
+
+        if self.is_stalemate() {
+            return Ok(self.declare_stalemate());
+        }
 
         // If there are pending draws, draw those cards
         if self.pending_draws > 0 {
-            let mut cards = Vec::new();
-            for _ in 0..self.pending_draws {
-                if let Some(card) = self.deck.pop() {
-                    player.hand.push(card.clone());
-                    cards.push(card);
-                } else {
-                    return Err(GameError::EmptyDeck);
-                }
-            }
-            self.pending_draws = 0;
+            return self.draw_penalty(player_id);
+        }
+        if self.pending_skips > 0 {
+            return Ok(self.take_skips(player_id));
+        }
+
+        if self.is_hand_full(player_id) {
             self.next_turn();
-            return Ok(GameEvent::DrawTwo { player_id, cards });
+            return Ok(GameEvent::HandFull { player_id });
+        }
+
+        if self.config.draw_until_playable {
+            return self.draw_until_playable(player_id);
         }
 
         // Normal draw
-        if let Some(card) = self.deck.pop() {
-            player.hand.push(card.clone());
+        if let Some(card) = self.take_from_deck() {
+            self.has_drawn_this_turn = true;
+            self.players[player_id].stats.cards_drawn += 1;
+            let hand = &mut self.players[player_id].hand;
+            hand.push(card.clone());
+            let index = hand.len() - 1;
+
+            // The player may play the drawn card straight away instead of ending their turn
+            let Some((top_card, _)) = self.discard_pile.last() else {
+                return Err(GameError::EmptyDiscardPile);
+            };
+            if self.config.draw_then_play && UnoGame::can_play_card(&card, top_card) {
+                self.drawn_card_index = Some(index);
+                return Ok(GameEvent::CardDrawnPlayable {
+                    player_id,
+                    card,
+                    index,
+                });
+            }
+
             self.next_turn();
             Ok(GameEvent::CardDrawn { player_id, card })
         } else {
             Err(GameError::EmptyDeck)
         }
     }
+
+    /// Takes the pending Draw Two or Wild Draw Four penalty, or the stacked Skips, for the
+    /// current player, which ends their turn. Unlike `draw_card`, fails if there is no
+    /// penalty to take.
+    pub fn take_penalty(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        self.check_in_progress()?;
+        if player_id != self.current_turn || !self.has_penalty() {
+            return Err(GameError::InvalidMove);
+        }
+        let event = if self.pending_draws > 0 {
+            self.draw_penalty(player_id)?
+        } else {
+            self.take_skips(player_id)
+        };
+        self.version += 1;
+        Ok(event)
+    }
+
+    /// Whether the current player owes cards or turns they must take before moving on.
+    pub fn has_penalty(&self) -> bool {
+        self.pending_draws > 0 || self.pending_skips > 0
+    }
+
+    /// Ends a Skip chain on the player: they lose this turn and one more for each further
+    /// Skip in the chain.
+    fn take_skips(&mut self, player_id: usize) -> GameEvent {
+        let turns = std::mem::take(&mut self.pending_skips);
+        self.players[player_id].turns_to_miss += turns - 1;
+        self.next_turn();
+        GameEvent::TurnsSkipped { player_id, turns }
+    }
+
+    fn draw_penalty(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        let mut cards = Vec::new();
+        for _ in 0..self.pending_draws {
+            // The rest of the penalty is waived once the hand is full
+            if self.is_hand_full(player_id) {
+                break;
+            }
+            if let Some(card) = self.take_from_deck() {
+                self.players[player_id].hand.push(card.clone());
+                self.players[player_id].stats.cards_drawn += 1;
+                cards.push(card);
+            } else {
+                return Err(GameError::EmptyDeck);
+            }
+        }
+        self.pending_draws = 0;
+        self.next_turn();
+        Ok(GameEvent::DrawTwo { player_id, cards })
+    }
+
+    /// Draws until a playable card turns up, leaving the turn with the player so they can play it.
+    /// Every drawn card leaves the deck and discard pile for good, so this always ends;
+    /// if both run dry first, or the hand fills up, the turn passes on.
+    fn draw_until_playable(&mut self, player_id: usize) -> Result<GameEvent, GameError> {
+        let top_card = match self.discard_pile.last() {
+            Some((card, _)) => card.clone(),
+            None => return Err(GameError::EmptyDiscardPile),
+        };
+        let mut cards = Vec::new();
+        while !self.is_hand_full(player_id) {
+            let Some(card) = self.take_from_deck() else {
+                break;
+            };
+            self.players[player_id].hand.push(card.clone());
+            self.players[player_id].stats.cards_drawn += 1;
+            let playable = UnoGame::can_play_card(&card, &top_card);
+            cards.push(card);
+            if playable {
+                self.has_drawn_this_turn = true;
+                return Ok(GameEvent::CardsDrawnUntilPlayable { player_id, cards });
+            }
+        }
+
+        if cards.is_empty() {
+            return Err(GameError::EmptyDeck);
+        }
+        self.next_turn();
+        Ok(GameEvent::CardsDrawnUntilPlayable { player_id, cards })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uno_game::player::PlayerStats;
+    use crate::uno_game::shuffle::NoopShuffler;
 
     #[test]
     fn test_initialize_deck() {
-        let deck = UnoGame::initialize_deck();
+        let deck = UnoGame::initialize_deck(&GameConfig::default());
         assert_eq!(deck.len(), 108); // Standard Uno deck has 108 cards
     }
 
+    #[test]
+    fn test_new_with_noop_shuffler_keeps_construction_order() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let game = UnoGame::new_with_shuffler(player_names, &mut NoopShuffler).unwrap();
+
+        // Cards are dealt from the end, so the rest of the deck is untouched
+        let mut expected = UnoGame::single_deck(&GameConfig::default());
+        expected.truncate(108 - 2 * 7 - 1);
+        assert_eq!(game.deck, expected);
+        assert_eq!(
+            game.discard_pile[0].0,
+            Card::new(Color::Yellow, CardType::Number(9))
+        );
+    }
+
     #[test]
     fn test_new_game() {
         let player_names = vec!["Alice".to_string(), "Bob".to_string()];
@@ -374,7 +2061,12 @@ mod tests {
         let player_names = vec!["Alice".to_string(), "Bob".to_string()];
         let mut game = UnoGame::new(player_names).unwrap();
 
-        // Get the top card of the discard pile
+        // Make sure Alice has a card that matches the top of the discard pile
+        game.players[0]
+            .hand
+            .insert(0, Card::new(Color::Red, CardType::Number(5)));
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(3)), usize::MAX));
         let top_card = &game.discard_pile.last().unwrap().0;
 
         // Find a matching card in Alice's hand
@@ -440,6 +2132,80 @@ mod tests {
         assert_eq!(game.current_turn, 0);
     }
 
+    #[test]
+    fn test_peek_next_player() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+            "Dave".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        assert_eq!(game.peek_next_player(), 1);
+        assert_eq!(game.peek_player_after_skip(), 2);
+
+        // Clockwise wraps from the last seat to the first
+        game.current_turn = 3;
+        assert_eq!(game.peek_next_player(), 0);
+        assert_eq!(game.peek_player_after_skip(), 1);
+
+        // Counter-clockwise wraps from the first seat to the last
+        game.current_turn = 0;
+        game.direction = Direction::CounterClockwise;
+        assert_eq!(game.peek_next_player(), 3);
+        assert_eq!(game.peek_player_after_skip(), 2);
+
+        // Peeking agrees with moving, and leaves the turn alone
+        game.players[3].active = false;
+        let peeked = game.peek_next_player();
+        assert_eq!(game.current_turn, 0);
+        game.next_turn();
+        assert_eq!(game.current_turn, peeked);
+        assert_eq!(peeked, 2);
+    }
+
+    #[test]
+    fn test_peek_next_player_passes_over_missed_turns() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.skip_stacking = true;
+        game.players[1].turns_to_miss = 1;
+
+        assert_eq!(game.peek_next_player(), 2);
+        assert_eq!(game.players[1].turns_to_miss, 1);
+        game.next_turn();
+        assert_eq!(game.current_turn, 2);
+
+        // Bob has served the missed turn, so Bob is next after Alice again
+        game.current_turn = 0;
+        assert_eq!(game.peek_next_player(), 1);
+
+        // Owing more turns than a lap of the table passes Bob more than once
+        game.players[1].turns_to_miss = 2;
+        game.players[2].turns_to_miss = 1;
+        let peeked = game.peek_next_player();
+        game.next_turn();
+        assert_eq!(game.current_turn, peeked);
+        assert_eq!(peeked, 0);
+    }
+
+    #[test]
+    fn test_peek_next_player_two_players() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+        assert_eq!(game.peek_next_player(), 1);
+        // Skipping the only opponent brings play straight back
+        assert_eq!(game.peek_player_after_skip(), 0);
+
+        game.direction = Direction::CounterClockwise;
+        assert_eq!(game.peek_next_player(), 1);
+        assert_eq!(game.peek_player_after_skip(), 0);
+    }
+
     #[test]
     fn test_reverse_direction() {
         let player_names = vec!["Alice".to_string(), "Bob".to_string()];
@@ -513,7 +2279,7 @@ mod tests {
 
         // Add a WildDrawFour card to Tanya's hand
         let wild_draw_four = Card::new(Color::Wild, CardType::WildDrawFour);
-        game.players[1].hand.push(wild_draw_four);
+        game.players[1].hand.insert(0, wild_draw_four);
 
         // Play the WildDrawFour card
         let result = game.play_card(1, 0);
@@ -539,7 +2305,9 @@ mod tests {
 
         // Add a Skip card to Alice's hand
         let skip_card = Card::new(Color::Red, CardType::Skip);
-        game.players[0].hand.push(skip_card);
+        game.players[0].hand.insert(0, skip_card);
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(3)), usize::MAX));
 
         // Play the Skip card
         let result = game.play_card(0, 0);
@@ -564,7 +2332,9 @@ mod tests {
 
         // Add a Reverse card to Bob's hand
         let reverse_card = Card::new(Color::Blue, CardType::Reverse);
-        game.players[1].hand.push(reverse_card);
+        game.players[1].hand.insert(0, reverse_card);
+        game.discard_pile
+            .push((Card::new(Color::Blue, CardType::Number(3)), usize::MAX));
 
         // Play the Reverse card
         let result = game.play_card(1, 0);
@@ -590,7 +2360,9 @@ mod tests {
 
         // Add a Draw Two card to Alice's hand
         let draw_two_card = Card::new(Color::Green, CardType::DrawTwo);
-        game.players[0].hand.push(draw_two_card);
+        game.players[0].hand.insert(0, draw_two_card);
+        game.discard_pile
+            .push((Card::new(Color::Green, CardType::Number(3)), usize::MAX));
 
         // Play the Draw Two card
         let result = game.play_card(0, 0);
@@ -616,7 +2388,9 @@ mod tests {
 
         // Add a normal card to Alice's hand
         let normal_card = Card::new(Color::Red, CardType::Number(5));
-        game.players[0].hand.push(normal_card);
+        game.players[0].hand.insert(0, normal_card);
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(3)), usize::MAX));
 
         // Play the normal card
         let result = game.play_card(0, 0);
@@ -669,4 +2443,1802 @@ mod tests {
         assert_eq!(game.pending_draws, 0);
         assert_eq!(game.current_turn, 1);
     }
+
+    fn skip_stacking_game() -> UnoGame {
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        game.config.skip_stacking = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(3)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Blue, CardType::Number(1)),
+            Card::new(Color::Blue, CardType::Number(9)),
+        ];
+        game.players[1].hand = vec![
+            Card::new(Color::Red, CardType::Number(2)),
+            Card::new(Color::Blue, CardType::Skip),
+            Card::new(Color::Blue, CardType::Number(2)),
+        ];
+        game.players[2].hand = vec![
+            Card::new(Color::Green, CardType::Number(4)),
+            Card::new(Color::Green, CardType::Number(5)),
+        ];
+        game
+    }
+
+    #[test]
+    fn test_stacked_skips_pass_along_the_chain() {
+        let mut game = skip_stacking_game();
+
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.pending_skips, 1);
+        assert_eq!(game.current_turn, 1);
+        // Only a Skip answers a Skip, though the Red 2 would otherwise match
+        assert_eq!(game.playable_indices(), vec![1]);
+        assert!(matches!(
+            game.play_card(1, 0),
+            Err(GameError::UnplayableCard(_))
+        ));
+        assert_eq!(
+            game.legal_actions(),
+            vec![Action::Play { index: 1 }, Action::TakePenalty]
+        );
+
+        game.play_card(1, 1).unwrap();
+        assert_eq!(game.pending_skips, 2);
+        assert_eq!(game.current_turn, 2);
+        assert_eq!(game.legal_actions(), vec![Action::TakePenalty]);
+
+        let event = game.take_penalty(2).unwrap();
+        assert_eq!(
+            event,
+            GameEvent::TurnsSkipped {
+                player_id: 2,
+                turns: 2
+            }
+        );
+        assert_eq!(
+            game.describe_event(&event),
+            "Carol is skipped and misses their next 1 turns."
+        );
+        assert_eq!(game.pending_skips, 0);
+        assert_eq!(game.current_turn, 0);
+
+        // Carol sits out her second turn too
+        game.play_card(0, 0).unwrap();
+        game.play_card(1, 1).unwrap();
+        assert_eq!(game.current_turn, 0);
+        assert_eq!(game.players[2].turns_to_miss, 0);
+    }
+
+    #[test]
+    fn test_unanswered_skip_costs_one_turn() {
+        let mut game = skip_stacking_game();
+        game.players[1].hand.remove(1);
+
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.current_turn, 1);
+        assert!(game.playable_indices().is_empty());
+
+        // Drawing takes the skip rather than a card
+        let hand_size = game.players[1].hand.len();
+        let event = game.draw_card(1).unwrap();
+        assert_eq!(
+            event,
+            GameEvent::TurnsSkipped {
+                player_id: 1,
+                turns: 1
+            }
+        );
+        assert_eq!(game.describe_event(&event), "Bob is skipped.");
+        assert_eq!(game.players[1].hand.len(), hand_size);
+        assert_eq!(game.current_turn, 2);
+        assert_eq!(game.players[1].turns_to_miss, 0);
+    }
+
+    #[test]
+    fn test_playable_indices() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+
+        game.discard_pile = vec![(Card::new(Color::Blue, CardType::Number(7)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Red, CardType::Reverse),
+            Card::new(Color::Blue, CardType::DrawTwo),
+            Card::new(Color::Wild, CardType::WildDrawFour),
+        ];
+
+        assert_eq!(game.playable_indices(), vec![0, 2, 3]);
+
+        // Nothing is playable while a draw penalty is pending
+        game.pending_draws = 2;
+        assert!(game.playable_indices().is_empty());
+    }
+
+    #[test]
+    fn test_forfeit_three_player_game() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        let initial_deck_size = game.deck.len();
+
+        // Bob forfeits while it's Alice's turn
+        let event = game.forfeit(1).unwrap();
+        assert!(matches!(event, GameEvent::PlayerForfeited { player_id: 1 }));
+        assert!(!game.players[1].active);
+        assert!(game.players[1].hand.is_empty());
+        assert_eq!(game.deck.len(), initial_deck_size + 7);
+        assert_eq!(game.status, GameStatus::InProgress);
+
+        // Turns now go between Alice and Charlie only
+        game.next_turn();
+        assert_eq!(game.current_turn, 2);
+        game.next_turn();
+        assert_eq!(game.current_turn, 0);
+
+        // Forfeiting twice is rejected
+        assert!(matches!(game.forfeit(1), Err(GameError::InvalidMove)));
+    }
+
+    #[test]
+    fn test_forfeit_current_player_advances_turn() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.pending_draws = 2;
+
+        game.forfeit(0).unwrap();
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.pending_draws, 0);
+    }
+
+    #[test]
+    fn test_kick_current_player_advances_turn() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.current_turn = 1;
+        let deck_size = game.deck.len();
+
+        let event = game.kick(1).unwrap();
+        assert!(matches!(event, GameEvent::PlayerKicked { player_id: 1 }));
+        assert_eq!(game.current_turn, 2);
+        assert!(!game.players[1].active);
+        assert_eq!(game.deck.len(), deck_size + 7);
+        assert_eq!(game.version, 1);
+
+        // Their seat is skipped from now on
+        game.next_turn();
+        game.next_turn();
+        assert_eq!(game.current_turn, 2);
+        assert!(matches!(game.kick(1), Err(GameError::InvalidMove)));
+    }
+
+    #[test]
+    fn test_kicking_down_to_one_player_ends_the_game() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let event = game.kick(0).unwrap();
+        assert!(matches!(event, GameEvent::PlayerWins { player_id: 1 }));
+        assert_eq!(game.status, GameStatus::Complete { winner_id: 1 });
+    }
+
+    #[test]
+    fn test_forfeit_last_opponent_wins() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+
+        game.forfeit(0).unwrap();
+        let event = game.forfeit(2).unwrap();
+        assert!(matches!(event, GameEvent::PlayerWins { player_id: 1 }));
+        assert_eq!(game.status, GameStatus::Complete { winner_id: 1 });
+        assert!(matches!(game.forfeit(1), Err(GameError::GameAlreadyOver)));
+    }
+
+    #[test]
+    fn test_draw_reshuffles_discard_pile() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+
+        game.deck.clear();
+        game.discard_pile = vec![
+            (Card::new(Color::Red, CardType::Wild), 1),
+            (Card::new(Color::Blue, CardType::Number(2)), 0),
+            (Card::new(Color::Green, CardType::Number(4)), 1),
+        ];
+
+        let result = game.draw_card(0);
+        assert!(result.is_ok());
+        assert_eq!(game.players[0].hand.len(), 8);
+        assert_eq!(game.deck.len(), 1);
+        assert_eq!(game.discard_pile.len(), 1);
+        assert_eq!(game.discard_pile[0].0.card_type, CardType::Number(4));
+
+        // The recycled wild goes back uncolored
+        let recycled: Vec<&Card> = game
+            .deck
+            .iter()
+            .chain(game.players[0].hand.last())
+            .collect();
+        assert!(recycled
+            .iter()
+            .any(|c| c.card_type == CardType::Wild && c.color == Color::Wild));
+    }
+
+    #[test]
+    fn test_recolored_wild_keeps_its_identity() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Green, CardType::Wild),
+            Card::new(Color::Red, CardType::Number(1)),
+        ];
+        game.play_card(0, 0).unwrap();
+
+        let (top_card, _) = game.discard_pile.last().unwrap();
+        assert_eq!(top_card.card_type, CardType::Wild);
+        assert!(top_card.is_wild());
+        assert_eq!(top_card.chosen_color(), Some(Color::Green));
+        assert_eq!(top_card.points(), 50);
+    }
+
+    #[test]
+    fn test_stalemate_detection() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+
+        game.deck.clear();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![Card::new(Color::Blue, CardType::Skip)];
+        game.players[1].hand = vec![
+            Card::new(Color::Green, CardType::Number(1)),
+            Card::new(Color::Yellow, CardType::Number(2)),
+        ];
+
+        assert!(game.is_stalemate());
+
+        // Drawing ends the game; Bob's hand is worth 3 points against Alice's 20
+        let event = game.draw_card(0).unwrap();
+        assert!(matches!(event, GameEvent::Stalemate { winner_id: 1 }));
+        assert_eq!(game.status, GameStatus::Stalemate { winner_id: 1 });
+        assert!(matches!(game.draw_card(0), Err(GameError::GameAlreadyOver)));
+    }
+
+    #[test]
+    fn test_no_stalemate_with_playable_card() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+
+        game.deck.clear();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![Card::new(Color::Red, CardType::Skip)];
+
+        assert!(!game.is_stalemate());
+    }
+
+    #[test]
+    fn test_redeal_player() {
+        let mut game =
+            UnoGame::new_with_seed(vec!["Alice".to_string(), "Bob".to_string()], 7).unwrap();
+        let total = game.deck.len()
+            + game.discard_pile.len()
+            + game.players.iter().map(|p| p.hand.len()).sum::<usize>();
+        let before = game.players[1].hand.clone();
+
+        game.redeal_player(1).unwrap();
+
+        assert_ne!(game.players[1].hand, before);
+        assert_eq!(game.players[1].hand.len(), 7);
+        assert_eq!(game.version, 1);
+        let after = game.deck.len()
+            + game.discard_pile.len()
+            + game.players.iter().map(|p| p.hand.len()).sum::<usize>();
+        assert_eq!(after, total);
+        assert!(game.validate().is_ok());
+    }
+
+    #[test]
+    fn test_redeal_reshuffles_discards_when_the_deck_is_short() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.players[0].hand.truncate(2);
+        game.deck.truncate(3);
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(1)), 1));
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(2)), 1));
+
+        game.redeal_player(0).unwrap();
+        assert_eq!(game.players[0].hand.len(), 7);
+        assert_eq!(game.deck.len(), 0);
+        assert_eq!(game.discard_pile.len(), 1);
+
+        game.players[1].hand.clear();
+        game.players[1]
+            .hand
+            .push(Card::new(Color::Blue, CardType::Skip));
+        assert!(matches!(game.redeal_player(1), Err(GameError::EmptyDeck)));
+        assert_eq!(game.players[1].hand.len(), 1);
+    }
+
+    #[test]
+    fn test_draw_then_play_with_playable_card() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.draw_then_play = true;
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        let event = game.draw_card(0).unwrap();
+        match event {
+            GameEvent::CardDrawnPlayable {
+                player_id, index, ..
+            } => {
+                assert_eq!(player_id, 0);
+                assert_eq!(index, 7);
+            }
+            other => panic!("Expected CardDrawnPlayable, got {:?}", other),
+        }
+
+        // The turn stays with Alice, who can now play the drawn card
+        assert_eq!(game.current_turn, 0);
+        assert!(game.play_card(0, 7).is_ok());
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_draw_then_play_with_unplayable_card() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.draw_then_play = true;
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.deck.push(Card::new(Color::Blue, CardType::Number(9)));
+
+        let event = game.draw_card(0).unwrap();
+        assert!(matches!(event, GameEvent::CardDrawn { player_id: 0, .. }));
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_draw_until_playable_first_card() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.draw_until_playable = true;
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        let event = game.draw_card(0).unwrap();
+        match event {
+            GameEvent::CardsDrawnUntilPlayable { player_id, cards } => {
+                assert_eq!(player_id, 0);
+                assert_eq!(cards, vec![Card::new(Color::Red, CardType::Number(9))]);
+            }
+            other => panic!("Expected CardsDrawnUntilPlayable, got {:?}", other),
+        }
+
+        assert_eq!(game.current_turn, 0);
+        assert!(game.play_card(0, 7).is_ok());
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_draw_until_playable_several_cards() {
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.draw_until_playable = true;
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        // Drawn from the end of the deck, so the red 9 comes out third
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+        game.deck.push(Card::new(Color::Blue, CardType::Number(1)));
+        game.deck.push(Card::new(Color::Green, CardType::Number(2)));
+        let deck_size = game.deck.len();
+
+        let event = game.draw_card(0).unwrap();
+        match event {
+            GameEvent::CardsDrawnUntilPlayable { cards, .. } => {
+                assert_eq!(cards.len(), 3);
+                assert_eq!(cards[2], Card::new(Color::Red, CardType::Number(9)));
+            }
+            other => panic!("Expected CardsDrawnUntilPlayable, got {:?}", other),
+        }
+        assert_eq!(game.deck.len(), deck_size - 3);
+        assert_eq!(game.players[0].hand.len(), 10);
+        assert_eq!(game.players[0].stats.cards_drawn, 3);
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_draw_until_playable_runs_out_of_cards() {
+        let players = vec![
+            player_with(
+                0,
+                "Alice",
+                vec![Card::new(Color::Blue, CardType::Number(1))],
+            ),
+            player_with(1, "Bob", vec![Card::new(Color::Blue, CardType::Number(2))]),
+        ];
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let deck = vec![
+            Card::new(Color::Green, CardType::Number(3)),
+            Card::new(Color::Yellow, CardType::Number(4)),
+        ];
+        let mut game =
+            UnoGame::from_parts(players, deck, discard, 0, Direction::Clockwise).unwrap();
+        game.config.draw_until_playable = true;
+
+        let event = game.draw_card(0).unwrap();
+        assert!(
+            matches!(event, GameEvent::CardsDrawnUntilPlayable { ref cards, .. } if cards.len() == 2)
+        );
+        assert!(game.deck.is_empty());
+        assert_eq!(game.current_turn, 1);
+
+        // Nothing left to draw and nothing to play: the usual stalemate check applies
+        assert!(matches!(
+            game.draw_card(1).unwrap(),
+            GameEvent::Stalemate { .. }
+        ));
+    }
+
+    #[test]
+    fn test_play_card_errors() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(7)),
+            Card::new(Color::Red, CardType::Number(1)),
+        ];
+        assert!(matches!(game.play_card(1, 0), Err(GameError::NotYourTurn)));
+        assert!(matches!(
+            game.play_card(0, 5),
+            Err(GameError::CardNotInHand)
+        ));
+        assert!(matches!(
+            game.play_card(0, 0),
+            Err(GameError::UnplayableCard(_))
+        ));
+        assert_eq!(game.version, 0);
+
+        game.pending_draws = 2;
+        assert_eq!(
+            game.play_card(0, 1).unwrap_err().to_string(),
+            "You must take the 2 pending cards first"
+        );
+    }
+
+    #[test]
+    fn test_explain_playability() {
+        let explain = |card: Card, top: Card| UnoGame::explain_playability(&card, &top);
+        assert_eq!(
+            explain(
+                Card::new(Color::Blue, CardType::Number(5)),
+                Card::new(Color::Red, CardType::Number(9))
+            ),
+            Err("Cannot play Blue 5 on Red 9: colors and numbers differ".to_string())
+        );
+        assert_eq!(
+            explain(
+                Card::new(Color::Blue, CardType::Number(5)),
+                Card::new(Color::Red, CardType::Skip)
+            ),
+            Err("Cannot play Blue 5 on Red Skip: colors differ".to_string())
+        );
+        assert_eq!(
+            explain(
+                Card::new(Color::Green, CardType::Number(2)),
+                Card::new(Color::Yellow, CardType::Wild)
+            ),
+            Err("Cannot play Green 2 on Wild (Yellow): colors differ".to_string())
+        );
+        assert_eq!(
+            explain(
+                Card::new(Color::Blue, CardType::DrawTwo),
+                Card::new(Color::Red, CardType::DrawTwo)
+            ),
+            Err(
+                "Cannot play Blue Draw Two on Red Draw Two: an action card must match the color"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            explain(
+                Card::new(Color::Blue, CardType::Number(9)),
+                Card::new(Color::Red, CardType::Number(9))
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_moves_on_an_empty_discard_pile_fail() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile.clear();
+        assert!(matches!(
+            game.play_card(0, 0),
+            Err(GameError::EmptyDiscardPile)
+        ));
+        game.config.draw_until_playable = true;
+        assert!(matches!(
+            game.draw_card(0),
+            Err(GameError::EmptyDiscardPile)
+        ));
+    }
+
+    #[test]
+    fn test_take_penalty() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        assert!(matches!(game.take_penalty(0), Err(GameError::InvalidMove)));
+
+        game.players[0]
+            .hand
+            .push(Card::new(Color::Red, CardType::DrawTwo));
+        game.play_card(0, 7).unwrap();
+        assert!(matches!(game.take_penalty(0), Err(GameError::InvalidMove)));
+
+        match game.take_penalty(1).unwrap() {
+            GameEvent::DrawTwo { player_id, cards } => {
+                assert_eq!(player_id, 1);
+                assert_eq!(cards.len(), 2);
+            }
+            other => panic!("Expected DrawTwo, got {:?}", other),
+        }
+        assert_eq!(game.players[1].hand.len(), 9);
+        assert_eq!(game.pending_draws, 0);
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_must_play_if_able_blocks_draw_with_playable_card() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.must_play_if_able = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(1)),
+            Card::new(Color::Red, CardType::Number(9)),
+        ];
+        let version = game.version;
+
+        assert!(matches!(game.draw_card(0), Err(GameError::InvalidMove)));
+        assert_eq!(game.players[0].hand.len(), 2);
+        assert_eq!(game.version, version);
+
+        // Without the rule the same draw is allowed
+        game.config.must_play_if_able = false;
+        game.draw_card(0).unwrap();
+        assert_eq!(game.players[0].hand.len(), 3);
+    }
+
+    #[test]
+    fn test_must_play_if_able_allows_draw_without_playable_card() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.must_play_if_able = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(1)),
+            Card::new(Color::Green, CardType::Skip),
+        ];
+
+        game.draw_card(0).unwrap();
+        assert_eq!(game.players[0].hand.len(), 3);
+    }
+
+    #[test]
+    fn test_time_out_draws_despite_must_play_if_able() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.must_play_if_able = true;
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![Card::new(Color::Red, CardType::Number(9))];
+
+        game.time_out_turn().unwrap();
+        assert_eq!(game.players[0].hand.len(), 2);
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_draw_at_hand_size_limit_passes_turn() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.max_hand_size = Some(8);
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[1].hand = vec![Card::new(Color::Red, CardType::Number(1)); 7];
+
+        // Alice draws up to the limit as normal
+        game.draw_card(0).unwrap();
+        assert_eq!(game.players[0].hand.len(), 8);
+
+        game.play_card(1, 0).unwrap();
+        let deck_size = game.deck.len();
+        let event = game.draw_card(0).unwrap();
+        assert_eq!(event, GameEvent::HandFull { player_id: 0 });
+        assert_eq!(game.players[0].hand.len(), 8);
+        assert_eq!(game.deck.len(), deck_size);
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_hand_size_limit_waives_rest_of_penalty() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.max_hand_size = Some(9);
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0]
+            .hand
+            .push(Card::new(Color::Red, CardType::DrawTwo));
+        game.players[1].hand = vec![Card::new(Color::Blue, CardType::Number(1)); 8];
+        game.play_card(0, 7).unwrap();
+
+        match game.draw_card(1).unwrap() {
+            GameEvent::DrawTwo { player_id, cards } => {
+                assert_eq!(player_id, 1);
+                assert_eq!(cards.len(), 1);
+            }
+            other => panic!("Expected DrawTwo, got {:?}", other),
+        }
+        assert_eq!(game.players[1].hand.len(), 9);
+        assert_eq!(game.pending_draws, 0);
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_full_hands_with_nothing_to_play_are_a_stalemate() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.max_hand_size = Some(3);
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![Card::new(Color::Blue, CardType::Number(1)); 3];
+        game.players[1].hand = vec![Card::new(Color::Green, CardType::Number(2)); 3];
+        assert!(game.is_stalemate());
+
+        let event = game.draw_card(0).unwrap();
+        assert_eq!(event, GameEvent::Stalemate { winner_id: 0 });
+    }
+
+    #[test]
+    fn test_seven_swaps_hands() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.seven_zero_enabled = true;
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(3)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Blue, CardType::Number(1)),
+        ];
+        let charlie_hand = game.players[2].hand.clone();
+
+        // A target is required
+        assert!(game.play_card(0, 0).is_err());
+        assert!(game.play_card_with_target(0, 0, Some(0)).is_err());
+
+        let event = game.play_card_with_target(0, 0, Some(2)).unwrap();
+        assert!(matches!(
+            event,
+            GameEvent::HandSwap {
+                player_id: 0,
+                target_player_id: 2
+            }
+        ));
+        assert_eq!(game.players[0].hand, charlie_hand);
+        assert_eq!(
+            game.players[2].hand,
+            vec![Card::new(Color::Blue, CardType::Number(1))]
+        );
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_zero_rotates_hands() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.config.seven_zero_enabled = true;
+
+        game.discard_pile = vec![(Card::new(Color::Green, CardType::Number(3)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Green, CardType::Number(0)),
+            Card::new(Color::Red, CardType::Skip),
+        ];
+        let alice_rest = vec![Card::new(Color::Red, CardType::Skip)];
+        let bob_hand = game.players[1].hand.clone();
+        let charlie_hand = game.players[2].hand.clone();
+
+        let event = game.play_card(0, 0).unwrap();
+        assert!(matches!(
+            event,
+            GameEvent::HandRotate {
+                direction: Direction::Clockwise
+            }
+        ));
+        assert_eq!(game.players[1].hand, alice_rest);
+        assert_eq!(game.players[2].hand, bob_hand);
+        assert_eq!(game.players[0].hand, charlie_hand);
+
+        // Counter-clockwise passes hands the other way
+        game.direction = Direction::CounterClockwise;
+        game.current_turn = 1;
+        game.players[1]
+            .hand
+            .insert(0, Card::new(Color::Green, CardType::Number(0)));
+        game.play_card(1, 0).unwrap();
+        assert_eq!(game.players[0].hand, alice_rest);
+        assert_eq!(game.players[1].hand, bob_hand);
+        assert_eq!(game.players[2].hand, charlie_hand);
+    }
+
+    #[test]
+    fn test_eight_players_double_deck() {
+        let player_names = (1..=8).map(|i| format!("Player {}", i)).collect();
+        let config = GameConfig {
+            starting_hand_size: 10,
+            deck_copies: 2,
+            ..GameConfig::default()
+        };
+        let game = UnoGame::new_with_config(player_names, config).unwrap();
+
+        assert_eq!(game.players.len(), 8);
+        for player in &game.players {
+            assert_eq!(player.hand.len(), 10);
+        }
+        assert_eq!(game.deck.len(), 216 - (8 * 10) - 1);
+        assert_eq!(game.config.deck_copies, 2);
+    }
+
+    #[test]
+    fn test_configurable_wild_counts() {
+        let config = GameConfig {
+            wild_count: 6,
+            wild_draw_four_count: 0,
+            ..GameConfig::default()
+        };
+        let game =
+            UnoGame::new_with_config(vec!["Alice".to_string(), "Bob".to_string()], config).unwrap();
+
+        let all_cards: Vec<&Card> = game
+            .deck
+            .iter()
+            .chain(game.players.iter().flat_map(|p| &p.hand))
+            .chain(game.discard_pile.iter().map(|(card, _)| card))
+            .collect();
+        assert_eq!(all_cards.len(), 106);
+        assert!(all_cards
+            .iter()
+            .all(|c| c.card_type != CardType::WildDrawFour));
+        assert_eq!(
+            all_cards
+                .iter()
+                .filter(|c| c.card_type == CardType::Wild)
+                .count(),
+            6
+        );
+        assert_eq!(game.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_not_enough_cards_to_deal() {
+        let player_names = (1..=8).map(|i| format!("Player {}", i)).collect();
+        let config = GameConfig {
+            starting_hand_size: 20,
+            ..GameConfig::default()
+        };
+        let result = UnoGame::new_with_config(player_names, config);
+        match result {
+            Err(GameError::Other(msg)) => assert!(msg.contains("need 161, deck has 108")),
+            other => panic!("Expected a deal error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_deal_needs_required_cards() {
+        assert_eq!(UnoGame::required_cards(4, 7), 29);
+        assert_eq!(UnoGame::required_cards(2, 0), 1);
+
+        // A standard deck has 108 cards: 2 players of 53 leaves exactly one for the discard
+        let names = || vec!["Alice".to_string(), "Bob".to_string()];
+        let config = |starting_hand_size| GameConfig {
+            starting_hand_size,
+            ..GameConfig::default()
+        };
+        let game = UnoGame::new_with_config(names(), config(53)).unwrap();
+        assert!(game.players.iter().all(|p| p.hand.len() == 53));
+
+        match UnoGame::new_with_config(names(), config(54)) {
+            Err(GameError::Other(msg)) => assert_eq!(
+                msg,
+                "Not enough cards to deal 54 cards to 2 players: need 109, deck has 108"
+            ),
+            other => panic!("Expected a deal error, got {:?}", other.map(|_| ())),
+        }
+
+        // Too many players is refused before the deck is considered
+        let crowd = (1..=20).map(|i| format!("Player {}", i)).collect();
+        match UnoGame::new(crowd) {
+            Err(GameError::Other(msg)) => assert!(msg.contains("between 2 and 10 players")),
+            other => panic!("Expected a player count error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_too_few_players() {
+        assert!(matches!(UnoGame::new(vec![]), Err(GameError::Other(_))));
+        assert!(matches!(
+            UnoGame::new(vec!["Alice".to_string()]),
+            Err(GameError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_many_players() {
+        let player_names = (1..=11).map(|i| format!("Player {}", i)).collect();
+        match UnoGame::new(player_names) {
+            Err(GameError::Other(msg)) => {
+                assert_eq!(msg, "A game needs between 2 and 10 players, got 11")
+            }
+            other => panic!("Expected a player count error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_player_names() {
+        match UnoGame::new(vec!["Bob".to_string(), "bob".to_string()]) {
+            Err(GameError::Other(msg)) => assert_eq!(msg, "Duplicate player name: bob"),
+            other => panic!(
+                "Expected a duplicate name error, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_empty_player_name() {
+        match UnoGame::new(vec!["Alice".to_string(), "  ".to_string()]) {
+            Err(GameError::Other(msg)) => assert_eq!(msg, "Player names must not be empty"),
+            other => panic!("Expected an empty name error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_long_player_name() {
+        let long_name = "x".repeat(UnoGame::MAX_NAME_LEN + 1);
+        assert!(UnoGame::new(vec!["Alice".to_string(), long_name]).is_err());
+
+        // Surrounding whitespace doesn't count, and is dropped
+        let padded = format!("  {}  ", "x".repeat(UnoGame::MAX_NAME_LEN));
+        let game = UnoGame::new(vec!["Alice".to_string(), padded]).unwrap();
+        assert_eq!(game.players[1].name.len(), UnoGame::MAX_NAME_LEN);
+    }
+
+    #[test]
+    fn test_lobby_join_and_start() {
+        let mut game =
+            UnoGame::new_lobby(vec!["Alice".to_string()], GameConfig::default(), None).unwrap();
+        assert_eq!(game.status, GameStatus::Lobby);
+        assert!(game.discard_pile.is_empty());
+        assert_eq!(game.is_decided(), None);
+        assert!(matches!(game.draw_card(0), Err(GameError::NotStarted)));
+        assert!(game.play_card(0, 0).is_err());
+
+        assert_eq!(game.join(" Bob ".to_string()).unwrap(), 1);
+        assert!(game.join("alice".to_string()).is_err());
+        game.start().unwrap();
+
+        assert_eq!(game.status, GameStatus::InProgress);
+        assert_eq!(game.players[1].name, "Bob");
+        assert!(game.players.iter().all(|p| p.hand.len() == 7));
+        assert_eq!(game.discard_pile.len(), 1);
+        assert_eq!(game.version, 2);
+        assert!(game.join("Carol".to_string()).is_err());
+        assert!(game.start().is_err());
+    }
+
+    #[test]
+    fn test_restart_deals_a_new_round() {
+        let mut game = UnoGame::new_with_seed(
+            vec![
+                "Alice".to_string(),
+                "Bob".to_string(),
+                "Charlie".to_string(),
+            ],
+            3,
+        )
+        .unwrap();
+        assert!(game.restart(true).is_err());
+
+        game.players[2].is_bot = true;
+        game.forfeit(1).unwrap();
+        game.players[0].hand.truncate(1);
+        game.current_turn = 0;
+        game.discard_pile[0].0 = game.players[0].hand[0].clone();
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.status, GameStatus::Complete { winner_id: 0 });
+        let version = game.version;
+
+        game.restart(true).unwrap();
+        assert_eq!(game.status, GameStatus::InProgress);
+        assert_eq!(game.version, version + 1);
+        assert_eq!(game.current_turn, 0);
+        assert_eq!(game.direction, Direction::Clockwise);
+        assert_eq!(game.pending_draws, 0);
+        assert_eq!(game.discard_pile.len(), 1);
+        for player in &game.players {
+            assert_eq!(player.hand.len(), 7);
+            assert!(player.active);
+        }
+        assert!(game.players[2].is_bot);
+        assert_eq!(game.players[0].stats.rounds_won, 1);
+        assert_eq!(game.deck.len(), 108 - 3 * 7 - 1);
+
+        game.status = GameStatus::Stalemate { winner_id: 1 };
+        game.restart(false).unwrap();
+        assert_eq!(game.players[0].stats.rounds_won, 0);
+    }
+
+    #[test]
+    fn test_lobby_start_needs_two_players() {
+        let mut game =
+            UnoGame::new_lobby(vec!["Alice".to_string()], GameConfig::default(), None).unwrap();
+        assert!(game.start().is_err());
+        assert_eq!(game.status, GameStatus::Lobby);
+    }
+
+    #[test]
+    fn test_seeded_lobby_deals_like_seeded_game() {
+        let names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut lobby = UnoGame::new_lobby(names.clone(), GameConfig::default(), Some(5)).unwrap();
+        lobby.start().unwrap();
+        let seeded = UnoGame::new_with_seed(names, 5).unwrap();
+        assert_eq!(lobby.deck, seeded.deck);
+        assert_eq!(lobby.players[0].hand, seeded.players[0].hand);
+    }
+
+    #[test]
+    fn test_new_with_prepared_top() {
+        let names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let top = vec![
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Blue, CardType::DrawTwo),
+            Card::new(Color::Green, CardType::Reverse),
+        ];
+        let game = UnoGame::new_with_prepared_top(names, top).unwrap();
+
+        assert_eq!(
+            game.discard_pile,
+            vec![(Card::new(Color::Red, CardType::Skip), usize::MAX)]
+        );
+        let next: Vec<&Card> = game.deck.iter().rev().take(2).collect();
+        assert_eq!(
+            next,
+            vec![
+                &Card::new(Color::Blue, CardType::DrawTwo),
+                &Card::new(Color::Green, CardType::Reverse),
+            ]
+        );
+        assert!(game.players.iter().all(|p| p.hand.len() == 7));
+        let total = game.deck.len() + game.discard_pile.len() + 21;
+        assert_eq!(total, 108);
+        game.validate_state().unwrap();
+    }
+
+    #[test]
+    fn test_new_with_prepared_top_rejects_cards_not_in_deck() {
+        let names = vec!["Alice".to_string(), "Bob".to_string()];
+        // A deck has two of each colored action card
+        let three_skips = vec![Card::new(Color::Red, CardType::Skip); 3];
+        assert!(UnoGame::new_with_prepared_top(names.clone(), three_skips).is_err());
+        // Wilds are in the deck uncolored
+        let colored_wild = vec![Card::new(Color::Red, CardType::Wild)];
+        assert!(UnoGame::new_with_prepared_top(names.clone(), colored_wild).is_err());
+        let no_such_number = vec![Card::new(Color::Red, CardType::Number(12))];
+        assert!(UnoGame::new_with_prepared_top(names, no_such_number).is_err());
+    }
+
+    #[test]
+    fn test_event_handler_receives_move_events() {
+        use std::sync::{Arc, Mutex};
+
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(1)),
+            Card::new(Color::Blue, CardType::Number(2)),
+        ];
+        game.players[1].hand = vec![
+            Card::new(Color::Green, CardType::Number(3)),
+            Card::new(Color::Green, CardType::Number(4)),
+        ];
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        game.set_event_handler(move |event| sink.lock().unwrap().push(event.clone()));
+
+        let played = game.play_card(0, 0).unwrap();
+        let drawn = game.draw_card(1).unwrap();
+        // A rejected move produces no event
+        assert!(game.play_card(1, 0).is_err());
+        assert_eq!(*seen.lock().unwrap(), vec![played, drawn]);
+
+        // A copy of the game doesn't report to the handler
+        let mut copy = game.clone();
+        assert_eq!(copy, game);
+        copy.draw_card(0).unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_event_handler_survives_start_and_restart() {
+        use std::sync::{Arc, Mutex};
+
+        let names = vec!["Alice".to_string(), "Bob".to_string()];
+        let mut game = UnoGame::new_lobby(names, GameConfig::default(), None).unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        game.set_event_handler(move |event| sink.lock().unwrap().push(event.clone()));
+
+        game.start().unwrap();
+        let drawn = game.draw_card(game.current_turn).unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec![drawn.clone()]);
+
+        game.status = GameStatus::Complete { winner_id: 0 };
+        game.restart(true).unwrap();
+        let drawn_again = game.draw_card(game.current_turn).unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec![drawn, drawn_again]);
+    }
+
+    #[test]
+    fn test_standings_rank_by_hand_score() {
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        assert_eq!(game.standings(), None);
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.current_turn = 1;
+        game.players[0].hand = vec![
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Blue, CardType::Skip),
+        ];
+        game.players[1].hand = vec![Card::new(Color::Red, CardType::Number(2))];
+        game.players[2].hand = vec![
+            Card::new(Color::Green, CardType::Number(3)),
+            Card::new(Color::Yellow, CardType::Number(6)),
+        ];
+        let event = game.play_card(1, 0).unwrap();
+        assert!(matches!(event, GameEvent::PlayerWins { player_id: 1 }));
+
+        let standings = game.standings().unwrap();
+        let order: Vec<(&str, usize, u32)> = standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.position, s.hand_score))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("Bob", 1, 0), ("Carol", 2, 9), ("Alice", 3, 70)]
+        );
+    }
+
+    #[test]
+    fn test_legal_actions_in_normal_turn() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Number(7)),
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Wild, CardType::Wild),
+        ];
+        assert_eq!(
+            game.legal_actions(),
+            vec![
+                Action::Play { index: 1 },
+                Action::Play { index: 2 },
+                Action::Draw
+            ]
+        );
+
+        game.has_drawn_this_turn = true;
+        assert_eq!(game.legal_actions().last(), Some(&Action::Pass));
+
+        game.status = GameStatus::Complete { winner_id: 1 };
+        assert!(game.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_legal_actions_with_pending_draws() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::DrawTwo),
+            Card::new(Color::Red, CardType::Number(4)),
+        ];
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::DrawTwo), 1)];
+        game.pending_draws = 2;
+        assert_eq!(game.legal_actions(), vec![Action::TakePenalty]);
+
+        game.discard_pile = vec![(Card::new(Color::Blue, CardType::WildDrawFour), 1)];
+        game.pending_draws = 4;
+        game.challengeable_draw_four = Some(WildDrawFourPlay {
+            player_id: 1,
+            previous_color: Color::Red,
+            had_matching_color: false,
+        });
+        assert_eq!(
+            game.legal_actions(),
+            vec![Action::TakePenalty, Action::Challenge]
+        );
+    }
+
+    #[test]
+    fn test_describe_event() {
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Blue, CardType::Number(3)), usize::MAX)];
+        game.players[0].hand = vec![
+            Card::new(Color::Blue, CardType::Skip),
+            Card::new(Color::Red, CardType::Number(1)),
+        ];
+
+        let event = game.play_card(0, 0).unwrap();
+        assert_eq!(
+            game.describe_event(&event),
+            "Alice played Blue Skip, skipping Bob."
+        );
+        assert_eq!(
+            game.describe_event(&GameEvent::Skip { player_id: 1 }),
+            "Bob is skipped."
+        );
+
+        let penalty = GameEvent::DrawTwo {
+            player_id: 2,
+            cards: vec![
+                Card::new(Color::Green, CardType::Number(4)),
+                Card::new(Color::Wild, CardType::Wild),
+            ],
+        };
+        assert_eq!(
+            game.describe_event(&penalty),
+            "Carol draws 2 cards: Green 4, Wild."
+        );
+
+        assert_eq!(
+            game.describe_event(&GameEvent::PlayerWins { player_id: 0 }),
+            "Alice has won the game!"
+        );
+    }
+
+    #[test]
+    fn test_reseat_keeps_hands_and_changes_play_order() {
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), 0)];
+        game.current_turn = 1;
+        let hands: Vec<(String, Vec<Card>)> = game
+            .players
+            .iter()
+            .map(|p| (p.name.clone(), p.hand.clone()))
+            .collect();
+
+        // Carol, then Bob, then Alice
+        game.reseat(vec![2, 1, 0]).unwrap();
+        for (seat, player) in game.players.iter().enumerate() {
+            assert_eq!(player.id, seat);
+            let (_, hand) = hands.iter().find(|(name, _)| *name == player.name).unwrap();
+            assert_eq!(&player.hand, hand);
+        }
+        assert_eq!(game.players[0].name, "Carol");
+        // Alice played the top card and now sits in the last seat
+        assert_eq!(game.discard_pile[0].1, 2);
+        assert_eq!(game.players[game.current_turn].name, "Bob");
+        game.validate_state().unwrap();
+
+        // Play now passes from Bob to Alice instead of Carol
+        game.next_turn();
+        assert_eq!(game.players[game.current_turn].name, "Alice");
+
+        game.reseat(vec![0, 1, 2]).unwrap();
+        let version = game.version;
+        for bad in [vec![0, 1], vec![0, 1, 1], vec![0, 1, 3], vec![0, 1, 2, 3]] {
+            assert!(game.reseat(bad).is_err());
+        }
+        assert_eq!(game.version, version);
+        assert_eq!(game.players[0].name, "Carol");
+    }
+
+    #[test]
+    fn test_peek_top_deck_shows_next_draw() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let deck_size = game.deck.len();
+        let peeked = game.peek_top_deck().cloned().unwrap();
+        assert_eq!(game.deck.len(), deck_size);
+
+        game.draw_card(0).unwrap();
+        assert_eq!(game.players[0].hand.last(), Some(&peeked));
+
+        game.deck.clear();
+        assert_eq!(game.peek_top_deck(), None);
+    }
+
+    #[test]
+    fn test_deck_composition_accounts_for_every_card() {
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let composition = game.deck_composition();
+        assert_eq!(composition.values().sum::<usize>(), game.deck.len());
+        assert_eq!(game.deck.len(), 108 - 2 * 7 - 1);
+
+        // With the cards dealt and flipped added back, it is the standard deck again
+        let mut counts = composition.clone();
+        let dealt = game.players.iter().flat_map(|p| &p.hand);
+        let flipped = game.discard_pile.iter().map(|(card, _)| card);
+        for card in dealt.chain(flipped) {
+            *counts
+                .entry((card.color, card.card_type.clone()))
+                .or_insert(0) += 1;
+        }
+        let mut standard = HashMap::new();
+        for card in UnoGame::unshuffled_deck(&GameConfig::default()) {
+            *standard.entry((card.color, card.card_type)).or_insert(0) += 1;
+        }
+        assert_eq!(counts, standard);
+        assert_eq!(standard[&(Color::Red, CardType::Number(0))], 1);
+        assert_eq!(standard[&(Color::Blue, CardType::Skip)], 2);
+        assert_eq!(standard[&(Color::Wild, CardType::WildDrawFour)], 4);
+    }
+
+    #[test]
+    fn test_cloned_game_is_independent() {
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut snapshot = game.clone();
+        snapshot.draw_card(0).unwrap();
+        snapshot.players[1].hand.clear();
+
+        assert_ne!(snapshot, game);
+        assert_eq!(game.players[0].hand.len(), 7);
+        assert_eq!(game.players[1].hand.len(), 7);
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_is_decided() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        assert_eq!(game.is_decided(), None);
+
+        game.discard_pile = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        game.players[0].hand = vec![Card::new(Color::Red, CardType::Number(1))];
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.status, GameStatus::Complete { winner_id: 0 });
+        assert_eq!(game.is_decided(), Some(0));
+    }
+
+    #[test]
+    fn test_is_decided_when_everyone_else_leaves() {
+        let names = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut game = UnoGame::new(names).unwrap();
+        game.forfeit(0).unwrap();
+        assert_eq!(game.is_decided(), None);
+
+        game.forfeit(2).unwrap();
+        assert_eq!(game.is_decided(), Some(1));
+        assert_eq!(game.status, GameStatus::Complete { winner_id: 1 });
+        assert_eq!(game.players[1].stats.rounds_won, 1);
+    }
+
+    #[test]
+    fn test_version_increments_on_each_change() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        assert_eq!(game.version, 0);
+
+        game.draw_card(0).unwrap();
+        assert_eq!(game.version, 1);
+
+        // A rejected move leaves the version alone
+        assert!(game.play_card(0, 0).is_err());
+        assert_eq!(game.version, 1);
+
+        game.forfeit(0).unwrap();
+        assert_eq!(game.version, 2);
+    }
+
+    #[test]
+    fn test_pass_turn_after_drawing() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.draw_then_play = true;
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        // The drawn card is playable, so Alice keeps the turn and may decline it
+        let event = game.draw_card(0).unwrap();
+        assert!(matches!(event, GameEvent::CardDrawnPlayable { .. }));
+        assert_eq!(game.current_turn, 0);
+
+        let event = game.pass_turn(0).unwrap();
+        assert!(matches!(event, GameEvent::TurnPassed { player_id: 0 }));
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.players[0].hand.len(), 8);
+        assert!(!game.has_drawn_this_turn);
+    }
+
+    #[test]
+    fn test_draw_then_play_allows_one_draw() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.draw_then_play = true;
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        game.deck.push(Card::new(Color::Blue, CardType::Number(2)));
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        let event = game.draw_card(0).unwrap();
+        assert!(matches!(event, GameEvent::CardDrawnPlayable { .. }));
+
+        // Alice can't keep drawing for a better card
+        assert!(matches!(game.draw_card(0), Err(GameError::InvalidMove)));
+        assert_eq!(game.players[0].hand.len(), 8);
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_draw_then_play_only_plays_the_drawn_card() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.draw_then_play = true;
+        game.players[0].hand = vec![
+            Card::new(Color::Red, CardType::Number(1)),
+            Card::new(Color::Green, CardType::Number(3)),
+        ];
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        let GameEvent::CardDrawnPlayable { index, .. } = game.draw_card(0).unwrap() else {
+            panic!("Expected CardDrawnPlayable");
+        };
+        assert_eq!(index, 2);
+        assert_eq!(game.playable_indices(), vec![2]);
+
+        // The Red 1 was playable before the draw, but only the drawn card may follow it
+        assert!(matches!(
+            game.play_card(0, 0),
+            Err(GameError::UnplayableCard(_))
+        ));
+        game.play_card(0, 2).unwrap();
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.drawn_card_index, None);
+    }
+
+    #[test]
+    fn test_pass_turn_requires_a_draw() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+
+        assert!(matches!(game.pass_turn(0), Err(GameError::InvalidMove)));
+        assert!(matches!(game.pass_turn(1), Err(GameError::InvalidMove)));
+        assert_eq!(game.current_turn, 0);
+    }
+
+    #[test]
+    fn test_time_out_turn_draws_and_passes() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.config.draw_then_play = true;
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        game.deck.push(Card::new(Color::Red, CardType::Number(9)));
+
+        // Even a playable card is left in the hand once time is up
+        let events = game.time_out_turn().unwrap();
+        assert!(matches!(events[0], GameEvent::CardDrawnPlayable { .. }));
+        assert!(matches!(events[1], GameEvent::TurnPassed { player_id: 0 }));
+        assert_eq!(game.current_turn, 1);
+        assert_eq!(game.players[0].hand.len(), 8);
+    }
+
+    fn game_after_wild_draw_four(alice_hand: Vec<Card>) -> UnoGame {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        let mut hand = vec![Card::new(Color::Blue, CardType::WildDrawFour)];
+        hand.extend(alice_hand);
+        game.players[0].hand = hand;
+        game.play_card(0, 0).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_successful_wild_draw_four_challenge() {
+        // Alice still held a red card, so the Wild Draw Four was illegal
+        let mut game = game_after_wild_draw_four(vec![
+            Card::new(Color::Red, CardType::Number(2)),
+            Card::new(Color::Green, CardType::Number(4)),
+        ]);
+        assert_eq!(game.current_turn, 1);
+
+        let event = game.challenge_wild_draw_four(1).unwrap();
+        match event {
+            GameEvent::ChallengeResult {
+                success,
+                drawer_id,
+                cards,
+            } => {
+                assert!(success);
+                assert_eq!(drawer_id, 0);
+                assert_eq!(cards.len(), 4);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert_eq!(game.players[0].hand.len(), 6);
+        assert_eq!(game.players[1].hand.len(), 7);
+        assert_eq!(game.pending_draws, 0);
+        // The challenger still takes the turn
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_failed_wild_draw_four_challenge() {
+        let mut game =
+            game_after_wild_draw_four(vec![Card::new(Color::Green, CardType::Number(4))]);
+
+        let event = game.challenge_wild_draw_four(1).unwrap();
+        assert!(matches!(
+            event,
+            GameEvent::ChallengeResult {
+                success: false,
+                drawer_id: 1,
+                ..
+            }
+        ));
+        assert_eq!(game.players[1].hand.len(), 13);
+        assert_eq!(game.pending_draws, 0);
+        assert_eq!(game.current_turn, 0);
+
+        // The same card can't be challenged twice
+        assert!(matches!(
+            game.challenge_wild_draw_four(0),
+            Err(GameError::InvalidMove)
+        ));
+    }
+
+    #[test]
+    fn test_challenge_window_closes_after_drawing() {
+        let mut game = game_after_wild_draw_four(vec![Card::new(Color::Red, CardType::Number(2))]);
+        game.draw_card(1).unwrap();
+
+        assert_eq!(game.players[1].hand.len(), 11);
+        assert!(matches!(
+            game.challenge_wild_draw_four(0),
+            Err(GameError::InvalidMove)
+        ));
+    }
+
+    #[test]
+    fn test_validate_state() {
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        assert!(game.validate_state().is_ok());
+
+        let mut bad_turn = game.clone();
+        bad_turn.current_turn = 2;
+        assert!(bad_turn.validate_state().is_err());
+
+        let mut no_discard = game.clone();
+        no_discard.discard_pile.clear();
+        assert!(no_discard.validate_state().is_err());
+
+        let mut bad_ids = game.clone();
+        bad_ids.players[1].id = 0;
+        assert!(bad_ids.validate_state().is_err());
+
+        let mut bad_winner = game.clone();
+        bad_winner.status = GameStatus::Complete { winner_id: 5 };
+        assert!(bad_winner.validate_state().is_err());
+
+        // An impossible card, e.g. from an edited import
+        let mut bad_card = game.clone();
+        bad_card.players[0].hand[0] = Card::new(Color::Red, CardType::Number(42));
+        assert!(bad_card.validate_state().is_err());
+
+        let mut bad_discard = game;
+        bad_discard
+            .discard_pile
+            .push((Card::new(Color::Red, CardType::Number(1)), 3));
+        assert!(bad_discard.validate_state().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        assert_eq!(game.validate(), Ok(()));
+
+        let mut lost_card = game.clone();
+        lost_card.deck.pop();
+        let err = lost_card.validate().unwrap_err();
+        assert!(err.contains("Expected 108 cards"), "{}", err);
+
+        let mut extra_card = game.clone();
+        extra_card.players[0]
+            .hand
+            .push(Card::new(Color::Red, CardType::Number(1)));
+        assert!(extra_card.validate().is_err());
+
+        let mut bad_penalty = game.clone();
+        bad_penalty.pending_draws = 12;
+        assert!(bad_penalty
+            .validate()
+            .unwrap_err()
+            .contains("pending draws"));
+
+        let mut bad_turn = game;
+        bad_turn.current_turn = 7;
+        assert!(bad_turn.validate().unwrap_err().contains("Current turn"));
+    }
+
+    #[test]
+    fn test_rename_player() {
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bbo".to_string()]).unwrap();
+
+        game.rename_player(1, " Bob ").unwrap();
+        assert_eq!(game.players[1].name, "Bob");
+        assert_eq!(game.version, 1);
+
+        // Keeping your own name in a different case is fine
+        game.rename_player(0, "alice").unwrap();
+
+        assert!(game.rename_player(1, "ALICE").is_err());
+        assert!(game.rename_player(1, "  ").is_err());
+        assert!(game.rename_player(2, "Carol").is_err());
+        assert_eq!(game.players[1].name, "Bob");
+    }
+
+    fn player_with(id: usize, name: &str, hand: Vec<Card>) -> Player {
+        let mut player = Player::new(id, name.to_string());
+        player.hand = hand;
+        player
+    }
+
+    #[test]
+    fn test_from_parts_validates() {
+        let players = vec![
+            player_with(0, "Alice", vec![]),
+            player_with(1, "Bob", vec![]),
+        ];
+        let result = UnoGame::from_parts(players, vec![], vec![], 0, Direction::Clockwise);
+        assert!(result.is_err());
+
+        let players = vec![player_with(0, "Alice", vec![])];
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let result = UnoGame::from_parts(players, vec![], discard, 0, Direction::Clockwise);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_forced_skip() {
+        let players = vec![
+            player_with(
+                0,
+                "Alice",
+                vec![
+                    Card::new(Color::Red, CardType::Skip),
+                    Card::new(Color::Blue, CardType::Number(1)),
+                ],
+            ),
+            player_with(1, "Bob", vec![Card::new(Color::Green, CardType::Number(2))]),
+            player_with(
+                2,
+                "Carol",
+                vec![Card::new(Color::Yellow, CardType::Number(3))],
+            ),
+        ];
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let mut game =
+            UnoGame::from_parts(players, vec![], discard, 0, Direction::Clockwise).unwrap();
+
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.current_turn, 2);
+        assert_eq!(
+            game.discard_pile.last().unwrap().0.card_type,
+            CardType::Skip
+        );
+    }
+
+    #[test]
+    fn test_forced_reshuffle() {
+        use std::sync::{Arc, Mutex};
+
+        let players = vec![
+            player_with(
+                0,
+                "Alice",
+                vec![Card::new(Color::Blue, CardType::Number(1))],
+            ),
+            player_with(1, "Bob", vec![Card::new(Color::Green, CardType::Number(2))]),
+        ];
+        let discard = vec![
+            (Card::new(Color::Yellow, CardType::Number(7)), usize::MAX),
+            (Card::new(Color::Yellow, CardType::Number(8)), 1),
+            (Card::new(Color::Red, CardType::Number(8)), 0),
+        ];
+        let mut game =
+            UnoGame::from_parts(players, vec![], discard, 0, Direction::Clockwise).unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        game.set_event_handler(move |event| sink.lock().unwrap().push(event.clone()));
+
+        let drawn = game.draw_card(0).unwrap();
+        // The reshuffle is reported before the draw that caused it
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![GameEvent::DeckReshuffled { cards_recycled: 2 }, drawn]
+        );
+        assert_eq!(
+            game.describe_event(&GameEvent::DeckReshuffled { cards_recycled: 2 }),
+            "The deck ran out; 2 cards from the discard pile shuffled back in."
+        );
+        // Everything but the top card was recycled, and one of those was drawn
+        assert_eq!(game.players[0].hand.len(), 2);
+        assert_eq!(game.deck.len(), 1);
+        assert_eq!(
+            game.discard_pile,
+            vec![(Card::new(Color::Red, CardType::Number(8)), 0)]
+        );
+    }
+
+    #[test]
+    fn test_next_turn_skips_inactive_player() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.players[1].active = false;
+
+        game.next_turn();
+        assert_eq!(game.current_turn, 2);
+
+        game.reverse_direction();
+        game.next_turn();
+        assert_eq!(game.current_turn, 0);
+        game.next_turn();
+        assert_eq!(game.current_turn, 2);
+
+        // A Skip from Charlie passes over the inactive Bob to skip Alice
+        game.direction = Direction::Clockwise;
+        game.discard_pile
+            .push((Card::new(Color::Red, CardType::Number(5)), usize::MAX));
+        game.players[2]
+            .hand
+            .insert(0, Card::new(Color::Red, CardType::Skip));
+        game.play_card(2, 0).unwrap();
+        assert_eq!(game.current_turn, 2);
+    }
+
+    #[test]
+    fn test_next_turn_with_one_active_player() {
+        let player_names = vec![
+            "Alice".to_string(),
+            "Bob".to_string(),
+            "Charlie".to_string(),
+        ];
+        let mut game = UnoGame::new(player_names).unwrap();
+        game.players[0].active = false;
+        game.players[2].active = false;
+        game.current_turn = 1;
+
+        // Terminates and stays on the only player left
+        game.next_turn();
+        assert_eq!(game.current_turn, 1);
+    }
+
+    #[test]
+    fn test_render_board() {
+        let mut alice = Player::new(0, "Alice".to_string());
+        alice.hand = vec![Card::new(Color::Blue, CardType::Number(1)); 3];
+        let mut bob = Player::new(1, "Bob".to_string());
+        bob.hand = vec![Card::new(Color::Green, CardType::Number(2)); 2];
+        let mut carol = Player::new(2, "Carol".to_string());
+        carol.active = false;
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let deck = vec![Card::new(Color::Yellow, CardType::Number(9)); 4];
+        let mut game = UnoGame::from_parts(
+            vec![alice, bob, carol],
+            deck,
+            discard,
+            1,
+            Direction::CounterClockwise,
+        )
+        .unwrap();
+        game.pending_draws = 2;
+
+        assert_eq!(
+            game.render_board(),
+            "Direction: CounterClockwise <-\n\
+             Top card: Red 5\n\
+             Deck: 4 cards\n\
+             Pending draws: 2\n\
+             Players:\n  \
+             Alice (3 cards)\n\
+             > Bob (2 cards)\n  \
+             Carol (left)\n"
+        );
+    }
+
+    #[test]
+    fn test_player_stats() {
+        let players = vec![
+            player_with(
+                0,
+                "Alice",
+                vec![
+                    Card::new(Color::Red, CardType::Skip),
+                    Card::new(Color::Blue, CardType::Wild),
+                    Card::new(Color::Blue, CardType::Number(3)),
+                    Card::new(Color::Green, CardType::Number(8)),
+                ],
+            ),
+            player_with(
+                1,
+                "Bob",
+                vec![Card::new(Color::Yellow, CardType::Number(1))],
+            ),
+        ];
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let deck = vec![Card::new(Color::Yellow, CardType::Number(9)); 5];
+        let mut game =
+            UnoGame::from_parts(players, deck, discard, 0, Direction::Clockwise).unwrap();
+
+        // Skip brings the turn straight back to Alice, who then plays a wild
+        game.play_card(0, 0).unwrap();
+        game.play_card(0, 0).unwrap();
+        game.draw_card(1).unwrap();
+        game.play_card(0, 0).unwrap();
+
+        let alice = &game.players[0].stats;
+        assert_eq!(alice.cards_played, 3);
+        assert_eq!(alice.skips_dealt, 1);
+        assert_eq!(alice.wilds_played, 1);
+        assert_eq!(alice.cards_drawn, 0);
+        assert_eq!(game.players[1].stats.cards_drawn, 1);
+        assert_eq!(game.players[1].stats.cards_played, 0);
+    }
+
+    #[test]
+    fn test_player_stats_count_wins() {
+        let players = vec![
+            player_with(0, "Alice", vec![Card::new(Color::Red, CardType::Number(1))]),
+            player_with(
+                1,
+                "Bob",
+                vec![Card::new(Color::Yellow, CardType::Number(1))],
+            ),
+        ];
+        let discard = vec![(Card::new(Color::Red, CardType::Number(5)), usize::MAX)];
+        let mut game =
+            UnoGame::from_parts(players, vec![], discard, 0, Direction::Clockwise).unwrap();
+
+        game.play_card(0, 0).unwrap();
+        assert_eq!(game.players[0].stats.rounds_won, 1);
+        assert_eq!(game.players[1].stats.rounds_won, 0);
+    }
+
+    #[test]
+    fn test_player_without_stats_deserializes() {
+        let json = r#"{"id":0,"name":"Alice","hand":[]}"#;
+        let player: Player = serde_json::from_str(json).unwrap();
+        assert_eq!(player.stats, PlayerStats::default());
+    }
 }
@@ -0,0 +1,87 @@
+use super::game::UnoGame;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Server-wide counters, rendered in the Prometheus text format by `GET /metrics`.
+/// Clones share the same counters.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    games_created: Arc<AtomicU64>,
+    cards_played: Arc<AtomicU64>,
+    games_completed: Arc<AtomicU64>,
+}
+
+impl Metrics {
+    pub fn game_created(&self) {
+        self.games_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn card_played(&self) {
+        self.cards_played(1);
+    }
+
+    pub fn cards_played(&self, count: u64) {
+        self.cards_played.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Call after a move has been applied. Moves are refused once a game is over, so a
+    /// finished game here is one the move just finished.
+    pub fn move_applied(&self, game: &UnoGame) {
+        if game.status.is_over() {
+            self.games_completed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Renders every metric; `active_sessions` is sampled by the caller at scrape time.
+    pub fn render(&self, active_sessions: usize) -> String {
+        let mut out = String::new();
+        let counters = [
+            (
+                "uno_games_created_total",
+                "Games created since the server started.",
+                &self.games_created,
+            ),
+            (
+                "uno_cards_played_total",
+                "Cards played since the server started.",
+                &self.cards_played,
+            ),
+            (
+                "uno_games_completed_total",
+                "Games won or stalemated since the server started.",
+                &self.games_completed,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        }
+        let _ = writeln!(out, "# HELP uno_active_sessions Saved game sessions.");
+        let _ = writeln!(out, "# TYPE uno_active_sessions gauge");
+        let _ = writeln!(out, "uno_active_sessions {}", active_sessions);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_completed_games_only() {
+        let metrics = Metrics::default();
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        metrics.game_created();
+        metrics.move_applied(&game);
+        game.forfeit(1).unwrap();
+        metrics.move_applied(&game);
+
+        let text = metrics.render(3);
+        assert!(text.contains("uno_games_created_total 1\n"));
+        assert!(text.contains("uno_cards_played_total 0\n"));
+        assert!(text.contains("uno_games_completed_total 1\n"));
+        assert!(text.contains("# TYPE uno_active_sessions gauge\nuno_active_sessions 3\n"));
+    }
+}
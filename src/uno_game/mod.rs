@@ -1,13 +1,27 @@
 pub mod api;
+pub mod auth;
+pub mod bot;
 pub mod card;
+pub mod clock;
+pub mod config;
 pub mod controller;
 pub mod game;
+pub mod metrics;
+pub mod openapi;
 pub mod player;
+pub mod replay;
+pub mod script;
 pub mod session;
+pub mod shuffle;
+pub mod store;
 pub mod ui;
 
-pub use api::start_api_server;
+pub use api::{start_api_server, CleanupConfig, ServerConfig};
 pub use card::{Card, CardType, Color};
-pub use game::{Direction, GameError, GameEvent, UnoGame};
-pub use player::Player;
-pub use session::{GameSession, SessionManager};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use config::GameConfig;
+pub use game::{Action, Direction, GameError, GameEvent, ReshuffleLog, Standing, UnoGame};
+pub use player::{Player, PlayerStats};
+pub use session::{GameSession, SessionManager, SessionQuery, SessionSummary, StatusFilter};
+pub use shuffle::{NoopShuffler, RandomShuffler, SeededShuffler, Shuffler};
+pub use store::{FileSessionStore, LayeredSessionStore, MemorySessionStore, SessionStore};
@@ -0,0 +1,1419 @@
+//! Hand-maintained OpenAPI 3 description of the HTTP API in [`super::api`].
+//!
+//! The schemas mirror the serde output of the request and response types; the
+//! tests in `api.rs` compare them against real responses so the two stay in sync.
+
+use serde_json::{json, Value};
+
+/// Builds the OpenAPI document served at `GET /openapi.json`.
+pub fn spec() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "UNO Game API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths(),
+        "components": {
+            "schemas": schemas(),
+            "parameters": {
+                "GameId": {
+                    "name": "id",
+                    "in": "path",
+                    "required": true,
+                    "description": "Game session id",
+                    "schema": { "type": "string" },
+                },
+                "IfMatch": {
+                    "name": "If-Match",
+                    "in": "header",
+                    "required": true,
+                    "description": "The ETag of the state this move is based on, or `*`",
+                    "schema": { "type": "string" },
+                },
+            },
+            "securitySchemes": {
+                "PlayerToken": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "The token issued to the player when the game was created or they joined",
+                },
+                "AdminToken": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "The token the server was started with as --admin-token",
+                },
+            },
+            "responses": {
+                "BadRequest": error_response("The request or move was rejected"),
+                "NotFound": error_response("No game with this id"),
+                "Unauthorized": error_response("The player's token is missing"),
+                "Forbidden": error_response("The server does not allow this, or the token is another player's"),
+                "ServerError": error_response("The game could not be read or saved"),
+                "Conflict": error_response("The game has changed since the If-Match version"),
+                "PreconditionRequired": error_response("The If-Match header is missing"),
+                "ServerFull": server_full(),
+            },
+        },
+    })
+}
+
+/// Minimal Swagger UI page pointing at the spec.
+pub const DOCS_HTML: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+  <title>UNO Game API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });</script>
+</body>
+</html>
+"##;
+
+fn paths() -> Value {
+    let id = json!({ "$ref": "#/components/parameters/GameId" });
+    let if_match = json!({ "$ref": "#/components/parameters/IfMatch" });
+    let mut paths = json!({
+        "/health": {
+            "get": {
+                "summary": "Liveness check",
+                "responses": {
+                    "200": json_response("The server is up", schema_ref("HealthResponse")),
+                },
+            },
+        },
+        "/ready": {
+            "get": {
+                "summary": "Readiness check: the server can also save games",
+                "responses": {
+                    "200": json_response("Ready", schema_ref("ReadyResponse")),
+                    "503": json_response("Games cannot be saved", schema_ref("ReadyResponse")),
+                },
+            },
+        },
+        "/metrics": {
+            "get": {
+                "summary": "Server counters in the Prometheus text format",
+                "responses": {
+                    "200": {
+                        "description": "Games created and completed, cards played and saved sessions",
+                        "content": { "text/plain": { "schema": { "type": "string" } } },
+                    },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games": {
+            "post": {
+                "summary": "Create a new game",
+                "requestBody": json_body("CreateGameRequest"),
+                "responses": {
+                    "201": json_response("The new game", schema_ref("GameResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                    "503": { "$ref": "#/components/responses/ServerFull" },
+                },
+            },
+            "get": {
+                "summary": "List games",
+                "parameters": [{
+                    "name": "detailed",
+                    "in": "query",
+                    "required": false,
+                    "description": "Return summaries instead of bare ids",
+                    "schema": { "type": "boolean", "default": false },
+                }, {
+                    "name": "status",
+                    "in": "query",
+                    "required": false,
+                    "description": "Only games with this status",
+                    "schema": {
+                        "type": "string",
+                        "enum": ["lobby", "in_progress", "complete", "stalemate"],
+                    },
+                }, {
+                    "name": "player",
+                    "in": "query",
+                    "required": false,
+                    "description": "Only games with a player whose name contains this, ignoring case",
+                    "schema": { "type": "string" },
+                }, {
+                    "name": "limit",
+                    "in": "query",
+                    "required": false,
+                    "description": "Games per page",
+                    "schema": { "type": "integer", "minimum": 1, "maximum": 200, "default": 50 },
+                }, {
+                    "name": "offset",
+                    "in": "query",
+                    "required": false,
+                    "description": "Games to skip, usually the previous page's `next_offset`",
+                    "schema": { "type": "integer", "minimum": 0, "default": 0 },
+                }],
+                "responses": {
+                    "200": json_response(
+                        "One page of games, most recently updated first",
+                        schema_ref("GamePage"),
+                    ),
+                    "400": {
+                        "description": "Unknown status",
+                        "content": { "text/plain": { "schema": { "type": "string" } } },
+                    },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/import": {
+            "post": {
+                "summary": "Recreate an exported game under a new id",
+                "requestBody": json_body("GameSession"),
+                "responses": {
+                    "201": json_response("The imported game", schema_ref("GameResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                    "503": { "$ref": "#/components/responses/ServerFull" },
+                },
+            },
+        },
+        "/games/import-replay": {
+            "post": {
+                "summary": "Rebuild a seeded game from its recorded events",
+                "description": "Deals the game from `seed` and re-applies each event as a move. Fails with `INVALID_REPLAY` naming the first step that is illegal or gives a different result.",
+                "requestBody": json_body("ReplayRequest"),
+                "responses": {
+                    "201": json_response("The replayed game, saved under a new id", schema_ref("ReplayResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                    "503": { "$ref": "#/components/responses/ServerFull" },
+                },
+            },
+        },
+        "/games/{id}/export": {
+            "parameters": [id],
+            "get": {
+                "summary": "Export the complete saved game",
+                "responses": {
+                    "200": json_response("The saved session", schema_ref("GameSession")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}": {
+            "parameters": [id],
+            "get": {
+                "summary": "Get a game overview",
+                "responses": {
+                    "200": json_response("The game", schema_ref("GameResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+            "delete": {
+                "summary": "Delete a game",
+                "security": [{ "AdminToken": [] }],
+                "responses": {
+                    "204": { "description": "Deleted" },
+                    "401": { "$ref": "#/components/responses/Unauthorized" },
+                    "403": error_response("Admin routes are off, or the token isn't the admin token"),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/state": {
+            "parameters": [id],
+            "get": {
+                "summary": "Get the full game state, including every hand",
+                "responses": {
+                    "200": json_response("The game state", schema_ref("GameStateResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/events": {
+            "parameters": [id],
+            "get": {
+                "summary": "Stream the game state after every change",
+                "parameters": [{
+                    "name": "player_id",
+                    "in": "query",
+                    "required": false,
+                    "description": "Hide every hand except this player's",
+                    "schema": { "type": "integer", "minimum": 0 },
+                }],
+                "responses": {
+                    "200": {
+                        "description": "Server-sent `state` events, each carrying a GameStateResponse",
+                        "content": { "text/event-stream": { "schema": { "type": "string" } } },
+                    },
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/board": {
+            "parameters": [id],
+            "get": {
+                "summary": "A plain-text summary of the table",
+                "responses": {
+                    "200": {
+                        "description": "Direction, top card, deck size, pending draws and hand sizes",
+                        "content": { "text/plain": { "schema": { "type": "string" } } },
+                    },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/public": {
+            "parameters": [id],
+            "get": {
+                "summary": "The game as a spectator sees it, with every hand hidden",
+                "responses": {
+                    "200": json_response("Public game state", schema_ref("PublicStateResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/stats": {
+            "parameters": [id],
+            "get": {
+                "summary": "Per-player counters for the match",
+                "responses": {
+                    "200": json_response(
+                        "One entry per player, in seat order",
+                        json!({ "type": "array", "items": schema_ref("PlayerStatsResponse") }),
+                    ),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/current": {
+            "parameters": [id],
+            "get": {
+                "summary": "The game from the point of view of the player who must act",
+                "responses": {
+                    "200": json_response("The current player's hand, with playable cards flagged, and their options", schema_ref("CurrentPlayerResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/deck": {
+            "parameters": [id],
+            "get": {
+                "summary": "List the cards left in the deck",
+                "parameters": [{
+                    "name": "reversed",
+                    "in": "query",
+                    "required": false,
+                    "description": "List the next card to be drawn first instead of last",
+                    "schema": { "type": "boolean", "default": false },
+                }],
+                "responses": {
+                    "200": json_response(
+                        "The deck, bottom first (the last card is drawn next) unless `reversed=true`",
+                        schema_ref("DeckResponse"),
+                    ),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/discard": {
+            "parameters": [id],
+            "get": {
+                "summary": "List the discard pile",
+                "responses": {
+                    "200": json_response("The discard pile, bottom first", schema_ref("DiscardPileResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/hints": {
+            "parameters": [id],
+            "get": {
+                "summary": "List the current player's playable cards",
+                "responses": {
+                    "200": json_response("Playable cards", schema_ref("HintsResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/play": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Play a card from the current player's hand",
+                "requestBody": json_body("PlayCardRequest"),
+                "responses": {
+                    "200": json_response("What happened", schema_ref("GameEvent")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/join": {
+            "parameters": [id],
+            "post": {
+                "summary": "Add a player to a game waiting in the lobby",
+                "requestBody": json_body("JoinGameRequest"),
+                "responses": {
+                    "200": json_response("The new player's id and token, and the game state", schema_ref("JoinGameResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/start": {
+            "parameters": [id],
+            "post": {
+                "summary": "Deal the cards for a game waiting in the lobby",
+                "description": "Fails unless the lobby has between 2 and 10 players.",
+                "responses": {
+                    "200": json_response("The dealt game", schema_ref("GameStateResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/advance-bots": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Play every bot turn until a human must act or the game ends",
+                "responses": {
+                    "200": json_response("The bot moves made, in order, and the state after them", schema_ref("AdvanceBotsResponse")),
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/simulate": {
+            "parameters": [id],
+            "post": {
+                "summary": "Try a move for the current player without saving it",
+                "description": "Applies the move to a copy of the game. Illegal moves fail with the same codes as `/play` and `/draw`.",
+                "requestBody": json_body("SimulateRequest"),
+                "responses": {
+                    "200": json_response("What would happen", schema_ref("SimulateResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                },
+            },
+        },
+        "/games/{id}/draw": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Draw for the current player",
+                "responses": {
+                    "200": json_response("What happened", schema_ref("GameEvent")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/take-penalty": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Take the pending Draw Two or Wild Draw Four penalty",
+                "description": "Draws the pending cards for the current player and ends their turn. Fails with `NO_PENALTY` when nothing is pending.",
+                "responses": {
+                    "200": json_response("The cards drawn and the top card", schema_ref("PenaltyResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/pass": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "End the current player's turn after they have drawn",
+                "responses": {
+                    "200": json_response("What happened", schema_ref("GameEvent")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/challenge": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Challenge the Wild Draw Four just played on the current player",
+                "responses": {
+                    "200": json_response("The outcome", schema_ref("GameEvent")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/color": {
+            "parameters": [id, if_match],
+            "post": {
+                "summary": "Set the color of the top discard",
+                "requestBody": json_body("ChooseColorRequest"),
+                "responses": {
+                    "200": { "description": "Color set" },
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "409": { "$ref": "#/components/responses/Conflict" },
+                    "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/players/{player_id}": {
+            "parameters": [id, {
+                "name": "player_id",
+                "in": "path",
+                "required": true,
+                "schema": { "type": "integer", "minimum": 0 },
+            }],
+            "patch": {
+                "summary": "Rename a player",
+                "requestBody": json_body("RenamePlayerRequest"),
+                "responses": {
+                    "200": json_response("The updated game state", schema_ref("GameStateResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/players/{player_id}/redeal": {
+            "parameters": [id, {
+                "name": "player_id",
+                "in": "path",
+                "required": true,
+                "schema": { "type": "integer", "minimum": 0 },
+            }],
+            "post": {
+                "summary": "Return a player's hand to the deck and deal them a fresh one",
+                "description": "A house rule; only allowed when the server is started with `--allow-redeal`.",
+                "responses": {
+                    "200": json_response("The updated game state", schema_ref("GameStateResponse")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "403": { "$ref": "#/components/responses/Forbidden" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+        "/games/{id}/forfeit": {
+            "parameters": [id],
+            "post": {
+                "summary": "Remove a player from the game",
+                "requestBody": json_body("ForfeitRequest"),
+                "responses": {
+                    "200": json_response("What happened", schema_ref("GameEvent")),
+                    "400": { "$ref": "#/components/responses/BadRequest" },
+                    "404": { "$ref": "#/components/responses/NotFound" },
+                    "500": { "$ref": "#/components/responses/ServerError" },
+                },
+            },
+        },
+    });
+    paths["/games/{id}/restart"] = json!({
+        "parameters": [id],
+        "post": {
+            "summary": "Deal a finished game again to the same players",
+            "parameters": [{
+                "name": "reset_stats",
+                "in": "query",
+                "required": false,
+                "description": "Clear the players' running stats instead of keeping them",
+                "schema": { "type": "boolean", "default": false },
+            }],
+            "responses": {
+                "200": json_response("The new round's state", schema_ref("GameStateResponse")),
+                "400": { "$ref": "#/components/responses/BadRequest" },
+                "404": { "$ref": "#/components/responses/NotFound" },
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/games/{id}/kick"] = json!({
+        "parameters": [id, if_match],
+        "post": {
+            "summary": "Remove a stalling player on a moderator's behalf",
+            "security": [{ "AdminToken": [] }],
+            "requestBody": json_body("KickRequest"),
+            "responses": {
+                "200": json_response("`PlayerKicked`, or `PlayerWins` if one player is left", schema_ref("GameEvent")),
+                "400": { "$ref": "#/components/responses/BadRequest" },
+                "401": { "$ref": "#/components/responses/Unauthorized" },
+                "403": error_response("Admin routes are off, or the token isn't the admin token"),
+                "404": { "$ref": "#/components/responses/NotFound" },
+                "409": { "$ref": "#/components/responses/Conflict" },
+                "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/games/{id}/players/{player_id}/sort"] = json!({
+        "parameters": [id, {
+            "name": "player_id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "integer", "minimum": 0 },
+        }],
+        "post": {
+            "summary": "Sort a player's hand by color and card type",
+            "description": "Card indices change, so the game's version (and ETag) changes too.",
+            "responses": {
+                "200": json_response("The updated game state", schema_ref("GameStateResponse")),
+                "404": { "$ref": "#/components/responses/NotFound" },
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/games/{id}/deck/composition"] = json!({
+        "parameters": [id],
+        "get": {
+            "summary": "Count the cards left in the deck by color and card type",
+            "responses": {
+                "200": json_response("The counts", schema_ref("DeckCompositionResponse")),
+                "404": { "$ref": "#/components/responses/NotFound" },
+            },
+        },
+    });
+    paths["/games/{id}/moves"] = json!({
+        "parameters": [id, if_match],
+        "post": {
+            "summary": "Make several moves at once, all or nothing",
+            "description": "The moves are tried in order on a copy of the game, which is saved only if they all succeed. The first failure is returned, its message naming the move's position, and the game is left unchanged.",
+            "requestBody": json_body("MovesRequest"),
+            "responses": {
+                "200": json_response("The events in order and the state after them", schema_ref("MovesResponse")),
+                "400": { "$ref": "#/components/responses/BadRequest" },
+                "404": { "$ref": "#/components/responses/NotFound" },
+                "409": { "$ref": "#/components/responses/Conflict" },
+                "428": { "$ref": "#/components/responses/PreconditionRequired" },
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/games/{id}/config"] = json!({
+        "parameters": [id],
+        "get": {
+            "summary": "Get the house rules the game is played with",
+            "responses": {
+                "200": json_response("The game's config", schema_ref("GameConfig")),
+                "404": { "$ref": "#/components/responses/NotFound" },
+            },
+        },
+    });
+    paths["/games/{id}/deck/next"] = json!({
+        "parameters": [id],
+        "get": {
+            "summary": "Look at the next card to be drawn without drawing it",
+            "description": "Only for games whose config sets `allow_deck_peek`.",
+            "responses": {
+                "200": json_response("The next card", schema_ref("CardResponse")),
+                "403": { "$ref": "#/components/responses/Forbidden" },
+                "404": { "$ref": "#/components/responses/NotFound" },
+            },
+        },
+    });
+    paths["/games/{id}/players/{player_id}/hand/{index}"] = json!({
+        "parameters": [id, {
+            "name": "player_id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "integer", "minimum": 0 },
+        }, {
+            "name": "index",
+            "in": "path",
+            "required": true,
+            "description": "Position of the card in the player's hand",
+            "schema": { "type": "integer", "minimum": 0 },
+        }],
+        "get": {
+            "summary": "Describe one card in a player's hand",
+            "description": "Needs the player's token, or the admin token, if the game issued tokens.",
+            "security": [{ "PlayerToken": [] }, { "AdminToken": [] }],
+            "responses": {
+                "200": json_response("The card", schema_ref("CardDetailResponse")),
+                "401": { "$ref": "#/components/responses/Unauthorized" },
+                "403": { "$ref": "#/components/responses/Forbidden" },
+                "404": { "$ref": "#/components/responses/NotFound" },
+            },
+        },
+    });
+    paths["/games"]["delete"] = json!({
+        "summary": "Delete every game with a given status",
+        "parameters": [{
+            "name": "status",
+            "in": "query",
+            "required": true,
+            "description": "Only games with this status are deleted",
+            "schema": {
+                "type": "string",
+                "enum": ["lobby", "in_progress", "complete", "stalemate"],
+            },
+        }, {
+            "name": "player",
+            "in": "query",
+            "required": false,
+            "description": "Only games with a player whose name contains this, ignoring case",
+            "schema": { "type": "string" },
+        }],
+        "security": [{ "AdminToken": [] }],
+        "responses": {
+            "200": json_response("The ids of the deleted games", schema_ref("PurgeGamesResponse")),
+            "400": { "$ref": "#/components/responses/BadRequest" },
+            "401": { "$ref": "#/components/responses/Unauthorized" },
+            "403": error_response("Admin routes are off, or the token isn't the admin token"),
+            "500": { "$ref": "#/components/responses/ServerError" },
+        },
+    });
+    paths["/games/{id}/state/at/{move_number}"] = json!({
+        "parameters": [id, {
+            "name": "move_number",
+            "in": "path",
+            "required": true,
+            "description": "How many recorded moves to replay; 0 is the deal",
+            "schema": { "type": "integer", "minimum": 0 },
+        }],
+        "get": {
+            "summary": "Get the full game state as it was after a number of moves",
+            "responses": {
+                "200": json_response("The game state after that move", schema_ref("GameStateResponse")),
+                "404": error_response("No game with this id, or it has fewer recorded moves"),
+                "409": error_response("The recorded moves can't be replayed"),
+            },
+        },
+    });
+    paths["/players/{name}/games"] = json!({
+        "parameters": [{
+            "name": "name",
+            "in": "path",
+            "required": true,
+            "description": "The player's name, ignoring case",
+            "schema": { "type": "string" },
+        }],
+        "get": {
+            "summary": "List the unfinished games a player is in, those waiting on them first",
+            "responses": {
+                "200": json_response("The player's games", schema_ref("PlayerGamesResponse")),
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/games/{id}/reload"] = json!({
+        "parameters": [id],
+        "post": {
+            "summary": "Drop the cached copy of a game and serve it as stored",
+            "security": [{ "AdminToken": [] }],
+            "responses": {
+                "200": json_response("The game state as stored", schema_ref("GameStateResponse")),
+                "401": { "$ref": "#/components/responses/Unauthorized" },
+                "403": error_response("Admin routes are off, or the token isn't the admin token"),
+                "404": { "$ref": "#/components/responses/NotFound" },
+                "500": { "$ref": "#/components/responses/ServerError" },
+            },
+        },
+    });
+    paths["/admin/reload-all"] = json!({
+        "post": {
+            "summary": "Drop every cached game, so each is read from the store when next used",
+            "security": [{ "AdminToken": [] }],
+            "responses": {
+                "200": json_response("How many games were dropped", schema_ref("ReloadAllResponse")),
+                "401": { "$ref": "#/components/responses/Unauthorized" },
+                "403": error_response("Admin routes are off, or the token isn't the admin token"),
+            },
+        },
+    });
+    // Moves made for a player need that player's token, and starting or restarting the game
+    // any seated player's, if the game issued them
+    for (path, method) in [
+        ("/games/{id}/play", "post"),
+        ("/games/{id}/draw", "post"),
+        ("/games/{id}/take-penalty", "post"),
+        ("/games/{id}/pass", "post"),
+        ("/games/{id}/challenge", "post"),
+        ("/games/{id}/color", "post"),
+        ("/games/{id}/moves", "post"),
+        ("/games/{id}/forfeit", "post"),
+        ("/games/{id}/players/{player_id}", "patch"),
+        ("/games/{id}/players/{player_id}/sort", "post"),
+        ("/games/{id}/players/{player_id}/redeal", "post"),
+        ("/games/{id}/start", "post"),
+        ("/games/{id}/restart", "post"),
+    ] {
+        let operation = &mut paths[path][method];
+        operation["security"] = json!([{ "PlayerToken": [] }]);
+        operation["responses"]["401"] = json!({ "$ref": "#/components/responses/Unauthorized" });
+        operation["responses"]["403"] = json!({ "$ref": "#/components/responses/Forbidden" });
+    }
+    paths
+}
+
+/// Every `code` an `ApiError` body can carry.
+const ERROR_CODES: &[&str] = &[
+    "NOT_FOUND",
+    "INVALID_MOVE",
+    "NOT_YOUR_TURN",
+    "CARD_NOT_IN_HAND",
+    "GAME_ALREADY_OVER",
+    "GAME_NOT_STARTED",
+    "EMPTY_DECK",
+    "GAME_ERROR",
+    "INVALID_COLOR",
+    "COLOR_REQUIRED",
+    "NO_DISCARD",
+    "INVALID_PLAYER",
+    "VERSION_REQUIRED",
+    "VERSION_CONFLICT",
+    "INVALID_REPLAY",
+    "NO_PENALTY",
+    "REDEAL_DISABLED",
+    "STATUS_REQUIRED",
+    "PEEK_DISABLED",
+    "TOKEN_REQUIRED",
+    "INVALID_TOKEN",
+    "SERVER_FULL",
+    "HISTORY_DIVERGED",
+    "ADMIN_DISABLED",
+    "INTERNAL_ERROR",
+];
+
+fn schemas() -> Value {
+    let index = json!({ "type": "integer", "minimum": 0 });
+    let status = json!({
+        "type": "string",
+        "enum": ["Lobby", "In Progress", "Complete", "Stalemate"],
+    });
+    let winner = json!({
+        "allOf": [schema_ref("WinnerResponse")],
+        "nullable": true,
+        "description": "Set once the game is Complete or a Stalemate",
+    });
+    let top_card = json!({
+        "allOf": [schema_ref("TopCardResponse")],
+        "nullable": true,
+        "description": "Null in the lobby, before the first card is turned over",
+    });
+    let indexed_card = json!({
+        "type": "array",
+        "description": "[hand index, card]",
+        "items": { "oneOf": [index, schema_ref("CardResponse")] },
+        "minItems": 2,
+        "maxItems": 2,
+    });
+    let mut schemas = json!({
+        "CreateGameRequest": object(json!({
+            "player_names": { "type": "array", "items": { "type": "string" } },
+            "seed": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Deal reproducibly, so the game can be replayed later",
+            },
+            "lobby": {
+                "type": "boolean",
+                "description": "Wait in the lobby for more players instead of dealing now",
+            },
+            "bots": {
+                "type": "array",
+                "items": index,
+                "description": "Ids of the players the server plays for with advance-bots",
+            },
+            "config": schema_ref("GameConfig"),
+        }), &["player_names"]),
+        "PlayCardRequest": object(json!({
+            "card_index": index,
+            "color": {
+                "type": "string",
+                "nullable": true,
+                "description": "Required when playing a wild card",
+            },
+            "target_player_id": {
+                "type": "integer",
+                "minimum": 0,
+                "nullable": true,
+                "description": "Player to swap hands with when playing a 7 under Seven-Zero",
+            },
+        }), &["card_index"]),
+        "ChooseColorRequest": object(json!({
+            "color": { "type": "string", "enum": ["Red", "Blue", "Green", "Yellow"] },
+        }), &["color"]),
+        "RenamePlayerRequest": object(json!({ "name": { "type": "string" } }), &["name"]),
+        "ForfeitRequest": object(json!({ "player_id": index }), &["player_id"]),
+        "KickRequest": object(json!({ "player_id": index }), &["player_id"]),
+        "CardResponse": object(json!({
+            "color": { "type": "string", "enum": ["Red", "Blue", "Green", "Yellow", "Wild"] },
+            "card_type": {
+                "type": "string",
+                "description": "`Number(n)`, `Skip`, `Reverse`, `DrawTwo`, `Wild` or `WildDrawFour`",
+            },
+            "player_id": { "type": "integer", "minimum": 0 },
+        }), &["color", "card_type"]),
+        "WinnerResponse": object(json!({
+            "id": index,
+            "name": { "type": "string" },
+        }), &["id", "name"]),
+        "PlayerResponse": object(json!({
+            "id": index,
+            "name": { "type": "string" },
+            "hand_size": index,
+            "active": { "type": "boolean" },
+            "is_bot": { "type": "boolean" },
+        }), &["id", "name", "hand_size", "active", "is_bot"]),
+        "PlayerStateResponse": object(json!({
+            "id": index,
+            "name": { "type": "string" },
+            "hand": { "type": "array", "items": indexed_card },
+            "hand_size": index,
+            "active": { "type": "boolean" },
+            "is_bot": { "type": "boolean" },
+            "stats": schema_ref("PlayerStats"),
+        }), &["id", "name", "hand", "hand_size", "active", "is_bot", "stats"]),
+        "GameResponse": object(json!({
+            "id": { "type": "string" },
+            "current_turn": index,
+            "players": { "type": "array", "items": schema_ref("PlayerResponse") },
+            "discard_pile_top": top_card,
+            "deck_cards_remaining": index,
+            "pending_draws": index,
+            "pending_skips": index,
+            "status": status,
+            "winner": winner,
+            "standings": {
+                "type": "array",
+                "nullable": true,
+                "items": schema_ref("Standing"),
+                "description": "The finishing order once the game is over, winner first; null until then",
+            },
+            "tokens": {
+                "type": "object",
+                "nullable": true,
+                "additionalProperties": { "type": "string" },
+                "description": "Each player's token, keyed by player id; only sent when the game is created",
+            },
+        }), &["id", "current_turn", "players", "discard_pile_top", "deck_cards_remaining",
+              "pending_draws", "pending_skips", "status", "winner", "standings", "tokens"]),
+        "GameStateResponse": object(json!({
+            "id": { "type": "string" },
+            "version": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Incremented on every change; also sent as the ETag header",
+            },
+            "current_turn": index,
+            "next_turn": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Who plays after the current player",
+            },
+            "direction": { "type": "string", "enum": ["Clockwise", "CounterClockwise"] },
+            "players": { "type": "array", "items": schema_ref("PlayerStateResponse") },
+            "discard_pile_top": top_card,
+            "recent_discards": {
+                "type": "array",
+                "items": schema_ref("CardResponse"),
+                "maxItems": 5,
+                "description": "The last five discards, most recent first",
+            },
+            "deck_cards_remaining": index,
+            "pending_draws": index,
+            "pending_skips": index,
+            "status": status,
+            "winner": winner,
+            "turn_seconds_remaining": {
+                "type": "integer",
+                "nullable": true,
+                "description": "Seconds until a card is drawn for the current player; null without a turn timer",
+            },
+        }), &["id", "version", "current_turn", "next_turn", "direction", "players", "discard_pile_top",
+              "recent_discards", "deck_cards_remaining", "pending_draws", "pending_skips", "status",
+              "winner", "turn_seconds_remaining"]),
+        "GameSummaryResponse": object(json!({
+            "id": { "type": "string" },
+            "player_names": { "type": "array", "items": { "type": "string" } },
+            "current_turn": index,
+            "status": status,
+            "last_updated": { "type": "string", "format": "date-time" },
+        }), &["id", "player_names", "current_turn", "status", "last_updated"]),
+        "DeckResponse": object(json!({
+            "cards": { "type": "array", "items": schema_ref("CardResponse") },
+        }), &["cards"]),
+        "DiscardPileResponse": object(json!({
+            "cards": {
+                "type": "array",
+                "description": "[card, id of the player who played it or null]",
+                "items": {
+                    "type": "array",
+                    "items": { "oneOf": [schema_ref("CardResponse"), { "type": "integer", "nullable": true }] },
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+            },
+        }), &["cards"]),
+        "HintsResponse": object(json!({
+            "player_id": index,
+            "playable_cards": { "type": "array", "items": indexed_card },
+            "must_draw": { "type": "boolean" },
+            "pending_draws": index,
+        }), &["player_id", "playable_cards", "must_draw", "pending_draws"]),
+        "GameSession": {
+            "type": "object",
+            "description": "A saved game exactly as stored, including every hand and the deck",
+            "properties": {
+                "id": { "type": "string" },
+                "game": { "type": "object" },
+                "last_updated": { "type": "string", "format": "date-time" },
+                "schema_version": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Format of the saved session; older versions are upgraded on load",
+                },
+            },
+            "required": ["id", "game", "last_updated"],
+        },
+    });
+    schemas["GameEvent"] = json!({
+        "description": "A single-key object naming the event, e.g. `{\"Skip\": {\"player_id\": 1}}`, or the string `Reverse`",
+        "oneOf": [
+            { "type": "string", "enum": ["Reverse"] },
+            {
+                "type": "object",
+                "minProperties": 1,
+                "maxProperties": 1,
+                "properties": {
+                    "CardPlayed": {}, "CardDrawn": {}, "CardDrawnPlayable": {}, "CardsDrawnUntilPlayable": {}, "Skip": {},
+                    "DrawTwo": {}, "WildColorChosen": {}, "WildDrawFour": {}, "PlayerWins": {},
+                    "PlayerForfeited": {}, "PlayerKicked": {}, "HandSwap": {}, "HandRotate": {}, "Stalemate": {},
+                    "TurnPassed": {}, "HandFull": {}, "ChallengeResult": {}, "TurnsSkipped": {},
+                    "DeckReshuffled": {},
+                },
+            },
+        ],
+    });
+    schemas["GamePage"] = object(
+        json!({
+            "games": {
+                "description": "Game ids, or summaries when `detailed=true`",
+                "oneOf": [
+                    { "type": "array", "items": { "type": "string" } },
+                    { "type": "array", "items": schema_ref("GameSummaryResponse") },
+                ],
+            },
+            "total": { "type": "integer", "description": "Games matching the filters across all pages" },
+            "next_offset": {
+                "type": "integer",
+                "nullable": true,
+                "description": "Offset of the next page; null on the last page",
+            },
+        }),
+        &["games", "total", "next_offset"],
+    );
+    schemas["CardCountResponse"] = object(
+        json!({
+            "color": { "type": "string" },
+            "card_type": { "type": "string", "description": "e.g. `Number(7)` or `Skip`" },
+            "count": { "type": "integer", "minimum": 1 },
+        }),
+        &["color", "card_type", "count"],
+    );
+    schemas["DeckCompositionResponse"] = object(
+        json!({
+            "total": { "type": "integer", "description": "Cards left in the deck" },
+            "cards": {
+                "type": "array",
+                "description": "One entry per color and card type left, by color and then type",
+                "items": schema_ref("CardCountResponse"),
+            },
+        }),
+        &["total", "cards"],
+    );
+    schemas["PurgeGamesResponse"] = object(
+        json!({
+            "removed": { "type": "array", "items": { "type": "string" } },
+            "count": index,
+        }),
+        &["removed", "count"],
+    );
+    let flag = |description: &str| json!({ "type": "boolean", "default": false, "description": description });
+    schemas["GameConfig"] = object(
+        json!({
+            "starting_hand_size": { "type": "integer", "minimum": 0, "default": 7 },
+            "deck_copies": { "type": "integer", "minimum": 1, "default": 1 },
+            "wild_count": { "type": "integer", "minimum": 0, "default": 4 },
+            "wild_draw_four_count": { "type": "integer", "minimum": 0, "default": 4 },
+            "draw_then_play": flag("A player who draws a playable card may play it"),
+            "draw_until_playable": flag("Draw until a playable card turns up, then it may be played"),
+            "seven_zero_enabled": flag("7s swap hands with a chosen player; 0s pass every hand on"),
+            "max_hand_size": {
+                "type": "integer",
+                "minimum": 0,
+                "nullable": true,
+                "description": "Most cards a player may hold; no limit when null",
+            },
+            "must_play_if_able": flag("A player with a playable card may not draw"),
+            "allow_deck_peek": flag("Allow GET /games/{id}/deck/next"),
+            "skip_stacking": flag("A Skip may be passed on with another Skip; whoever can't loses a turn per Skip"),
+        }),
+        &[],
+    );
+    schemas["GameConfig"]["description"] =
+        json!("House rules. Options left out take their defaults; unknown options are refused.");
+    schemas["GameConfig"]["additionalProperties"] = json!(false);
+    schemas["ApiError"] = object(
+        json!({
+            "error": { "type": "string", "description": "Human-readable message" },
+            "code": { "type": "string", "enum": ERROR_CODES },
+        }),
+        &["error", "code"],
+    );
+    schemas["TopCardResponse"] = object(
+        json!({
+            "color": { "type": "string", "enum": ["Red", "Blue", "Green", "Yellow", "Wild"] },
+            "card_type": { "type": "string" },
+            "player_id": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Who played it; absent for the opening card",
+            },
+            "effective_color": {
+                "type": "string",
+                "nullable": true,
+                "description": "The color the next card must match; null while a wild awaits its color",
+            },
+            "is_wild_active": {
+                "type": "boolean",
+                "description": "The top card is a wild that has been given a color",
+            },
+        }),
+        &["color", "card_type", "effective_color", "is_wild_active"],
+    );
+    schemas["PlayerStats"] = object(
+        json!({
+            "cards_played": index,
+            "cards_drawn": index,
+            "skips_dealt": index,
+            "wilds_played": index,
+            "rounds_won": index,
+        }),
+        &[
+            "cards_played",
+            "cards_drawn",
+            "skips_dealt",
+            "wilds_played",
+            "rounds_won",
+        ],
+    );
+    schemas["PublicStateResponse"] = object(
+        json!({
+            "id": { "type": "string" },
+            "version": index,
+            "current_turn": index,
+            "direction": { "type": "string", "enum": ["Clockwise", "CounterClockwise"] },
+            "players": { "type": "array", "items": schema_ref("PlayerResponse") },
+            "discard_pile_top": top_card,
+            "deck_cards_remaining": index,
+            "pending_draws": index,
+            "pending_skips": index,
+            "status": status,
+            "winner": winner,
+        }),
+        &[
+            "id",
+            "version",
+            "current_turn",
+            "direction",
+            "players",
+            "discard_pile_top",
+            "deck_cards_remaining",
+            "pending_draws",
+            "pending_skips",
+            "status",
+            "winner",
+        ],
+    );
+    schemas["ReplayRequest"] = object(
+        json!({
+            "player_names": { "type": "array", "items": { "type": "string" } },
+            "seed": { "type": "integer", "minimum": 0 },
+            "events": {
+                "type": "array",
+                "items": schema_ref("GameEvent"),
+                "description": "The events returned by each move, in order",
+            },
+        }),
+        &["player_names", "seed", "events"],
+    );
+    schemas["ReplayResponse"] = object(
+        json!({
+            "steps_applied": index,
+            "state": schema_ref("GameStateResponse"),
+            "tokens": {
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+                "description": "Each player's token, keyed by player id",
+            },
+        }),
+        &["steps_applied", "state", "tokens"],
+    );
+    schemas["HandCardResponse"] = object(
+        json!({
+            "index": index,
+            "card": schema_ref("CardResponse"),
+            "playable": { "type": "boolean" },
+        }),
+        &["index", "card", "playable"],
+    );
+    schemas["CardDetailResponse"] = object(
+        json!({
+            "index": index,
+            "color": { "type": "string" },
+            "card_type": { "type": "string", "description": "e.g. `Number(7)` or `Skip`" },
+            "display": { "type": "string", "description": "How the card reads, e.g. `Red 7`" },
+            "points": { "type": "integer", "minimum": 0 },
+            "playable": {
+                "type": "boolean",
+                "description": "True if it is this player's turn and the card can be played now",
+            },
+        }),
+        &[
+            "index",
+            "color",
+            "card_type",
+            "display",
+            "points",
+            "playable",
+        ],
+    );
+    schemas["Action"] = object(
+        json!({
+            "action": {
+                "type": "string",
+                "enum": ["play", "draw", "pass", "challenge", "take_penalty", "choose_color"],
+            },
+            "index": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "For `play`: the hand index of the card to play",
+            },
+        }),
+        &["action"],
+    );
+    schemas["CurrentPlayerResponse"] = object(
+        json!({
+            "player_id": index,
+            "name": { "type": "string" },
+            "hand": { "type": "array", "items": schema_ref("HandCardResponse") },
+            "effective_color": {
+                "type": "string",
+                "nullable": true,
+                "description": "The color to match: the top card's, or the one chosen for a wild",
+            },
+            "pending_draws": index,
+            "actions": {
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "enum": ["play", "draw", "pass", "challenge", "take_penalty", "choose_color"],
+                },
+                "description": "The moves open to the player; empty unless the game is in progress",
+            },
+            "legal_actions": {
+                "type": "array",
+                "items": schema_ref("Action"),
+                "description": "The same moves in full, with one `play` per playable card",
+            },
+        }),
+        &[
+            "player_id",
+            "name",
+            "hand",
+            "effective_color",
+            "pending_draws",
+            "actions",
+            "legal_actions",
+        ],
+    );
+    schemas["PenaltyResponse"] = object(
+        json!({
+            "event": schema_ref("GameEvent"),
+            "cards_drawn": index,
+            "discard_pile_top": schema_ref("TopCardResponse"),
+        }),
+        &["event", "cards_drawn", "discard_pile_top"],
+    );
+    schemas["JoinGameRequest"] = object(json!({ "name": { "type": "string" } }), &["name"]);
+    schemas["JoinGameResponse"] = object(
+        json!({
+            "player_id": index,
+            "token": {
+                "type": "string",
+                "description": "The new player's token; it is not shown again",
+            },
+            "state": schema_ref("GameStateResponse"),
+        }),
+        &["player_id", "token", "state"],
+    );
+    schemas["SimulateRequest"] = object(
+        json!({
+            "action": { "type": "string", "enum": ["play", "draw"] },
+            "card_index": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Required when `action` is `play`",
+            },
+            "color": {
+                "type": "string",
+                "nullable": true,
+                "description": "Required when playing a wild card",
+            },
+            "target_player_id": {
+                "type": "integer",
+                "minimum": 0,
+                "nullable": true,
+                "description": "Player to swap hands with when playing a 7 under Seven-Zero",
+            },
+        }),
+        &["action"],
+    );
+    schemas["Standing"] = object(
+        json!({
+            "player_id": index,
+            "name": { "type": "string" },
+            "position": { "type": "integer", "minimum": 1, "description": "1 for the winner" },
+            "hand_score": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Points left in the player's hand when the game ended",
+            },
+        }),
+        &["player_id", "name", "position", "hand_score"],
+    );
+    schemas["MoveRequest"] = schemas["SimulateRequest"].clone();
+    schemas["MoveRequest"]["properties"]["action"] = json!({
+        "type": "string",
+        "enum": ["play", "draw", "take_penalty", "pass", "challenge"],
+    });
+    schemas["MovesRequest"] = object(
+        json!({
+            "moves": {
+                "type": "array",
+                "minItems": 1,
+                "items": schema_ref("MoveRequest"),
+                "description": "Applied in order, each for whoever's turn it is by then",
+            },
+        }),
+        &["moves"],
+    );
+    schemas["MovesResponse"] = object(
+        json!({
+            "events": { "type": "array", "items": schema_ref("GameEvent") },
+            "state": schema_ref("GameStateResponse"),
+        }),
+        &["events", "state"],
+    );
+    schemas["AdvanceBotsResponse"] = object(
+        json!({
+            "events": { "type": "array", "items": schema_ref("GameEvent") },
+            "state": schema_ref("GameStateResponse"),
+        }),
+        &["events", "state"],
+    );
+    schemas["SimulateResponse"] = object(
+        json!({
+            "event": schema_ref("GameEvent"),
+            "state": schema_ref("GameStateResponse"),
+        }),
+        &["event", "state"],
+    );
+    schemas["PlayerStatsResponse"] = object(
+        json!({
+            "id": index,
+            "name": { "type": "string" },
+            "stats": schema_ref("PlayerStats"),
+        }),
+        &["id", "name", "stats"],
+    );
+    schemas["HealthResponse"] = object(
+        json!({
+            "status": { "type": "string", "enum": ["ok"] },
+            "version": { "type": "string" },
+        }),
+        &["status", "version"],
+    );
+    schemas["ReadyResponse"] = object(
+        json!({
+            "status": { "type": "string", "enum": ["ready", "unavailable"] },
+            "error": { "type": "string" },
+        }),
+        &["status"],
+    );
+    schemas["PlayerGameResponse"] = object(
+        json!({
+            "id": { "type": "string" },
+            "player_id": index,
+            "your_turn": { "type": "boolean", "description": "Whether the game is waiting on this player" },
+            "status": { "type": "string", "enum": ["Lobby", "In Progress"] },
+        }),
+        &["id", "player_id", "your_turn", "status"],
+    );
+    schemas["ReloadAllResponse"] = object(json!({ "dropped": index }), &["dropped"]);
+    schemas["PlayerGamesResponse"] = object(
+        json!({
+            "games": { "type": "array", "items": schema_ref("PlayerGameResponse") },
+        }),
+        &["games"],
+    );
+    schemas
+}
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn json_body(schema: &str) -> Value {
+    json!({
+        "required": true,
+        "content": { "application/json": { "schema": schema_ref(schema) } },
+    })
+}
+
+fn json_response(description: &str, schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema } },
+    })
+}
+
+fn error_response(description: &str) -> Value {
+    json_response(description, schema_ref("ApiError"))
+}
+
+fn server_full() -> Value {
+    let mut response = error_response("The server has as many active games as it allows");
+    response["headers"] = json!({
+        "Retry-After": {
+            "description": "Seconds to wait before trying again",
+            "schema": { "type": "integer" },
+        },
+    });
+    response
+}
@@ -1,12 +1,49 @@
-use super::card::Card;
+use super::card::{Card, CardType, Color};
 use super::game::GameError;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Player {
     pub id: usize,
     pub name: String,
     pub hand: Vec<Card>,
+    /// Whether the player is still taking turns (false once they forfeit).
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// Whether the computer plays this player's turns.
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub stats: PlayerStats,
+    /// Turns the player still has to sit out after losing a chain of stacked Skips.
+    #[serde(default)]
+    pub turns_to_miss: usize,
+}
+
+/// Running totals for one player, for leaderboards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PlayerStats {
+    pub cards_played: u32,
+    pub cards_drawn: u32,
+    pub skips_dealt: u32,
+    pub wilds_played: u32,
+    pub rounds_won: u32,
+}
+
+impl PlayerStats {
+    /// Counts a card the player has just played.
+    pub fn record_play(&mut self, card: &Card) {
+        self.cards_played += 1;
+        match card.card_type {
+            CardType::Skip => self.skips_dealt += 1,
+            CardType::Wild | CardType::WildDrawFour => self.wilds_played += 1,
+            _ => {}
+        }
+    }
+}
+
+fn default_active() -> bool {
+    true
 }
 
 impl Player {
@@ -15,6 +52,10 @@ impl Player {
             id,
             name,
             hand: Vec::new(),
+            active: true,
+            is_bot: false,
+            stats: PlayerStats::default(),
+            turns_to_miss: 0,
         }
     }
 
@@ -38,4 +79,58 @@ impl Player {
     pub fn has_won(&self) -> bool {
         self.hand.is_empty()
     }
+
+    /// Returns the total point value of the cards in the player's hand.
+    pub fn hand_score(&self) -> u32 {
+        self.hand.iter().map(|card| card.points()).sum()
+    }
+
+    /// Orders the hand by color (red, green, blue, yellow, then wilds) and then by card
+    /// type (numbers low to high, actions, wilds). Only done when asked, so card indices
+    /// don't shift under a player in the middle of a turn.
+    pub fn sort_hand(&mut self) {
+        self.hand.sort_by_key(|card| {
+            let color = Color::all().iter().position(|c| *c == card.color);
+            let card_type = CardType::all().iter().position(|t| *t == card.card_type);
+            (color, card_type)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_hand() {
+        let mut player = Player::new(0, "Alice".to_string());
+        player.hand = vec![
+            Card::new(Color::Wild, CardType::WildDrawFour),
+            Card::new(Color::Blue, CardType::Number(3)),
+            Card::new(Color::Red, CardType::Skip),
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Red, CardType::Number(7)),
+            Card::new(Color::Yellow, CardType::DrawTwo),
+            Card::new(Color::Blue, CardType::Number(0)),
+        ];
+
+        player.sort_hand();
+        assert_eq!(
+            player.hand,
+            vec![
+                Card::new(Color::Red, CardType::Number(7)),
+                Card::new(Color::Red, CardType::Skip),
+                Card::new(Color::Blue, CardType::Number(0)),
+                Card::new(Color::Blue, CardType::Number(3)),
+                Card::new(Color::Yellow, CardType::DrawTwo),
+                Card::new(Color::Wild, CardType::Wild),
+                Card::new(Color::Wild, CardType::WildDrawFour),
+            ]
+        );
+
+        // Sorting an already sorted hand changes nothing
+        let once = player.hand.clone();
+        player.sort_hand();
+        assert_eq!(player.hand, once);
+    }
 }
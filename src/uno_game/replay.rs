@@ -0,0 +1,198 @@
+use super::card::{Card, Color};
+use super::game::{GameEvent, UnoGame};
+
+/// Why a recorded game could not be replayed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayError {
+    /// Index into the event list of the step that failed.
+    pub step: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Step {}: {}", self.step, self.reason)
+    }
+}
+
+/// Deals a seeded game and re-applies each recorded event to it as a move, checking that
+/// every move is legal and produces exactly the recorded event. Returns the final game.
+///
+/// Events are the ones the game hands back for each move: `CardPlayed` and `PlayerWins` for
+/// plays, the draw events (including `Stalemate` and `TurnsSkipped`) for draws,
+/// `TurnPassed`, `ChallengeResult` `PlayerForfeited`, `PlayerKicked` and the
+/// `WildColorChosen` recorded for a color chosen separately. `DeckReshuffled`
+/// events are skipped, since the seeded draws reshuffle the same way again.
+pub fn replay(
+    player_names: Vec<String>,
+    seed: u64,
+    events: &[GameEvent],
+) -> Result<UnoGame, ReplayError> {
+    let game = UnoGame::new_with_seed(player_names, seed).map_err(|e| ReplayError {
+        step: 0,
+        reason: e.to_string(),
+    })?;
+    replay_from(game, events)
+}
+
+/// Like `replay`, but starts from `game` rather than a fresh seeded deal.
+pub fn replay_from(mut game: UnoGame, events: &[GameEvent]) -> Result<UnoGame, ReplayError> {
+    for (step, expected) in events.iter().enumerate() {
+        if matches!(expected, GameEvent::DeckReshuffled { .. }) {
+            continue;
+        }
+        let actual =
+            apply_step(&mut game, expected).map_err(|reason| ReplayError { step, reason })?;
+        if actual != *expected {
+            return Err(ReplayError {
+                step,
+                reason: format!("expected {:?} but the move gave {:?}", expected, actual),
+            });
+        }
+    }
+    Ok(game)
+}
+
+/// Makes the move that should have produced `event`.
+fn apply_step(game: &mut UnoGame, event: &GameEvent) -> Result<GameEvent, String> {
+    match event {
+        GameEvent::CardPlayed {
+            player_id, card, ..
+        } => {
+            let index = find_card(game, *player_id, card)?;
+            game.play_card(*player_id, index).map_err(|e| e.to_string())
+        }
+        // The winning card isn't recorded, but it must be the last one in the hand
+        GameEvent::PlayerWins { player_id } => {
+            let hand_size = hand(game, *player_id)?.len();
+            if hand_size != 1 {
+                return Err(format!(
+                    "player {} can't win holding {} cards",
+                    player_id, hand_size
+                ));
+            }
+            game.play_card(*player_id, 0).map_err(|e| e.to_string())
+        }
+        GameEvent::CardDrawn { player_id, .. }
+        | GameEvent::CardDrawnPlayable { player_id, .. }
+        | GameEvent::CardsDrawnUntilPlayable { player_id, .. }
+        | GameEvent::DrawTwo { player_id, .. }
+        | GameEvent::HandFull { player_id }
+        | GameEvent::TurnsSkipped { player_id, .. } => {
+            if *player_id != game.current_turn {
+                return Err("Not your turn".to_string());
+            }
+            game.draw_card(*player_id).map_err(|e| e.to_string())
+        }
+        GameEvent::Stalemate { .. } => game.draw_card(game.current_turn).map_err(|e| e.to_string()),
+        GameEvent::TurnPassed { player_id } => {
+            game.pass_turn(*player_id).map_err(|e| e.to_string())
+        }
+        GameEvent::ChallengeResult { .. } => game
+            .challenge_wild_draw_four(game.current_turn)
+            .map_err(|e| e.to_string()),
+        GameEvent::PlayerForfeited { player_id } => {
+            game.forfeit(*player_id).map_err(|e| e.to_string())
+        }
+        GameEvent::PlayerKicked { player_id } => game.kick(*player_id).map_err(|e| e.to_string()),
+        // Recorded by `/color`, which sets the color of whatever card is on top
+        GameEvent::WildColorChosen { color, .. } => {
+            let (top_card, _) = game
+                .discard_pile
+                .last_mut()
+                .ok_or("there is no discard pile to color")?;
+            top_card.color = *color;
+            game.version += 1;
+            Ok(event.clone())
+        }
+        other => Err(format!("{:?} is not a move that can be replayed", other)),
+    }
+}
+
+fn hand(game: &UnoGame, player_id: usize) -> Result<&[Card], String> {
+    game.players
+        .get(player_id)
+        .map(|p| p.hand.as_slice())
+        .ok_or_else(|| format!("there is no player {}", player_id))
+}
+
+/// Finds `card` in the player's hand. A recorded wild carries its chosen color, so it
+/// matches an uncolored wild of the same kind, which is given that color.
+fn find_card(game: &mut UnoGame, player_id: usize, card: &Card) -> Result<usize, String> {
+    let hand = hand(game, player_id)?;
+    if let Some(index) = hand.iter().position(|c| c == card) {
+        return Ok(index);
+    }
+    let is_wild = card.is_wild();
+    let index = hand
+        .iter()
+        .position(|c| is_wild && c.card_type == card.card_type && c.color == Color::Wild)
+        .ok_or_else(|| format!("player {} doesn't hold {}", player_id, card))?;
+    game.players[player_id].hand[index].color = card.color;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::bot;
+
+    fn names() -> Vec<String> {
+        vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()]
+    }
+
+    // Lets bots play a seeded game for a number of moves, recording each event
+    fn record_game(seed: u64, moves: usize) -> (UnoGame, Vec<GameEvent>) {
+        let mut game = UnoGame::new_with_seed(names(), seed).unwrap();
+        let mut events = Vec::new();
+        for _ in 0..moves {
+            if game.status.is_over() {
+                break;
+            }
+            let player_id = game.current_turn;
+            let bot_move = bot::choose_move(&game, player_id);
+            events.push(bot::apply_move(&mut game, player_id, &bot_move).unwrap());
+        }
+        (game, events)
+    }
+
+    #[test]
+    fn test_seeded_games_deal_the_same() {
+        let first = UnoGame::new_with_seed(names(), 7).unwrap();
+        let second = UnoGame::new_with_seed(names(), 7).unwrap();
+        let other = UnoGame::new_with_seed(names(), 8).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first.deck, other.deck);
+    }
+
+    #[test]
+    fn test_replay_reaches_the_same_state() {
+        let (recorded, events) = record_game(42, 40);
+        let replayed = replay(names(), 42, &events).unwrap();
+        assert_eq!(replayed, recorded);
+    }
+
+    #[test]
+    fn test_replay_rejects_an_illegal_step() {
+        let (_, mut events) = record_game(42, 6);
+        // Bob can't move first
+        events.insert(0, GameEvent::TurnPassed { player_id: 1 });
+
+        let err = replay(names(), 42, &events).unwrap_err();
+        assert_eq!(err.step, 0);
+    }
+
+    #[test]
+    fn test_replay_rejects_a_different_draw() {
+        let game = UnoGame::new_with_seed(names(), 42).unwrap();
+        let not_next = game.deck[0].clone();
+        let events = vec![GameEvent::CardDrawn {
+            player_id: 0,
+            card: not_next,
+        }];
+
+        let err = replay(names(), 42, &events).unwrap_err();
+        assert_eq!(err.step, 0);
+        assert!(err.reason.contains("expected"));
+    }
+}
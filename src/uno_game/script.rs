@@ -0,0 +1,202 @@
+use super::card::Color;
+use super::game::{ReshuffleLog, UnoGame};
+use super::ui::ConsoleUI;
+
+/// A script line that could not be run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    /// 1-based line number in the script.
+    pub line_number: usize,
+    pub line: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: `{}`: {}",
+            self.line_number, self.line, self.reason
+        )
+    }
+}
+
+/// Runs a line-oriented game script, printing each event through `ui`.
+/// Returns the game as it stands after the last line.
+///
+/// Commands, one per line (blank lines and lines starting with `#` are skipped):
+/// - `create [--seed N] NAME NAME...` starts a new game
+/// - `play INDEX [COLOR]` plays the current player's card at INDEX, giving a wild COLOR;
+///   only a wild takes a COLOR
+/// - `color COLOR` sets the color of the wild on top of the discard pile
+/// - `draw` draws for the current player
+/// - `state` prints the board
+///
+/// Stops at the first line that fails.
+pub fn run_script(script: &str, ui: &mut ConsoleUI) -> Result<UnoGame, ScriptError> {
+    let mut game: Option<UnoGame> = None;
+    for (i, line) in script.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        run_line(trimmed, &mut game, ui).map_err(|reason| ScriptError {
+            line_number: i + 1,
+            line: trimmed.to_string(),
+            reason,
+        })?;
+    }
+    game.ok_or_else(|| ScriptError {
+        line_number: script.lines().count(),
+        line: String::new(),
+        reason: "the script never creates a game".to_string(),
+    })
+}
+
+fn run_line(line: &str, game: &mut Option<UnoGame>, ui: &mut ConsoleUI) -> Result<(), String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if words[0] == "create" {
+        *game = Some(create(&words[1..])?);
+        return Ok(());
+    }
+
+    let game = game.as_mut().ok_or("no game yet; start with `create`")?;
+    let reshuffles = ReshuffleLog::attach(game);
+    match words.as_slice() {
+        ["play", index, color @ ..] if color.len() <= 1 => {
+            let index: usize = index
+                .parse()
+                .map_err(|_| format!("invalid card index: {}", index))?;
+            if let [color] = color {
+                let color: Color = color.parse()?;
+                let player = game.current_turn;
+                let card = game.players[player]
+                    .hand
+                    .get_mut(index)
+                    .ok_or_else(|| format!("no card at index {}", index))?;
+                if !card.is_wild() {
+                    return Err(format!(
+                        "card at index {} is not a wild; it takes no color",
+                        index
+                    ));
+                }
+                card.color = color;
+            }
+            let event = game
+                .play_card(game.current_turn, index)
+                .map_err(|e| e.to_string())?;
+            ui.handle_move(&reshuffles, &event, game);
+        }
+        ["color", color] => {
+            let color: Color = color.parse()?;
+            let (top_card, _) = game.discard_pile.last_mut().ok_or("no discard pile")?;
+            top_card.color = color;
+            game.version += 1;
+        }
+        ["draw"] => {
+            let event = game
+                .draw_card(game.current_turn)
+                .map_err(|e| e.to_string())?;
+            ui.handle_move(&reshuffles, &event, game);
+        }
+        ["state"] => ui.display_game_state(game),
+        _ => return Err(format!("unknown command: {}", line)),
+    }
+    Ok(())
+}
+
+fn create(args: &[&str]) -> Result<UnoGame, String> {
+    let (seed, names) = match args {
+        ["--seed", seed, names @ ..] => {
+            let seed: u64 = seed
+                .parse()
+                .map_err(|_| format!("invalid seed: {}", seed))?;
+            (Some(seed), names)
+        }
+        names => (None, names),
+    };
+    let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let game = match seed {
+        Some(seed) => UnoGame::new_with_seed(names, seed),
+        None => UnoGame::new(names),
+    };
+    game.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::bot::{self, BotMove};
+    use crate::uno_game::game::GameStatus;
+    use std::io::Cursor;
+
+    fn quiet_ui() -> ConsoleUI {
+        ConsoleUI::with_streams(Box::new(Cursor::new("")), Box::new(Vec::new()))
+    }
+
+    // Has the bot play a seeded game to the end, writing its moves out as a script
+    fn script_for_bot_game(seed: u64) -> (String, UnoGame) {
+        let mut game =
+            UnoGame::new_with_seed(vec!["Alice".to_string(), "Bob".to_string()], seed).unwrap();
+        let mut script = format!("create --seed {} Alice Bob\n", seed);
+        while !game.status.is_over() {
+            let player_id = game.current_turn;
+            let bot_move = bot::choose_move(&game, player_id);
+            bot::apply_move(&mut game, player_id, &bot_move).unwrap();
+            match bot_move {
+                BotMove::Play(index, Some(color)) => {
+                    script.push_str(&format!("play {} {:?}\n", index, color))
+                }
+                BotMove::Play(index, None) => script.push_str(&format!("play {}\n", index)),
+                BotMove::Draw => script.push_str("draw\n"),
+            }
+        }
+        script.push_str("state\n");
+        (script, game)
+    }
+
+    #[test]
+    fn test_script_plays_to_the_same_winner() {
+        let (script, expected) = script_for_bot_game(3);
+        let game = run_script(&script, &mut quiet_ui()).unwrap();
+
+        assert!(matches!(game.status, GameStatus::Complete { .. }));
+        assert_eq!(game.status, expected.status);
+    }
+
+    #[test]
+    fn test_script_stops_at_the_failing_line() {
+        let script = "# two players\ncreate Alice Bob\ndraw\nplay 99\ndraw\n";
+        let err = run_script(script, &mut quiet_ui()).unwrap_err();
+        assert_eq!(err.line_number, 4);
+        assert_eq!(err.line, "play 99");
+    }
+
+    #[test]
+    fn test_script_colors_a_wild() {
+        let script = "create Alice Bob\ncolor green\n";
+        let game = run_script(script, &mut quiet_ui()).unwrap();
+        assert_eq!(game.discard_pile.last().unwrap().0.color, Color::Green);
+    }
+
+    #[test]
+    fn test_script_refuses_a_color_for_a_colored_card() {
+        let game = UnoGame::new_with_seed(vec!["Alice".to_string(), "Bob".to_string()], 7).unwrap();
+        let index = game.players[game.current_turn]
+            .hand
+            .iter()
+            .position(|card| !card.is_wild())
+            .unwrap();
+        let script = format!("create --seed 7 Alice Bob\nplay {} red\n", index);
+        let err = run_script(&script, &mut quiet_ui()).unwrap_err();
+        assert_eq!(err.line_number, 2);
+        assert!(err.reason.contains("not a wild"));
+    }
+
+    #[test]
+    fn test_script_needs_a_game_first() {
+        let err = run_script("draw\n", &mut quiet_ui()).unwrap_err();
+        assert_eq!(err.line_number, 1);
+        assert!(err.reason.contains("create"));
+    }
+}
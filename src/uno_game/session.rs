@@ -1,83 +1,585 @@
-use super::game::UnoGame;
+use super::auth;
+use super::clock::{Clock, SystemClock};
+use super::game::{GameEvent, GameStatus, UnoGame};
+use super::replay::{self, ReplayError};
+use super::store::{FileSessionStore, LayeredSessionStore, SessionStore};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The format of saved sessions. Bump it, and add a step to `MIGRATIONS`, whenever
+/// old files can't be read as they are.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades a saved session from version `n` to `n + 1`.
+const MIGRATIONS: &[fn(&mut Value)] = &[migrate_v0];
+
+/// Version 0 files were written before sessions carried a version. Every field added
+/// since then has a serde default, so they only need the version stamping.
+fn migrate_v0(_session: &mut Value) {}
+
+/// Upgrades a saved session to the current `SCHEMA_VERSION`.
+/// Files written by a newer version are rejected rather than misread.
+fn migrate(session: &mut Value) -> io::Result<()> {
+    let version = session
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    if version > SCHEMA_VERSION as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Session schema version {} is newer than the supported {}",
+                version, SCHEMA_VERSION
+            ),
+        ));
+    }
+    for step in &MIGRATIONS[version..] {
+        step(session);
+    }
+    session["schema_version"] = SCHEMA_VERSION.into();
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSession {
     pub id: String,
     pub game: UnoGame,
     pub last_updated: chrono::DateTime<chrono::Utc>,
+    /// When the current player runs out of time, if the server runs a turn timer.
+    #[serde(default)]
+    pub turn_deadline: Option<chrono::DateTime<chrono::Utc>>,
+    /// The `SCHEMA_VERSION` the session was saved with; 0 for files that predate it.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// The hash of each player's token, by player id. Players without one, such as in
+    /// games saved before tokens existed, can be moved for without a token.
+    #[serde(default)]
+    pub token_hashes: HashMap<usize, String>,
+    /// The game as it was dealt, which `history` replays from. `None` for lobbies and
+    /// games saved before history was kept.
+    #[serde(default)]
+    pub initial: Option<UnoGame>,
+    /// The moves made since the deal, as the events they gave.
+    #[serde(default)]
+    pub history: Vec<GameEvent>,
 }
 
 impl GameSession {
     pub fn new(id: String, game: UnoGame) -> Self {
         Self {
             id,
-            game,
             last_updated: chrono::Utc::now(),
+            turn_deadline: None,
+            schema_version: SCHEMA_VERSION,
+            token_hashes: HashMap::new(),
+            initial: (game.status != GameStatus::Lobby).then(|| game.clone()),
+            history: Vec::new(),
+            game,
+        }
+    }
+
+    /// Adds a move's event to the history. Reshuffles aren't moves, so they are left out.
+    pub fn record(&mut self, event: &GameEvent) {
+        if !matches!(event, GameEvent::DeckReshuffled { .. }) {
+            self.history.push(event.clone());
         }
     }
 
-    pub fn save(&self, sessions_dir: &PathBuf) -> std::io::Result<()> {
+    /// Starts the history afresh from the game as it is now, after a new deal.
+    pub fn restart_history(&mut self) {
+        self.initial = Some(self.game.clone());
+        self.history.clear();
+    }
+
+    /// The game as it was after the first `moves` recorded moves, or `None` if the
+    /// history doesn't go that far.
+    pub fn state_at(&self, moves: usize) -> Option<Result<UnoGame, ReplayError>> {
+        let initial = self.initial.clone()?;
+        let events = self.history.get(..moves)?;
+        Some(replay::replay_from(initial, events))
+    }
+
+    /// Gives a player a new token, replacing any they had, and returns it. Only its
+    /// hash is kept, so this is the one chance to hand it to the player.
+    pub fn issue_token(&mut self, player_id: usize) -> String {
+        let token = auth::generate_token();
+        self.token_hashes
+            .insert(player_id, auth::hash_token(&token));
+        token
+    }
+
+    /// The player `token` was issued to, if it is one of this game's tokens.
+    pub fn token_owner(&self, token: &str) -> Option<usize> {
+        self.token_hashes
+            .iter()
+            .find(|(_, hash)| auth::verify_token(token, hash))
+            .map(|(&player_id, _)| player_id)
+    }
+
+    /// Whether `token` may move for the player: `Some(true)` if it is theirs,
+    /// `Some(false)` if it isn't, and `None` if the player has no token to check.
+    pub fn check_token(&self, player_id: usize, token: Option<&str>) -> Option<bool> {
+        let hash = self.token_hashes.get(&player_id)?;
+        Some(token.is_some_and(|token| auth::verify_token(token, hash)))
+    }
+
+    /// Gives the current player `timeout` from `now` to move.
+    /// Clears the deadline when there is no timeout or the game isn't in progress.
+    pub fn restart_turn_clock(
+        &mut self,
+        timeout: Option<chrono::Duration>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.turn_deadline = match timeout {
+            Some(timeout) if self.game.status == GameStatus::InProgress => Some(now + timeout),
+            _ => None,
+        };
+    }
+
+    /// Saves the session to `{id}.json` in the sessions directory.
+    /// The JSON is first written to `{id}.json.tmp` and then renamed into place,
+    /// so readers only ever see the previous complete file or the new complete file,
+    /// never a partially written one.
+    pub fn save(&self, sessions_dir: &Path) -> std::io::Result<()> {
         let session_path = sessions_dir.join(format!("{}.json", self.id));
+        let temp_path = sessions_dir.join(format!("{}.json.tmp", self.id));
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(session_path, json)
+        let written =
+            fs::write(&temp_path, json).and_then(|()| fs::rename(&temp_path, session_path));
+        if written.is_err() {
+            // The saved file is untouched; don't leave a half-written copy beside it
+            let _ = fs::remove_file(&temp_path);
+        }
+        written
     }
 
-    pub fn load(id: &str, sessions_dir: &PathBuf) -> std::io::Result<Self> {
+    /// Reads `{id}.json` from the sessions directory, upgrading it to the current schema.
+    /// A file that isn't a valid session fails with `InvalidData` naming the session.
+    pub fn load(id: &str, sessions_dir: &Path) -> std::io::Result<Self> {
         let session_path = sessions_dir.join(format!("{}.json", id));
         let json = fs::read_to_string(session_path)?;
-        let mut session: Self = serde_json::from_str(&json)?;
-        session.last_updated = chrono::Utc::now();
+        let mut value: Value = serde_json::from_str(&json).map_err(|e| corrupt(id, e))?;
+        if !value.is_object() {
+            return Err(corrupt(id, "not a JSON object"));
+        }
+        migrate(&mut value)?;
+        let session: Self = serde_json::from_value(value).map_err(|e| corrupt(id, e))?;
+        // Reject a corrupt file here rather than panic on it later
+        session.game.validate().map_err(|e| corrupt(id, e))?;
         Ok(session)
     }
 }
 
+fn corrupt(id: &str, e: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Session {} is corrupt: {}", id, e),
+    )
+}
+
+/// A lightweight description of a saved session, for listing games without
+/// handing the full game state to the caller.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub player_names: Vec<String>,
+    pub current_turn: usize,
+    pub status: GameStatus,
+    pub last_updated: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub turn_deadline: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<&GameSession> for SessionSummary {
+    fn from(session: &GameSession) -> Self {
+        Self {
+            id: session.id.clone(),
+            player_names: session
+                .game
+                .players
+                .iter()
+                .map(|p| p.name.clone())
+                .collect(),
+            current_turn: session.game.current_turn,
+            status: session.game.status.clone(),
+            last_updated: session.last_updated,
+            turn_deadline: session.turn_deadline,
+        }
+    }
+}
+
+impl SessionSummary {
+    /// The id of the player called `name`, ignoring case.
+    pub fn seat_of(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        self.player_names
+            .iter()
+            .position(|player| player.to_lowercase() == name)
+    }
+
+    /// Whether the game is under way and waiting on the player called `name`.
+    pub fn is_turn_of(&self, name: &str) -> bool {
+        self.status == GameStatus::InProgress && self.seat_of(name) == Some(self.current_turn)
+    }
+}
+
+/// A game status to filter sessions by, ignoring who won.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusFilter {
+    Lobby,
+    InProgress,
+    Complete,
+    Stalemate,
+}
+
+impl StatusFilter {
+    pub fn matches(&self, status: &GameStatus) -> bool {
+        matches!(
+            (self, status),
+            (StatusFilter::Lobby, GameStatus::Lobby)
+                | (StatusFilter::InProgress, GameStatus::InProgress)
+                | (StatusFilter::Complete, GameStatus::Complete { .. })
+                | (StatusFilter::Stalemate, GameStatus::Stalemate { .. })
+        )
+    }
+}
+
+/// Which sessions `SessionManager::query` returns. Fields left as `None` match everything.
+#[derive(Debug, Clone, Default)]
+pub struct SessionQuery {
+    pub status: Option<StatusFilter>,
+    /// Matches sessions with a player whose name contains this, ignoring case.
+    pub player: Option<String>,
+}
+
+impl SessionQuery {
+    pub fn matches(&self, summary: &SessionSummary) -> bool {
+        let status_matches = self
+            .status
+            .is_none_or(|status| status.matches(&summary.status));
+        let player_matches = self.player.as_ref().is_none_or(|player| {
+            let player = player.to_lowercase();
+            summary
+                .player_names
+                .iter()
+                .any(|name| name.to_lowercase().contains(&player))
+        });
+        status_matches && player_matches
+    }
+}
+
+/// Loads and saves sessions through a `SessionStore`.
+/// Sessions that have been loaded or saved are kept in memory so repeated loads
+/// skip the store; saves write through, so the store stays the source of truth,
+/// unless the manager was made `with_write_behind`.
+/// Clones of the manager share the cache, but two managers on the same store don't.
 #[derive(Clone)]
 pub struct SessionManager {
-    pub sessions_dir: PathBuf,
+    store: Arc<dyn SessionStore>,
+    locks: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+    cache: Arc<Mutex<HashMap<String, GameSession>>>,
+    write_behind: bool,
+    /// Ids of cached sessions saved since they were last written to the store.
+    dirty: Arc<Mutex<HashSet<String>>>,
+    /// Held while flushing or deleting, so an older copy of a session can't be written
+    /// over a newer one, or bring back a deleted one.
+    flush_lock: Arc<Mutex<()>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl SessionManager {
+    /// Creates a manager keeping sessions as JSON files in `sessions_dir`.
     pub fn new(sessions_dir: PathBuf) -> std::io::Result<Self> {
-        fs::create_dir_all(&sessions_dir)?;
-        Ok(Self { sessions_dir })
+        Ok(Self::with_store(Arc::new(FileSessionStore::new(
+            sessions_dir,
+        )?)))
+    }
+
+    /// Like `new`, but games not in `sessions_dir` are looked for in `snapshot_dir`,
+    /// which is only ever read. Changes to a snapshot game are saved to `sessions_dir`.
+    pub fn with_snapshot_dir(
+        sessions_dir: PathBuf,
+        snapshot_dir: PathBuf,
+    ) -> std::io::Result<Self> {
+        let store = LayeredSessionStore::new(
+            Arc::new(FileSessionStore::new(sessions_dir)?),
+            Arc::new(FileSessionStore::read_only(snapshot_dir)?),
+        );
+        Ok(Self::with_store(Arc::new(store)))
+    }
+
+    pub fn with_store(store: Arc<dyn SessionStore>) -> Self {
+        Self {
+            store,
+            locks: Arc::new(Mutex::new(HashMap::new())),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            write_behind: false,
+            dirty: Arc::new(Mutex::new(HashSet::new())),
+            flush_lock: Arc::new(Mutex::new(())),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Takes the time from `clock` instead of the system clock, e.g. a `MockClock` to
+    /// test expiry without waiting.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The current time by the manager's clock.
+    pub fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        self.clock.now()
+    }
+
+    /// Makes `save_session` only update the cached copy; the store is written by `flush`,
+    /// which the owner calls on a timer and before shutting down. A burst of moves then
+    /// costs one write, but changes not yet flushed are lost if the process dies.
+    pub fn with_write_behind(mut self) -> Self {
+        self.write_behind = true;
+        self
+    }
+
+    /// Returns the lock guarding the session with the given id.
+    /// Hold it around a load-mutate-save sequence so concurrent requests to the
+    /// same game can't overwrite each other's changes. Clones of the manager share locks.
+    pub fn session_lock(&self, id: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.locks.lock().unwrap();
+        locks.entry(id.to_string()).or_default().clone()
     }
 
     pub fn create_session(&self, game: UnoGame) -> std::io::Result<GameSession> {
         let id = uuid::Uuid::new_v4().to_string();
-        let session = GameSession::new(id.clone(), game);
-        session.save(&self.sessions_dir)?;
+        let mut session = GameSession::new(id.clone(), game);
+        session.last_updated = self.now();
+        self.store.create(&session)?;
         Ok(session)
     }
 
+    /// Creates a session like `create_session`, giving every player a token.
+    /// Returns the tokens by player id alongside the session.
+    pub fn create_session_with_tokens(
+        &self,
+        game: UnoGame,
+    ) -> std::io::Result<(GameSession, HashMap<usize, String>)> {
+        let mut session = GameSession::new(uuid::Uuid::new_v4().to_string(), game);
+        session.last_updated = self.now();
+        let tokens = (0..session.game.players.len())
+            .map(|player_id| (player_id, session.issue_token(player_id)))
+            .collect();
+        self.store.create(&session)?;
+        Ok((session, tokens))
+    }
+
     pub fn load_session(&self, id: &str) -> std::io::Result<GameSession> {
-        GameSession::load(id, &self.sessions_dir)
+        if let Some(session) = self.cache.lock().unwrap().get(id) {
+            let mut session = session.clone();
+            session.last_updated = self.now();
+            return Ok(session);
+        }
+        let mut session = self.store.load(id)?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), session.clone());
+        session.last_updated = self.now();
+        Ok(session)
+    }
+
+    /// Drops the cached copy of a session and reads it from the store again, for when the
+    /// stored file was edited by hand. With write-behind, changes to it not yet flushed
+    /// are dropped too, so they can't overwrite the edit.
+    pub fn reload_session(&self, id: &str) -> std::io::Result<GameSession> {
+        {
+            let _flushing = self.flush_lock.lock().unwrap();
+            self.dirty.lock().unwrap().remove(id);
+            self.cache.lock().unwrap().remove(id);
+        }
+        self.load_session(id)
+    }
+
+    /// Drops every cached session, and any changes not yet flushed, so each is read from
+    /// the store when next loaded. Returns how many were dropped.
+    pub fn clear_cache(&self) -> usize {
+        let _flushing = self.flush_lock.lock().unwrap();
+        self.dirty.lock().unwrap().clear();
+        let mut cache = self.cache.lock().unwrap();
+        let dropped = cache.len();
+        cache.clear();
+        dropped
+    }
+
+    /// Writes the session to the store and refreshes the cached copy. With write-behind,
+    /// only the cached copy is updated and the next `flush` writes it. If the write fails
+    /// the cached copy is left alone, so the next `load_session` returns the state from
+    /// before the failed change and the stored and served games never differ.
+    pub fn save_session(&self, session: &GameSession) -> std::io::Result<()> {
+        if !self.write_behind {
+            self.store.save(session)?;
+        }
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(session.id.clone(), session.clone());
+        if self.write_behind {
+            self.dirty.lock().unwrap().insert(session.id.clone());
+        }
+        Ok(())
+    }
+
+    /// Writes every session saved since the last flush to the store and returns how many
+    /// were written. Sessions that fail to write are kept for the next flush, and the
+    /// last error is returned once the rest have been tried. Without write-behind there
+    /// is never anything to write.
+    pub fn flush(&self) -> std::io::Result<usize> {
+        let _flushing = self.flush_lock.lock().unwrap();
+        let ids: Vec<String> = self.dirty.lock().unwrap().drain().collect();
+        let mut written = 0;
+        let mut result = Ok(());
+        for id in ids {
+            let Some(session) = self.cache.lock().unwrap().get(&id).cloned() else {
+                continue;
+            };
+            match self.store.save(&session) {
+                Ok(()) => written += 1,
+                Err(e) => {
+                    self.dirty.lock().unwrap().insert(id);
+                    result = Err(e);
+                }
+            }
+        }
+        result.map(|()| written)
     }
 
     pub fn list_sessions(&self) -> std::io::Result<Vec<String>> {
-        let mut sessions = Vec::new();
-        for entry in fs::read_dir(&self.sessions_dir)? {
-            let entry = entry?;
-            if let Some(file_name) = entry.file_name().to_str() {
-                if file_name.ends_with(".json") {
-                    sessions.push(file_name.trim_end_matches(".json").to_string());
+        self.store.list()
+    }
+
+    /// Lists all sessions along with their players, turn, status and last update time.
+    /// Summaries come from the store, so pending write-behind saves are flushed first.
+    pub fn list_sessions_with_meta(&self) -> std::io::Result<Vec<SessionSummary>> {
+        self.flush()?;
+        self.store.list_summaries()
+    }
+
+    /// Summaries of every session as they are now, without flushing: sessions with
+    /// write-behind changes are described from their cached copy instead of the store.
+    /// Cheap enough to call every second, e.g. from the turn timer.
+    pub fn current_summaries(&self) -> std::io::Result<Vec<SessionSummary>> {
+        let mut summaries = self.store.list_summaries()?;
+        let cache = self.cache.lock().unwrap();
+        for summary in &mut summaries {
+            if let Some(session) = cache.get(&summary.id) {
+                *summary = SessionSummary::from(session);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Lists the sessions matching `filter`, most recently updated first (then by id, so
+    /// the order is stable). Works from the same summaries as `list_sessions_with_meta`,
+    /// so the file store doesn't read whole games.
+    pub fn query(&self, filter: SessionQuery) -> std::io::Result<Vec<SessionSummary>> {
+        let mut summaries: Vec<SessionSummary> = self
+            .list_sessions_with_meta()?
+            .into_iter()
+            .filter(|summary| filter.matches(summary))
+            .collect();
+        summaries.sort_by(|a, b| {
+            b.last_updated
+                .cmp(&a.last_updated)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(summaries)
+    }
+
+    /// The unfinished games with a player called `name`, ignoring case. Games where it is
+    /// that player's turn come first, then the rest, each most recently updated first.
+    pub fn player_games(&self, name: &str) -> std::io::Result<Vec<SessionSummary>> {
+        let mut summaries: Vec<SessionSummary> = self
+            .query(SessionQuery::default())?
+            .into_iter()
+            .filter(|summary| !summary.status.is_over() && summary.seat_of(name).is_some())
+            .collect();
+        // A stable sort keeps the recency order within each group
+        summaries.sort_by_key(|summary| !summary.is_turn_of(name));
+        Ok(summaries)
+    }
+
+    /// Deletes every session whose `last_updated` is older than `max_age`
+    /// and returns the ids of the removed sessions.
+    pub fn cleanup_expired(&self, max_age: chrono::Duration) -> std::io::Result<Vec<String>> {
+        self.cleanup_expired_with(max_age, None)
+    }
+
+    /// Like `cleanup_expired`, but completed games are removed once they are older
+    /// than `completed_max_age` (when given) so finished games can be reclaimed sooner.
+    pub fn cleanup_expired_with(
+        &self,
+        max_age: chrono::Duration,
+        completed_max_age: Option<chrono::Duration>,
+    ) -> std::io::Result<Vec<String>> {
+        let now = self.now();
+        let mut removed = Vec::new();
+        for summary in self.list_sessions_with_meta()? {
+            let age = now - summary.last_updated;
+            let limit = match (&summary.status, completed_max_age) {
+                (status, Some(completed_max_age)) if status.is_over() => {
+                    completed_max_age.min(max_age)
                 }
+                _ => max_age,
+            };
+            if age > limit {
+                self.delete_session(&summary.id)?;
+                removed.push(summary.id);
             }
         }
-        Ok(sessions)
+        Ok(removed)
     }
 
+    /// Checks that sessions can currently be saved.
+    pub fn check_writable(&self) -> std::io::Result<()> {
+        self.store.check_writable()
+    }
+
+    /// Deletes the session. Callers that may race with moves should hold its
+    /// `session_lock`, then call `release_lock` once they have dropped it.
     pub fn delete_session(&self, id: &str) -> std::io::Result<()> {
-        let session_path = self.sessions_dir.join(format!("{}.json", id));
-        fs::remove_file(session_path)
+        let _flushing = self.flush_lock.lock().unwrap();
+        self.dirty.lock().unwrap().remove(id);
+        self.cache.lock().unwrap().remove(id);
+        self.store.delete(id)?;
+        self.release_lock(id);
+        Ok(())
+    }
+
+    /// Forgets the lock for a session once nothing holds or waits on it. A lock still
+    /// shared is kept, so a later request can't get a fresh one and run alongside a
+    /// request using the old one.
+    pub fn release_lock(&self, id: &str) {
+        let mut locks = self.locks.lock().unwrap();
+        if locks
+            .get(id)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            locks.remove(id);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uno_game::card::{Card, CardType, Color};
+    use crate::uno_game::clock::MockClock;
+    use crate::uno_game::config::GameConfig;
+    use crate::uno_game::store::MemorySessionStore;
     use std::fs;
     use tempfile::tempdir;
 
@@ -101,6 +603,193 @@ mod tests {
         assert_eq!(loaded.game.players.len(), 2);
     }
 
+    #[test]
+    fn test_snapshot_games_are_copied_on_save() {
+        let temp_dir = tempdir().unwrap();
+        let primary = temp_dir.path().join("sessions");
+        let snapshot = temp_dir.path().join("fixtures");
+        fs::create_dir(&snapshot).unwrap();
+        let fixture = GameSession::new(
+            "fixture".to_string(),
+            UnoGame::new_with_seed(vec!["Alice".to_string(), "Bob".to_string()], 3).unwrap(),
+        );
+        fixture.save(&snapshot).unwrap();
+        let fixture_path = snapshot.join("fixture.json");
+        let original = fs::read_to_string(&fixture_path).unwrap();
+
+        let manager = SessionManager::with_snapshot_dir(primary.clone(), snapshot).unwrap();
+        let own = manager
+            .create_session(UnoGame::new(vec!["Carol".to_string(), "Dan".to_string()]).unwrap())
+            .unwrap();
+        let mut ids = manager.list_sessions().unwrap();
+        ids.sort();
+        let mut expected = vec!["fixture".to_string(), own.id.clone()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let mut session = manager.load_session("fixture").unwrap();
+        assert_eq!(session.game, fixture.game);
+        session.game.draw_card(0).unwrap();
+        manager.save_session(&session).unwrap();
+
+        // The change lands in the sessions directory and the fixture is untouched
+        assert_eq!(fs::read_to_string(&fixture_path).unwrap(), original);
+        let saved = GameSession::load("fixture", &primary).unwrap();
+        assert_eq!(saved.game, session.game);
+        assert_eq!(manager.list_sessions().unwrap().len(), 2);
+
+        // A fresh manager prefers the changed copy
+        let reloaded = SessionManager::with_snapshot_dir(primary, temp_dir.path().join("fixtures"))
+            .unwrap()
+            .load_session("fixture")
+            .unwrap();
+        assert_eq!(reloaded.game, session.game);
+    }
+
+    #[test]
+    fn test_load_upgrades_unversioned_session() {
+        let temp_dir = tempdir().unwrap();
+        let v0 = r#"{
+            "id": "old",
+            "game": {
+                "players": [
+                    { "id": 0, "name": "Alice", "hand": [{ "color": "Red", "card_type": { "Number": 3 } }] },
+                    { "id": 1, "name": "Bob", "hand": [{ "color": "Wild", "card_type": "Wild" }] }
+                ],
+                "deck": [],
+                "discard_pile": [[{ "color": "Red", "card_type": { "Number": 5 } }, 18446744073709551615]],
+                "current_turn": 1,
+                "direction": "Clockwise",
+                "pending_draws": 0,
+                "status": "InProgress"
+            },
+            "last_updated": "2024-01-01T00:00:00Z"
+        }"#;
+        // The rest of a full deck, which older files held like any other
+        let mut deck = UnoGame::unshuffled_deck(&GameConfig::default());
+        for card in [
+            Card::new(Color::Red, CardType::Number(3)),
+            Card::new(Color::Wild, CardType::Wild),
+            Card::new(Color::Red, CardType::Number(5)),
+        ] {
+            let index = deck.iter().position(|c| *c == card).unwrap();
+            deck.remove(index);
+        }
+        let mut v0: Value = serde_json::from_str(v0).unwrap();
+        v0["game"]["deck"] = serde_json::to_value(deck).unwrap();
+        fs::write(temp_dir.path().join("old.json"), v0.to_string()).unwrap();
+
+        let session = GameSession::load("old", temp_dir.path()).unwrap();
+        assert_eq!(session.schema_version, SCHEMA_VERSION);
+        assert_eq!(session.game.current_turn, 1);
+        assert!(session.game.players.iter().all(|p| p.active));
+        assert_eq!(session.game.config, GameConfig::default());
+        assert_eq!(session.turn_deadline, None);
+        assert!(session.game.validate_state().is_ok());
+
+        // Saved again, it is written at the current version
+        session.save(temp_dir.path()).unwrap();
+        let json = fs::read_to_string(temp_dir.path().join("old.json")).unwrap();
+        let saved: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(saved["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_load_rejects_corrupt_session() {
+        let temp_dir = tempdir().unwrap();
+        let mut session = GameSession::new(
+            "corrupt".to_string(),
+            UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap(),
+        );
+        session.game.discard_pile.clear();
+        session.save(temp_dir.path()).unwrap();
+
+        let err = GameSession::load("corrupt", temp_dir.path()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Discard pile must not be empty"));
+    }
+
+    #[test]
+    fn test_load_rejects_impossible_turns() {
+        let temp_dir = tempdir().unwrap();
+        let load_error = |game: UnoGame| {
+            GameSession::new("stuck".to_string(), game)
+                .save(temp_dir.path())
+                .unwrap();
+            let err = GameSession::load("stuck", temp_dir.path()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            err.to_string()
+        };
+        let game = || UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+
+        let mut already_won = game();
+        already_won.players[0].hand.clear();
+        assert!(load_error(already_won).contains("It is Alice's turn, but they have no cards left"));
+
+        let mut nothing_to_draw = game();
+        nothing_to_draw.pending_draws = 2;
+        nothing_to_draw.deck.clear();
+        nothing_to_draw.discard_pile.truncate(1);
+        assert!(
+            load_error(nothing_to_draw).contains("2 pending draws, but the deck and discard pile")
+        );
+
+        let mut stray_skips = game();
+        stray_skips.pending_skips = 1;
+        assert!(load_error(stray_skips).contains("skip_stacking is off"));
+
+        let mut both_pending = game();
+        both_pending.config.skip_stacking = true;
+        both_pending.pending_draws = 2;
+        both_pending.pending_skips = 1;
+        assert!(load_error(both_pending).contains("can't both be pending"));
+
+        // The same draws are fine while the discard pile can be shuffled back in
+        let mut reshuffle = game();
+        reshuffle.pending_draws = 2;
+        let deck = reshuffle.deck.drain(..).map(|card| (card, 0));
+        reshuffle.discard_pile.splice(0..0, deck);
+        GameSession::new("reshuffle".to_string(), reshuffle)
+            .save(temp_dir.path())
+            .unwrap();
+        assert!(GameSession::load("reshuffle", temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_load_rejects_newer_schema_version() {
+        let temp_dir = tempdir().unwrap();
+        let session = GameSession {
+            schema_version: SCHEMA_VERSION + 1,
+            ..GameSession::new(
+                "future".to_string(),
+                UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap(),
+            )
+        };
+        session.save(temp_dir.path()).unwrap();
+
+        let err = GameSession::load("future", temp_dir.path()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_tokens_are_saved_only_as_hashes() {
+        let temp_dir = tempdir().unwrap();
+        let manager = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let (session, tokens) = manager.create_session_with_tokens(game).unwrap();
+        assert_eq!(tokens.len(), 2);
+
+        let saved =
+            fs::read_to_string(temp_dir.path().join(format!("{}.json", session.id))).unwrap();
+        assert!(!saved.contains(&tokens[&0]));
+
+        let loaded = GameSession::load(&session.id, temp_dir.path()).unwrap();
+        assert_eq!(loaded.check_token(0, Some(&tokens[&0])), Some(true));
+        assert_eq!(loaded.check_token(0, Some(&tokens[&1])), Some(false));
+        assert_eq!(loaded.check_token(1, None), Some(false));
+        assert_eq!(loaded.check_token(2, None), None);
+    }
+
     #[test]
     fn test_list_sessions() {
         let (manager, _temp_dir) = create_test_session_manager();
@@ -131,18 +820,355 @@ mod tests {
         assert!(!sessions.contains(&session.id));
     }
 
+    #[tokio::test]
+    async fn test_delete_session_keeps_a_lock_in_use() {
+        let (manager, _temp_dir) = create_test_session_manager();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = manager.create_session(game).unwrap();
+
+        let lock = manager.session_lock(&session.id);
+        let guard = lock.lock().await;
+        manager.delete_session(&session.id).unwrap();
+        // Anyone arriving now still waits for the holder
+        assert!(Arc::ptr_eq(&lock, &manager.session_lock(&session.id)));
+
+        drop(guard);
+        drop(lock);
+        manager.release_lock(&session.id);
+        assert!(manager.locks.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn test_session_persistence() {
-        let (manager, _temp_dir) = create_test_session_manager();
+        let (manager, temp_dir) = create_test_session_manager();
         let player_names = vec!["Alice".to_string(), "Bob".to_string()];
         let game = UnoGame::new(player_names).unwrap();
 
         let session = manager.create_session(game).unwrap();
-        let session_path = manager.sessions_dir.join(format!("{}.json", session.id));
+        let session_path = temp_dir.path().join(format!("{}.json", session.id));
 
         assert!(session_path.exists());
         let contents = fs::read_to_string(session_path).unwrap();
         assert!(contents.contains("Alice"));
         assert!(contents.contains("Bob"));
     }
+
+    #[test]
+    fn test_save_replaces_stale_temp_file() {
+        let (manager, temp_dir) = create_test_session_manager();
+        let player_names = (0..10).map(|i| format!("Player {}", i)).collect();
+        let game = UnoGame::new(player_names).unwrap();
+        let session = manager.create_session(game).unwrap();
+
+        // Simulate a save that was interrupted after writing a partial temp file
+        let temp_path = temp_dir.path().join(format!("{}.json.tmp", session.id));
+        fs::write(&temp_path, "{\"id\": \"trunc").unwrap();
+
+        session.save(temp_dir.path()).unwrap();
+
+        assert!(!temp_path.exists());
+        let loaded = manager.load_session(&session.id).unwrap();
+        assert_eq!(loaded.game, session.game);
+
+        // The temp file is never reported as a session
+        assert_eq!(manager.list_sessions().unwrap(), vec![session.id]);
+    }
+
+    #[test]
+    fn test_list_sessions_with_meta() {
+        let (manager, _temp_dir) = create_test_session_manager();
+        let session1 = manager
+            .create_session(UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap())
+            .unwrap();
+
+        let mut game = UnoGame::new(vec!["Charlie".to_string(), "David".to_string()]).unwrap();
+        game.current_turn = 1;
+        game.status = GameStatus::Complete { winner_id: 1 };
+        let session2 = manager.create_session(game).unwrap();
+
+        let summaries = manager.list_sessions_with_meta().unwrap();
+        assert_eq!(summaries.len(), 2);
+
+        let summary1 = summaries.iter().find(|s| s.id == session1.id).unwrap();
+        assert_eq!(summary1.player_names, vec!["Alice", "Bob"]);
+        assert_eq!(summary1.current_turn, 0);
+        assert_eq!(summary1.status, GameStatus::InProgress);
+
+        let summary2 = summaries.iter().find(|s| s.id == session2.id).unwrap();
+        assert_eq!(summary2.player_names, vec!["Charlie", "David"]);
+        assert_eq!(summary2.current_turn, 1);
+        assert_eq!(summary2.status, GameStatus::Complete { winner_id: 1 });
+    }
+
+    #[test]
+    fn test_query_by_status_and_player() {
+        let (manager, _temp_dir) = create_test_session_manager();
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        let alice_playing = manager
+            .create_session(UnoGame::new(names(&["Alice", "Bob"])).unwrap())
+            .unwrap();
+        let mut game = UnoGame::new(names(&["Alicia", "Carol"])).unwrap();
+        game.status = GameStatus::Complete { winner_id: 0 };
+        let alicia_done = manager.create_session(game).unwrap();
+        let others_playing = manager
+            .create_session(UnoGame::new(names(&["Carol", "Dave"])).unwrap())
+            .unwrap();
+
+        let ids = |filter: SessionQuery| {
+            let mut ids: Vec<String> = manager
+                .query(filter)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        let sorted = |mut ids: Vec<String>| {
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(SessionQuery::default()).len(), 3);
+        assert_eq!(
+            ids(SessionQuery {
+                status: Some(StatusFilter::InProgress),
+                player: Some("alice".to_string()),
+            }),
+            vec![alice_playing.id.clone()]
+        );
+        assert_eq!(
+            ids(SessionQuery {
+                player: Some("ALI".to_string()),
+                ..SessionQuery::default()
+            }),
+            sorted(vec![alice_playing.id.clone(), alicia_done.id.clone()])
+        );
+        assert_eq!(
+            ids(SessionQuery {
+                status: Some(StatusFilter::InProgress),
+                ..SessionQuery::default()
+            }),
+            sorted(vec![alice_playing.id, others_playing.id])
+        );
+        assert_eq!(
+            ids(SessionQuery {
+                status: Some(StatusFilter::Complete),
+                player: Some("Carol".to_string()),
+            }),
+            vec![alicia_done.id]
+        );
+        assert!(ids(SessionQuery {
+            status: Some(StatusFilter::Stalemate),
+            ..SessionQuery::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_cleanup_expired() {
+        let (manager, temp_dir) = create_test_session_manager();
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+
+        let mut old_session = manager
+            .create_session(UnoGame::new(player_names.clone()).unwrap())
+            .unwrap();
+        old_session.last_updated = chrono::Utc::now() - chrono::Duration::days(10);
+        old_session.save(temp_dir.path()).unwrap();
+
+        let fresh_session = manager
+            .create_session(UnoGame::new(player_names).unwrap())
+            .unwrap();
+
+        let removed = manager.cleanup_expired(chrono::Duration::days(7)).unwrap();
+        assert_eq!(removed, vec![old_session.id.clone()]);
+
+        let sessions = manager.list_sessions().unwrap();
+        assert_eq!(sessions, vec![fresh_session.id]);
+    }
+
+    #[test]
+    fn test_cleanup_expired_with_mock_clock() {
+        let temp_dir = tempdir().unwrap();
+        let clock = MockClock::default();
+        let manager = SessionManager::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_clock(Arc::new(clock.clone()));
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+
+        let old = manager
+            .create_session(UnoGame::new(player_names.clone()).unwrap())
+            .unwrap();
+        clock.advance(chrono::Duration::days(5));
+        let recent = manager
+            .create_session(UnoGame::new(player_names).unwrap())
+            .unwrap();
+        assert_eq!(
+            recent.last_updated - old.last_updated,
+            chrono::Duration::days(5)
+        );
+
+        clock.advance(chrono::Duration::days(3));
+        let removed = manager.cleanup_expired(chrono::Duration::days(7)).unwrap();
+        assert_eq!(removed, vec![old.id]);
+
+        // Saving after a load counts as an update and keeps the session alive
+        let touched = manager.load_session(&recent.id).unwrap();
+        manager.save_session(&touched).unwrap();
+        clock.advance(chrono::Duration::days(6));
+        assert!(manager
+            .cleanup_expired(chrono::Duration::days(7))
+            .unwrap()
+            .is_empty());
+        clock.advance(chrono::Duration::days(2));
+        let removed = manager.cleanup_expired(chrono::Duration::days(7)).unwrap();
+        assert_eq!(removed, vec![recent.id]);
+    }
+
+    #[test]
+    fn test_cleanup_expired_completed_sooner() {
+        let (manager, _temp_dir) = create_test_session_manager();
+        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
+
+        let mut game = UnoGame::new(player_names.clone()).unwrap();
+        game.status = GameStatus::Complete { winner_id: 0 };
+        let mut completed = manager.create_session(game).unwrap();
+        completed.last_updated = chrono::Utc::now() - chrono::Duration::hours(2);
+        manager.save_session(&completed).unwrap();
+
+        let mut in_progress = manager
+            .create_session(UnoGame::new(player_names).unwrap())
+            .unwrap();
+        in_progress.last_updated = chrono::Utc::now() - chrono::Duration::hours(2);
+        manager.save_session(&in_progress).unwrap();
+
+        let removed = manager
+            .cleanup_expired_with(chrono::Duration::days(1), Some(chrono::Duration::hours(1)))
+            .unwrap();
+        assert_eq!(removed, vec![completed.id]);
+        assert_eq!(manager.list_sessions().unwrap(), vec![in_progress.id]);
+    }
+
+    #[test]
+    fn test_load_session_is_served_from_cache() {
+        let (manager, temp_dir) = create_test_session_manager();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = manager.create_session(game).unwrap();
+        let session_path = temp_dir.path().join(format!("{}.json", session.id));
+
+        // The first load reads the file; later loads must not need it
+        manager.load_session(&session.id).unwrap();
+        fs::remove_file(&session_path).unwrap();
+        for _ in 0..3 {
+            let loaded = manager.load_session(&session.id).unwrap();
+            assert_eq!(loaded.game, session.game);
+        }
+
+        // Clones share the cache
+        assert!(manager.clone().load_session(&session.id).is_ok());
+    }
+
+    #[test]
+    fn test_save_session_writes_through() {
+        let (manager, temp_dir) = create_test_session_manager();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut session = manager.create_session(game).unwrap();
+
+        manager.load_session(&session.id).unwrap();
+        session.game.draw_card(0).unwrap();
+        manager.save_session(&session).unwrap();
+
+        assert_eq!(
+            manager.load_session(&session.id).unwrap().game,
+            session.game
+        );
+        let on_disk = GameSession::load(&session.id, temp_dir.path()).unwrap();
+        assert_eq!(on_disk.game, session.game);
+
+        // A fresh manager starts from what is on disk
+        let fresh = SessionManager::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(fresh.load_session(&session.id).unwrap().game, session.game);
+
+        manager.delete_session(&session.id).unwrap();
+        assert!(manager.load_session(&session.id).is_err());
+    }
+
+    /// A memory store that counts the saves reaching it.
+    #[derive(Default)]
+    struct CountingStore {
+        inner: MemorySessionStore,
+        saves: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingStore {
+        fn saves(&self) -> usize {
+            self.saves.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl SessionStore for CountingStore {
+        fn create(&self, session: &GameSession) -> io::Result<()> {
+            self.inner.create(session)
+        }
+
+        fn load(&self, id: &str) -> io::Result<GameSession> {
+            self.inner.load(id)
+        }
+
+        fn save(&self, session: &GameSession) -> io::Result<()> {
+            self.saves.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.save(session)
+        }
+
+        fn list(&self) -> io::Result<Vec<String>> {
+            self.inner.list()
+        }
+
+        fn delete(&self, id: &str) -> io::Result<()> {
+            self.inner.delete(id)
+        }
+    }
+
+    #[test]
+    fn test_write_behind_coalesces_saves() {
+        let store = Arc::new(CountingStore::default());
+        let manager = SessionManager::with_store(store.clone()).with_write_behind();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let session = manager.create_session(game).unwrap();
+
+        for _ in 0..10 {
+            let mut session = manager.load_session(&session.id).unwrap();
+            session.game.draw_card(session.game.current_turn).unwrap();
+            manager.save_session(&session).unwrap();
+        }
+        assert_eq!(store.saves(), 0);
+        // Reads through the manager already see the latest moves
+        let latest = manager.load_session(&session.id).unwrap();
+        assert_eq!(latest.game.version, 10);
+
+        assert_eq!(manager.flush().unwrap(), 1);
+        assert_eq!(store.saves(), 1);
+        assert_eq!(store.load(&session.id).unwrap().game, latest.game);
+        assert_eq!(manager.flush().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_write_behind_listing_and_delete() {
+        let store = Arc::new(CountingStore::default());
+        let manager = SessionManager::with_store(store.clone()).with_write_behind();
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        let mut session = manager.create_session(game).unwrap();
+
+        // Listing flushes first, so summaries are current
+        session.game.status = GameStatus::Complete { winner_id: 1 };
+        manager.save_session(&session).unwrap();
+        let summaries = manager.list_sessions_with_meta().unwrap();
+        assert_eq!(summaries[0].status, GameStatus::Complete { winner_id: 1 });
+
+        // A deleted session isn't written back by a later flush
+        manager.save_session(&session).unwrap();
+        manager.delete_session(&session.id).unwrap();
+        assert_eq!(manager.flush().unwrap(), 0);
+        assert!(store.load(&session.id).is_err());
+    }
 }
@@ -0,0 +1,47 @@
+use super::card::Card;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Decides the order of the deck when a game is dealt.
+pub trait Shuffler {
+    fn shuffle(&mut self, cards: &mut Vec<Card>);
+}
+
+/// Shuffles with the thread RNG. Used unless a game asks for something else.
+#[derive(Debug, Default)]
+pub struct RandomShuffler;
+
+impl Shuffler for RandomShuffler {
+    fn shuffle(&mut self, cards: &mut Vec<Card>) {
+        cards.shuffle(&mut rand::rng());
+    }
+}
+
+/// Leaves the cards as they are, for stacked decks.
+#[derive(Debug, Default)]
+pub struct NoopShuffler;
+
+impl Shuffler for NoopShuffler {
+    fn shuffle(&mut self, _cards: &mut Vec<Card>) {}
+}
+
+/// Shuffles reproducibly: the same seed always gives the same sequence of orders.
+#[derive(Debug)]
+pub struct SeededShuffler {
+    rng: StdRng,
+}
+
+impl SeededShuffler {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Shuffler for SeededShuffler {
+    fn shuffle(&mut self, cards: &mut Vec<Card>) {
+        cards.shuffle(&mut self.rng);
+    }
+}
@@ -0,0 +1,519 @@
+use super::game::GameStatus;
+use super::session::{GameSession, SessionSummary};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Where game sessions are persisted. `SessionManager` works with any implementation.
+pub trait SessionStore: Send + Sync {
+    /// Stores a new session. Fails if one with the same id already exists.
+    fn create(&self, session: &GameSession) -> io::Result<()>;
+
+    fn load(&self, id: &str) -> io::Result<GameSession>;
+
+    /// Replaces the stored copy of an existing or new session.
+    fn save(&self, session: &GameSession) -> io::Result<()>;
+
+    /// Returns the ids of all stored sessions.
+    fn list(&self) -> io::Result<Vec<String>>;
+
+    /// Returns a summary of every stored session.
+    /// The default loads each session in full; stores can override it with something cheaper.
+    fn list_summaries(&self) -> io::Result<Vec<SessionSummary>> {
+        self.list()?
+            .iter()
+            .map(|id| self.load(id).map(|session| SessionSummary::from(&session)))
+            .collect()
+    }
+
+    fn delete(&self, id: &str) -> io::Result<()>;
+
+    /// Checks that the store can currently accept writes.
+    fn check_writable(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps each session as `{id}.json` in a directory. Files there that can't be read as
+/// sessions are moved to a `corrupt` subdirectory the next time sessions are listed.
+pub struct FileSessionStore {
+    dir: PathBuf,
+    read_only: bool,
+}
+
+impl FileSessionStore {
+    /// Uses `dir` for session files, creating it if needed.
+    pub fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            read_only: false,
+        })
+    }
+
+    /// Reads sessions from an existing `dir` without ever writing to it: creating, saving
+    /// and deleting fail, and unreadable files are skipped rather than moved.
+    pub fn read_only(dir: PathBuf) -> io::Result<Self> {
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not a directory", dir.display()),
+            ));
+        }
+        Ok(Self {
+            dir,
+            read_only: true,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn session_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    /// Reads the summary of every session file, quarantining the ones that can't be read.
+    fn scan(&self) -> io::Result<Vec<SessionSummary>> {
+        let mut summaries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let Some(id) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            let header = fs::read_to_string(entry.path()).and_then(|json| {
+                serde_json::from_str::<SessionHeader>(&json).map_err(io::Error::from)
+            });
+            match header {
+                Ok(header) if header.id == id => summaries.push(header.into()),
+                Ok(header) => self.quarantine(&id, format!("it holds session {}", header.id)),
+                Err(e) => self.quarantine(&id, e),
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Moves `{id}.json` into the `corrupt` subdirectory, out of the way of listings.
+    fn quarantine(&self, id: &str, reason: impl std::fmt::Display) {
+        if self.read_only {
+            warn!("Skipping unreadable session file {}.json: {}", id, reason);
+            return;
+        }
+        let corrupt_dir = self.dir.join(CORRUPT_DIR);
+        let file_name = format!("{}.json", id);
+        let moved = fs::create_dir_all(&corrupt_dir)
+            .and_then(|()| fs::rename(self.dir.join(&file_name), corrupt_dir.join(&file_name)));
+        match moved {
+            Ok(()) => warn!(
+                "Moved unreadable session file {} to {}/: {}",
+                file_name, CORRUPT_DIR, reason
+            ),
+            Err(e) => warn!(
+                "Skipping unreadable session file {} ({}); could not move it: {}",
+                file_name, reason, e
+            ),
+        }
+    }
+
+    fn check_not_read_only(&self) -> io::Result<()> {
+        if self.read_only {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is read-only", self.dir.display()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Subdirectory of the sessions directory that unreadable session files are moved to.
+const CORRUPT_DIR: &str = "corrupt";
+
+// Only the fields needed for a `SessionSummary`; serde skips everything else
+// in the file (hands, deck, discard pile) without building it.
+#[derive(Deserialize)]
+struct SessionHeader {
+    id: String,
+    game: GameHeader,
+    last_updated: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    turn_deadline: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize)]
+struct GameHeader {
+    players: Vec<PlayerHeader>,
+    current_turn: usize,
+    status: GameStatus,
+}
+
+#[derive(Deserialize)]
+struct PlayerHeader {
+    name: String,
+}
+
+impl From<SessionHeader> for SessionSummary {
+    fn from(header: SessionHeader) -> Self {
+        Self {
+            id: header.id,
+            player_names: header.game.players.into_iter().map(|p| p.name).collect(),
+            current_turn: header.game.current_turn,
+            status: header.game.status,
+            last_updated: header.last_updated,
+            turn_deadline: header.turn_deadline,
+        }
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn create(&self, session: &GameSession) -> io::Result<()> {
+        self.check_not_read_only()?;
+        if self.session_path(&session.id).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Session {} already exists", session.id),
+            ));
+        }
+        session.save(&self.dir)
+    }
+
+    fn load(&self, id: &str) -> io::Result<GameSession> {
+        GameSession::load(id, &self.dir)
+    }
+
+    fn save(&self, session: &GameSession) -> io::Result<()> {
+        self.check_not_read_only()?;
+        session.save(&self.dir)
+    }
+
+    fn list(&self) -> io::Result<Vec<String>> {
+        Ok(self.scan()?.into_iter().map(|summary| summary.id).collect())
+    }
+
+    fn list_summaries(&self) -> io::Result<Vec<SessionSummary>> {
+        self.scan()
+    }
+
+    fn delete(&self, id: &str) -> io::Result<()> {
+        self.check_not_read_only()?;
+        fs::remove_file(self.session_path(id))
+    }
+
+    fn check_writable(&self) -> io::Result<()> {
+        self.check_not_read_only()?;
+        // Not a `.json` file, so it is never listed as a session
+        let probe = self.dir.join(".ready-check");
+        fs::write(&probe, b"ok")?;
+        fs::remove_file(probe)
+    }
+}
+
+/// Writes to `primary`, but loads sessions `primary` doesn't have from `fallback`, e.g. a
+/// read-only directory of example games or a replica's snapshot. A fallback session
+/// that is saved is copied to `primary`, which wins from then on; the fallback copy is
+/// never changed. Listings include both, without duplicates.
+pub struct LayeredSessionStore {
+    primary: Arc<dyn SessionStore>,
+    fallback: Arc<dyn SessionStore>,
+}
+
+impl LayeredSessionStore {
+    pub fn new(primary: Arc<dyn SessionStore>, fallback: Arc<dyn SessionStore>) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+impl SessionStore for LayeredSessionStore {
+    fn create(&self, session: &GameSession) -> io::Result<()> {
+        if self.fallback.load(&session.id).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Session {} already exists", session.id),
+            ));
+        }
+        self.primary.create(session)
+    }
+
+    fn load(&self, id: &str) -> io::Result<GameSession> {
+        match self.primary.load(id) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => self.fallback.load(id),
+            result => result,
+        }
+    }
+
+    fn save(&self, session: &GameSession) -> io::Result<()> {
+        self.primary.save(session)
+    }
+
+    fn list(&self) -> io::Result<Vec<String>> {
+        let mut ids = self.primary.list()?;
+        for id in self.fallback.list()? {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    fn list_summaries(&self) -> io::Result<Vec<SessionSummary>> {
+        let mut summaries = self.primary.list_summaries()?;
+        for summary in self.fallback.list_summaries()? {
+            if !summaries.iter().any(|s| s.id == summary.id) {
+                summaries.push(summary);
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Deletes the copy in `primary`. A session only in `fallback` can't be deleted, and
+    /// one copied from it reverts to the fallback copy.
+    fn delete(&self, id: &str) -> io::Result<()> {
+        match self.primary.delete(id) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound && self.fallback.load(id).is_ok() => {
+                Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Session {} is read-only", id),
+                ))
+            }
+            result => result,
+        }
+    }
+
+    fn check_writable(&self) -> io::Result<()> {
+        self.primary.check_writable()
+    }
+}
+
+/// Keeps sessions in memory only, for tests and throwaway servers.
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, GameSession>>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn not_found(id: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("Session {} not found", id))
+}
+
+impl SessionStore for MemorySessionStore {
+    fn create(&self, session: &GameSession) -> io::Result<()> {
+        let mut sessions = self.sessions.lock().unwrap();
+        if sessions.contains_key(&session.id) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Session {} already exists", session.id),
+            ));
+        }
+        sessions.insert(session.id.clone(), session.clone());
+        Ok(())
+    }
+
+    fn load(&self, id: &str) -> io::Result<GameSession> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| not_found(id))
+    }
+
+    fn save(&self, session: &GameSession) -> io::Result<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id.clone(), session.clone());
+        Ok(())
+    }
+
+    fn list(&self) -> io::Result<Vec<String>> {
+        Ok(self.sessions.lock().unwrap().keys().cloned().collect())
+    }
+
+    fn delete(&self, id: &str) -> io::Result<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| not_found(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uno_game::UnoGame;
+    use tempfile::tempdir;
+
+    fn new_session(id: &str) -> GameSession {
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        GameSession::new(id.to_string(), game)
+    }
+
+    // The same behavior is expected of every store
+    fn exercise_store(store: &dyn SessionStore) {
+        let mut session = new_session("one");
+        store.create(&session).unwrap();
+        assert_eq!(
+            store.create(&session).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        store.create(&new_session("two")).unwrap();
+
+        let loaded = store.load("one").unwrap();
+        assert_eq!(loaded.game, session.game);
+
+        session.game.draw_card(0).unwrap();
+        store.save(&session).unwrap();
+        assert_eq!(store.load("one").unwrap().game, session.game);
+
+        let mut ids = store.list().unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["one", "two"]);
+
+        let summaries = store.list_summaries().unwrap();
+        let summary = summaries.iter().find(|s| s.id == "one").unwrap();
+        assert_eq!(summary.player_names, vec!["Alice", "Bob"]);
+        assert_eq!(summary.current_turn, 1);
+
+        store.delete("one").unwrap();
+        assert_eq!(
+            store.load("one").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(store.delete("one").is_err());
+        assert_eq!(store.list().unwrap(), vec!["two"]);
+    }
+
+    #[test]
+    fn test_file_session_store() {
+        let temp_dir = tempdir().unwrap();
+        let store = FileSessionStore::new(temp_dir.path().join("sessions")).unwrap();
+        exercise_store(&store);
+    }
+
+    #[test]
+    fn test_file_session_store_skips_and_quarantines_junk() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path().join("sessions");
+        let store = FileSessionStore::new(dir.clone()).unwrap();
+        store.create(&new_session("good")).unwrap();
+        fs::write(dir.join("junk.json"), "not json at all").unwrap();
+        fs::write(dir.join("array.json"), "[1, 2, 3]").unwrap();
+        fs::write(dir.join("notes.txt"), "hello").unwrap();
+        fs::create_dir(dir.join("folder.json")).unwrap();
+        let mut renamed = new_session("elsewhere");
+        renamed.id = "other".to_string();
+        fs::write(
+            dir.join("elsewhere.json"),
+            serde_json::to_string(&renamed).unwrap(),
+        )
+        .unwrap();
+
+        for id in ["junk", "array"] {
+            let err = store.load(id).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err
+                .to_string()
+                .contains(&format!("Session {} is corrupt", id)));
+        }
+
+        assert_eq!(store.list().unwrap(), vec!["good"]);
+        assert_eq!(store.list_summaries().unwrap().len(), 1);
+        for file in ["junk.json", "array.json", "elsewhere.json"] {
+            assert!(!dir.join(file).exists());
+            assert!(dir.join(CORRUPT_DIR).join(file).exists());
+        }
+        assert!(dir.join("notes.txt").exists());
+        assert!(dir.join("folder.json").is_dir());
+        assert_eq!(
+            store.load("junk").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_read_only_file_store_never_writes() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path().join("fixtures");
+        FileSessionStore::new(dir.clone())
+            .unwrap()
+            .create(&new_session("one"))
+            .unwrap();
+        fs::write(dir.join("junk.json"), "not json at all").unwrap();
+
+        let store = FileSessionStore::read_only(dir.clone()).unwrap();
+        assert_eq!(store.list().unwrap(), vec!["one"]);
+        assert!(dir.join("junk.json").exists());
+        let session = store.load("one").unwrap();
+        for result in [
+            store.save(&session),
+            store.create(&new_session("two")),
+            store.delete("one"),
+            store.check_writable(),
+        ] {
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        }
+        assert!(FileSessionStore::read_only(temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_layered_session_store() {
+        exercise_store(&LayeredSessionStore::new(
+            Arc::new(MemorySessionStore::new()),
+            Arc::new(MemorySessionStore::new()),
+        ));
+
+        let fallback = Arc::new(MemorySessionStore::new());
+        fallback.create(&new_session("fixture")).unwrap();
+        let store = LayeredSessionStore::new(Arc::new(MemorySessionStore::new()), fallback.clone());
+        store.create(&new_session("own")).unwrap();
+        let mut ids = store.list().unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["fixture", "own"]);
+        assert_eq!(
+            store.create(&new_session("fixture")).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            store.delete("fixture").unwrap_err().kind(),
+            io::ErrorKind::PermissionDenied
+        );
+
+        // A saved copy hides the fallback one until it is deleted
+        let mut session = store.load("fixture").unwrap();
+        session.game.draw_card(0).unwrap();
+        store.save(&session).unwrap();
+        assert_eq!(store.load("fixture").unwrap().game, session.game);
+        assert_eq!(store.list_summaries().unwrap().len(), 2);
+        assert_ne!(fallback.load("fixture").unwrap().game, session.game);
+        store.delete("fixture").unwrap();
+        assert_eq!(
+            store.load("fixture").unwrap().game,
+            fallback.load("fixture").unwrap().game
+        );
+    }
+
+    #[test]
+    fn test_memory_session_store() {
+        exercise_store(&MemorySessionStore::new());
+    }
+}
@@ -1,5 +1,5 @@
-use super::card::{Card, CardType, Color};
-use super::game::{GameEvent, UnoGame};
+use super::card::{Card, Color};
+use super::game::{GameEvent, ReshuffleLog, UnoGame};
 use std::io::{self, BufRead, BufReader, Write};
 
 pub struct ConsoleUI {
@@ -7,6 +7,12 @@ pub struct ConsoleUI {
     output: Box<dyn Write>,
 }
 
+impl Default for ConsoleUI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ConsoleUI {
     pub fn new() -> Self {
         Self {
@@ -20,6 +26,11 @@ impl ConsoleUI {
     }
 
     pub fn get_player_names(&mut self) -> Vec<String> {
+        self.get_player_names_min(2)
+    }
+
+    /// Asks for player names until the user finishes with '.', requiring at least `min` names.
+    pub fn get_player_names_min(&mut self, min: usize) -> Vec<String> {
         let mut player_names = Vec::new();
         loop {
             write!(self.output, "Enter player name (or '.' to finish): ").unwrap();
@@ -30,10 +41,11 @@ impl ConsoleUI {
             let name = name.trim().to_string();
 
             if name.to_lowercase() == "." {
-                if player_names.len() < 2 {
+                if player_names.len() < min {
                     writeln!(
                         self.output,
-                        "You need at least 2 players to start the game."
+                        "You need at least {} players to start the game.",
+                        min
                     )
                     .unwrap();
                     continue;
@@ -48,14 +60,7 @@ impl ConsoleUI {
 
     pub fn display_game_state(&mut self, game: &UnoGame) {
         writeln!(self.output, "\n--- Game State ---").unwrap();
-        writeln!(self.output, "Direction: {:?}", game.direction).unwrap();
-        writeln!(
-            self.output,
-            "Discard Pile Top Card: {:?}",
-            game.discard_pile.last().unwrap()
-        )
-        .unwrap();
-        writeln!(self.output, "Deck Cards Remaining: {}", game.deck.len()).unwrap();
+        write!(self.output, "{}", game.render_board()).unwrap();
 
         // Show pending draws if any
         if game.pending_draws > 0 {
@@ -66,12 +71,20 @@ impl ConsoleUI {
             )
             .unwrap();
         }
+        if game.pending_skips > 0 {
+            writeln!(
+                self.output,
+                "⚠️  Current player must play a Skip or lose {} turns!",
+                game.pending_skips
+            )
+            .unwrap();
+        }
     }
 
     pub fn display_player_hand(&mut self, player_name: &str, hand: &[Card]) {
         writeln!(self.output, "\nPlayer {}'s hand:", player_name).unwrap();
         for (i, card) in hand.iter().enumerate() {
-            writeln!(self.output, "{}. {:?}", i, card).unwrap();
+            writeln!(self.output, "{}. {}", i, card).unwrap();
         }
     }
 
@@ -87,6 +100,28 @@ impl ConsoleUI {
         choice.trim().to_string()
     }
 
+    /// Asks whether to keep playing after a turn.
+    /// Returns false when the user chooses to save and return to the menu.
+    pub fn prompt_continue(&mut self) -> bool {
+        loop {
+            writeln!(self.output, "\n1. Continue playing").unwrap();
+            writeln!(self.output, "2. Save and return to menu").unwrap();
+            write!(self.output, "Enter your choice: ").unwrap();
+            self.output.flush().unwrap();
+
+            let mut choice = String::new();
+            if self.input.read_line(&mut choice).unwrap() == 0 {
+                return false;
+            }
+
+            match choice.trim() {
+                "1" => return true,
+                "2" => return false,
+                _ => writeln!(self.output, "Invalid choice. Please enter 1 or 2.").unwrap(),
+            }
+        }
+    }
+
     pub fn get_card_index(&mut self) -> Result<(usize, Option<Color>), String> {
         write!(
             self.output,
@@ -105,23 +140,39 @@ impl ConsoleUI {
         Ok((index, None))
     }
 
-    pub fn get_card_play(&mut self, card: &Card) -> Result<(usize, Option<Color>), String> {
+    /// Asks which card in `hand` to play, and for a color if it is a wild card.
+    /// The card can be given by its index or described, as in "red 5" or "skip"; a
+    /// description picks the first matching card. Returns an error if the hand is
+    /// empty, the index is out of range or no card in the hand matches.
+    pub fn get_card_play(&mut self, hand: &[Card]) -> Result<(usize, Option<Color>), String> {
+        if hand.is_empty() {
+            return Err("You have no cards to play.".to_string());
+        }
+
         write!(
             self.output,
-            "Enter the index of the card you want to play: "
+            "Enter the index of the card you want to play, or the card (e.g. red 5): "
         )
         .unwrap();
         self.output.flush().unwrap();
 
-        let mut index = String::new();
-        self.input.read_line(&mut index).unwrap();
-        let index = index
-            .trim()
-            .parse::<usize>()
-            .map_err(|_| "Invalid input. Please enter a number.".to_string())?;
+        let mut input = String::new();
+        self.input.read_line(&mut input).unwrap();
+        let index = match input.trim().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => find_in_hand(hand, input.trim())?,
+        };
+
+        let card = hand.get(index).ok_or_else(|| {
+            format!(
+                "Invalid card index {}. Please enter a number from 0 to {}.",
+                index,
+                hand.len() - 1
+            )
+        })?;
 
         // If the card is a Wild or Wild Draw Four, get the color choice
-        if matches!(card.card_type, CardType::Wild | CardType::WildDrawFour) {
+        if card.is_wild() {
             let color = self.choose_color();
             Ok((index, Some(color)))
         } else {
@@ -131,102 +182,66 @@ impl ConsoleUI {
 
     pub fn choose_color(&mut self) -> Color {
         loop {
+            let colors = Color::playable();
             writeln!(self.output, "Choose a color:").unwrap();
-            writeln!(self.output, "1. Red").unwrap();
-            writeln!(self.output, "2. Green").unwrap();
-            writeln!(self.output, "3. Blue").unwrap();
-            writeln!(self.output, "4. Yellow").unwrap();
+            for (i, color) in colors.iter().enumerate() {
+                writeln!(self.output, "{}. {}", i + 1, color).unwrap();
+            }
             write!(self.output, "Enter your choice: ").unwrap();
             self.output.flush().unwrap();
 
             let mut choice = String::new();
             self.input.read_line(&mut choice).unwrap();
-            let choice = choice.trim();
 
-            match choice {
-                "1" => return Color::Red,
-                "2" => return Color::Green,
-                "3" => return Color::Blue,
-                "4" => return Color::Yellow,
+            match choice.trim().parse::<usize>() {
+                Ok(n) if (1..=colors.len()).contains(&n) => return colors[n - 1],
                 _ => writeln!(self.output, "Invalid choice. Please enter 1, 2, 3, or 4.").unwrap(),
             }
         }
     }
 
     pub fn handle_game_event(&mut self, event: &GameEvent, game: &UnoGame) {
-        match event {
-            GameEvent::CardPlayed {
-                player_id: _,
-                card,
-                player_name,
-            } => {
-                writeln!(self.output, "Player {} played {:?}", player_name, card).unwrap();
-            }
-            GameEvent::CardDrawn { player_id, card } => {
-                writeln!(
-                    self.output,
-                    "Player {} drew {:?}",
-                    game.players[*player_id].name, card
-                )
-                .unwrap();
-            }
-            GameEvent::Skip { player_id: _ } => {
-                writeln!(self.output, "Next player is skipped!").unwrap();
-            }
-            GameEvent::Reverse => {
-                writeln!(self.output, "Direction reversed!").unwrap();
-            }
-            GameEvent::DrawTwo { player_id, cards } => {
-                writeln!(
-                    self.output,
-                    "Player {} draws 2 cards: {:?}",
-                    game.players[*player_id].name, cards
-                )
-                .unwrap();
-            }
-            GameEvent::WildColorChosen { player_id, color } => {
-                writeln!(
-                    self.output,
-                    "Player {} chose color {:?}",
-                    game.players[*player_id].name, color
-                )
-                .unwrap();
-            }
-            GameEvent::WildDrawFour {
-                player_id,
-                next_player_id,
-                cards,
-                color,
-            } => {
-                writeln!(
-                    self.output,
-                    "Player {} played Wild Draw Four! Player {} draws 4 cards: {:?}",
-                    game.players[*player_id].name, game.players[*next_player_id].name, cards
-                )
-                .unwrap();
-                writeln!(
-                    self.output,
-                    "Player {} chose color {:?}",
-                    game.players[*player_id].name, color
-                )
-                .unwrap();
-            }
-            GameEvent::PlayerWins { player_id } => {
-                writeln!(
-                    self.output,
-                    "Player {} has won the game!",
-                    game.players[*player_id].name
-                )
-                .unwrap();
-            }
+        writeln!(self.output, "{}", game.describe_event(event)).unwrap();
+    }
+
+    /// Shows a move's event, after any reshuffles the move caused.
+    pub fn handle_move(&mut self, reshuffles: &ReshuffleLog, event: &GameEvent, game: &UnoGame) {
+        for reshuffle in reshuffles.take() {
+            self.handle_game_event(&reshuffle, game);
         }
+        self.handle_game_event(event, game);
     }
 }
 
+/// The index of the first card in `hand` matching `description`. A description without
+/// a color, like "skip" or "draw two", matches that card in any color.
+fn find_in_hand(hand: &[Card], description: &str) -> Result<usize, String> {
+    let (wanted, any_color) = match description.parse::<Card>() {
+        Ok(card) => (card, false),
+        // Any color will do to parse the rest; only the card type is compared
+        Err(_) => {
+            let card = format!("red {}", description)
+                .parse::<Card>()
+                .map_err(|_| "Invalid input. Please enter a number or a card like \"red 5\".")?;
+            (card, true)
+        }
+    };
+    hand.iter()
+        .position(|card| {
+            if any_color {
+                card.card_type == wanted.card_type
+            } else {
+                *card == wanted
+            }
+        })
+        .ok_or_else(|| format!("You have no {} in your hand.", description))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::uno_game::card::CardType;
+    use crate::uno_game::game::Direction;
     use crate::uno_game::player::Player;
     use std::io::Cursor;
 
@@ -234,6 +249,27 @@ mod tests {
         ConsoleUI::new()
     }
 
+    /// An output stream the test can read back after handing it to the UI.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_display_game_state() {
         let mut ui = create_test_ui();
@@ -244,6 +280,22 @@ mod tests {
         ui.display_game_state(&game);
     }
 
+    #[test]
+    fn test_display_game_state_shows_board() {
+        let output = SharedBuffer::default();
+        let mut ui = ConsoleUI::with_streams(Box::new(Cursor::new("")), Box::new(output.clone()));
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+
+        ui.display_game_state(&game);
+
+        let text = output.contents();
+        assert!(text.contains("> Alice (7 cards)"));
+        assert!(text.contains(&format!(
+            "Top card: {}",
+            game.discard_pile.last().unwrap().0
+        )));
+    }
+
     #[test]
     fn test_display_player_hand() {
         let mut ui = create_test_ui();
@@ -302,6 +354,11 @@ mod tests {
                 card: Card::new(Color::Red, CardType::Number(1)),
                 player_name: "Alice".to_string(),
             },
+            GameEvent::CardDrawnPlayable {
+                player_id: 0,
+                card: Card::new(Color::Red, CardType::Number(2)),
+                index: 7,
+            },
             GameEvent::Skip { player_id: 1 },
             GameEvent::Reverse,
             GameEvent::DrawTwo {
@@ -319,6 +376,22 @@ mod tests {
                 color: Color::Red,
             },
             GameEvent::PlayerWins { player_id: 0 },
+            GameEvent::PlayerForfeited { player_id: 1 },
+            GameEvent::HandSwap {
+                player_id: 0,
+                target_player_id: 1,
+            },
+            GameEvent::HandRotate {
+                direction: Direction::Clockwise,
+            },
+            GameEvent::Stalemate { winner_id: 1 },
+            GameEvent::TurnPassed { player_id: 0 },
+            GameEvent::ChallengeResult {
+                success: true,
+                drawer_id: 0,
+                cards: vec![Card::new(Color::Green, CardType::Number(1))],
+            },
+            GameEvent::DeckReshuffled { cards_recycled: 12 },
         ];
 
         // Verify that handle_game_event doesn't panic for any event type
@@ -326,4 +399,122 @@ mod tests {
             ui.handle_game_event(&event, &game);
         }
     }
+
+    #[test]
+    fn test_handle_game_event_prints_description() {
+        let output = SharedBuffer::default();
+        let mut ui = ConsoleUI::with_streams(Box::new(Cursor::new("")), Box::new(output.clone()));
+        let game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+
+        ui.handle_game_event(&GameEvent::TurnPassed { player_id: 1 }, &game);
+
+        assert_eq!(output.contents(), "Bob passed.\n");
+    }
+
+    #[test]
+    fn test_handle_move_shows_reshuffles_first() {
+        let output = SharedBuffer::default();
+        let mut ui = ConsoleUI::with_streams(Box::new(Cursor::new("")), Box::new(output.clone()));
+        let mut game = UnoGame::new(vec!["Alice".to_string(), "Bob".to_string()]).unwrap();
+        game.deck.clear();
+        game.discard_pile = vec![
+            (Card::new(Color::Blue, CardType::Number(4)), 1),
+            (Card::new(Color::Red, CardType::Number(9)), 0),
+        ];
+        let reshuffles = ReshuffleLog::attach(&mut game);
+
+        let event = game.draw_card(game.current_turn).unwrap();
+        ui.handle_move(&reshuffles, &event, &game);
+
+        let contents = output.contents();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "The deck ran out; 1 card from the discard pile shuffled back in."
+        );
+        assert!(reshuffles.take().is_empty());
+    }
+
+    #[test]
+    fn test_display_player_hand_uses_card_text() {
+        let output = SharedBuffer::default();
+        let mut ui = ConsoleUI::with_streams(Box::new(Cursor::new("")), Box::new(output.clone()));
+        let hand = vec![
+            Card::new(Color::Red, CardType::Number(5)),
+            Card::new(Color::Wild, CardType::WildDrawFour),
+        ];
+
+        ui.display_player_hand("Alice", &hand);
+
+        let text = output.contents();
+        assert!(text.contains("0. Red 5"));
+        assert!(text.contains("1. Wild Draw Four"));
+    }
+
+    #[test]
+    fn test_get_card_play_out_of_range() {
+        let hand = vec![
+            Card::new(Color::Red, CardType::Number(5)),
+            Card::new(Color::Blue, CardType::Skip),
+        ];
+        let input = Cursor::new("2\n");
+        let mut ui = ConsoleUI::with_streams(Box::new(input), Box::new(Vec::new()));
+
+        let result = ui.get_card_play(&hand);
+        assert_eq!(
+            result,
+            Err("Invalid card index 2. Please enter a number from 0 to 1.".to_string())
+        );
+
+        let mut ui = ConsoleUI::with_streams(Box::new(Cursor::new("0\n")), Box::new(Vec::new()));
+        assert!(ui.get_card_play(&[]).is_err());
+    }
+
+    #[test]
+    fn test_get_card_play_asks_color_for_chosen_wild() {
+        let hand = vec![
+            Card::new(Color::Red, CardType::Number(5)),
+            Card::new(Color::Wild, CardType::Wild),
+        ];
+
+        // The first card isn't wild, so no color is asked for
+        let input = Cursor::new("0\n");
+        let mut ui = ConsoleUI::with_streams(Box::new(input), Box::new(Vec::new()));
+        assert_eq!(ui.get_card_play(&hand), Ok((0, None)));
+
+        // The chosen card is wild, so the color prompt follows
+        let input = Cursor::new("1\n3\n");
+        let mut ui = ConsoleUI::with_streams(Box::new(input), Box::new(Vec::new()));
+        assert_eq!(ui.get_card_play(&hand), Ok((1, Some(Color::Blue))));
+    }
+
+    #[test]
+    fn test_get_card_play_by_description() {
+        let hand = vec![
+            Card::new(Color::Red, CardType::Number(5)),
+            Card::new(Color::Blue, CardType::Skip),
+            Card::new(Color::Green, CardType::Skip),
+            Card::new(Color::Wild, CardType::WildDrawFour),
+        ];
+        let play = |input: &'static str| {
+            let mut ui =
+                ConsoleUI::with_streams(Box::new(Cursor::new(input)), Box::new(Vec::new()));
+            ui.get_card_play(&hand)
+        };
+
+        assert_eq!(play("Red 5\n"), Ok((0, None)));
+        assert_eq!(play("green  skip\n"), Ok((2, None)));
+        // Without a color, the first card of that kind
+        assert_eq!(play("skip\n"), Ok((1, None)));
+        assert_eq!(play("wild draw four\n2\n"), Ok((3, Some(Color::Green))));
+        // Numbers are still indices
+        assert_eq!(play("1\n"), Ok((1, None)));
+
+        assert_eq!(
+            play("blue 5\n"),
+            Err("You have no blue 5 in your hand.".to_string())
+        );
+        assert!(play("banana\n").is_err());
+    }
 }